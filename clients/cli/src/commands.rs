@@ -0,0 +1,361 @@
+use crate::config::{CommandResult, Config};
+use solana_program::{hash::Hash, pubkey::Pubkey};
+use solana_sdk::signature::{Keypair, Signer};
+use std::{fs, path::Path};
+use token_recipes_client::{
+    accounts::{fetch_program_account, ProgramAccount},
+    craft::{fetch_recipe, resolve_craft},
+    error::ClientError,
+    fetcher::AccountFetcher,
+    spec::{plan_recipe, RecipeSpec},
+    token_recipes::{
+        instruction::{
+            activate_recipe, add_ingredient, collect_experience, collect_fees, create_recipe,
+            delete_recipe, migrate_account, pause_recipe, remove_ingredient, set_fees,
+            unlock_feature,
+        },
+        pda::{
+            find_additional_outputs_feature_pda, find_fees_feature_pda,
+            find_max_supply_feature_pda, find_recipe_pda, find_sol_payment_feature_pda,
+            find_transfer_inputs_feature_pda, find_wisdom_feature_pda,
+        },
+        state::{
+            features::{fees::FeesFeature, wisdom::WisdomFeature, Feature},
+            recipe::IngredientType,
+        },
+    },
+};
+
+pub fn process_create(config: &Config, base: Option<Box<dyn Signer>>) -> CommandResult {
+    let base = base.unwrap_or_else(|| Box::new(Keypair::new()));
+    let authority = config.authority().pubkey();
+    let (recipe, _) = find_recipe_pda(&base.pubkey());
+    println!("Creating recipe {}", recipe);
+    config.send(
+        &[create_recipe(
+            &base.pubkey(),
+            &authority,
+            &config.payer.pubkey(),
+        )],
+        &[base.as_ref()],
+    )?;
+    Ok(())
+}
+
+pub fn process_add_ingredient(
+    config: &Config,
+    recipe: &Pubkey,
+    ingredient_type: IngredientType,
+    mint: Option<Pubkey>,
+    amount: u64,
+    destination: Option<Pubkey>,
+    max_supply: Option<u64>,
+) -> CommandResult {
+    assert_mint_provided(&ingredient_type, mint.as_ref())?;
+    let token_program = token_program(config, mint.as_ref())?;
+    config.send(
+        &[add_ingredient(
+            recipe,
+            mint.as_ref(),
+            &config.authority().pubkey(),
+            &config.payer.pubkey(),
+            &token_program,
+            ingredient_type,
+            amount,
+            destination,
+            max_supply,
+        )],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_remove_ingredient(
+    config: &Config,
+    recipe: &Pubkey,
+    ingredient_type: IngredientType,
+    mint: Option<Pubkey>,
+) -> CommandResult {
+    assert_mint_provided(&ingredient_type, mint.as_ref())?;
+    let token_program = token_program(config, mint.as_ref())?;
+    config.send(
+        &[remove_ingredient(
+            recipe,
+            mint.as_ref(),
+            &config.authority().pubkey(),
+            &config.payer.pubkey(),
+            &token_program,
+            ingredient_type,
+        )],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_activate(config: &Config, recipe: &Pubkey) -> CommandResult {
+    config.send(
+        &[activate_recipe(recipe, &config.authority().pubkey())],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_pause(config: &Config, recipe: &Pubkey) -> CommandResult {
+    config.send(&[pause_recipe(recipe, &config.authority().pubkey())], &[])?;
+    Ok(())
+}
+
+pub fn process_craft(
+    config: &Config,
+    recipe: &Pubkey,
+    quantity: u64,
+    proof: Vec<Hash>,
+) -> CommandResult {
+    let instruction = resolve_craft(
+        &config.rpc_client,
+        recipe,
+        &config.authority().pubkey(),
+        &config.payer.pubkey(),
+        quantity,
+        proof.iter().map(|hash| hash.to_bytes()).collect(),
+    )?;
+    config.send(&[instruction], &[])?;
+    Ok(())
+}
+
+pub fn process_unlock_feature(
+    config: &Config,
+    recipe: &Pubkey,
+    feature: &str,
+    mint: &Pubkey,
+) -> CommandResult {
+    let token_program = token_program(config, Some(mint))?;
+    let authority = config.authority().pubkey();
+    config.send(
+        &[unlock_feature(
+            recipe,
+            &feature_pda(feature),
+            &authority,
+            &authority,
+            mint,
+            &token_program,
+        )],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_set_fees(config: &Config, recipe: &Pubkey, fees: u64) -> CommandResult {
+    config.send(&[set_fees(recipe, &config.authority().pubkey(), fees)], &[])?;
+    Ok(())
+}
+
+pub fn process_collect_fees(config: &Config, recipe: &Pubkey) -> CommandResult {
+    let fees_feature = fetch_fees_feature(config)?;
+    config.send(
+        &[collect_fees(
+            recipe,
+            &config.authority().pubkey(),
+            &config.payer.pubkey(),
+            &fees_feature.admin_destination,
+            &fees_feature.shard_mint,
+        )],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_collect_experience(config: &Config, recipe: &Pubkey) -> CommandResult {
+    let wisdom_feature = fetch_wisdom_feature(config)?;
+    config.send(
+        &[collect_experience(
+            recipe,
+            &config.authority().pubkey(),
+            &config.payer.pubkey(),
+            &wisdom_feature.experience_mint,
+        )],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_delete(config: &Config, recipe: &Pubkey) -> CommandResult {
+    let fees_feature = fetch_fees_feature(config)?;
+    let wisdom_feature = fetch_wisdom_feature(config)?;
+    config.send(
+        &[delete_recipe(
+            recipe,
+            &config.authority().pubkey(),
+            &config.payer.pubkey(),
+            &fees_feature.admin_destination,
+            &fees_feature.shard_mint,
+            &wisdom_feature.experience_mint,
+        )],
+        &[],
+    )?;
+    Ok(())
+}
+
+pub fn process_show(config: &Config, address: &Pubkey) -> CommandResult {
+    match fetch_program_account(&config.rpc_client, address)? {
+        ProgramAccount::Recipe(account) => println!("{:#?}", account),
+        ProgramAccount::IngredientRecord(account) => println!("{:#?}", account),
+        ProgramAccount::DelegatedIngredient(account) => println!("{:#?}", account),
+        ProgramAccount::CrafterRecord(account) => println!("{:#?}", account),
+        ProgramAccount::Feature(account) => println!("{:#?}", account),
+        ProgramAccount::ProgramConfig(account) => println!("{:#?}", account),
+        ProgramAccount::LootRoll(account) => println!("{:#?}", account),
+    }
+    Ok(())
+}
+
+pub fn process_show_features(config: &Config) -> CommandResult {
+    for feature in FEATURES {
+        let address = feature_pda(feature);
+        println!("{} feature ({})", feature, address);
+        match fetch_program_account(&config.rpc_client, &address) {
+            Ok(account) => println!("{:#?}", account),
+            Err(ClientError::AccountNotFound(_)) => println!("Not initialized"),
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(())
+}
+
+pub fn process_migrate(config: &Config, address: &Pubkey) -> CommandResult {
+    config.send(&[migrate_account(address, &config.payer.pubkey())], &[])?;
+    Ok(())
+}
+
+pub fn process_plan(config: &Config, recipe: &Pubkey, spec_path: &str) -> CommandResult {
+    let plan = plan_recipe(
+        &config.rpc_client,
+        recipe,
+        &read_spec(spec_path)?,
+        &config.authority().pubkey(),
+        &config.payer.pubkey(),
+    )?;
+    if plan.is_empty() {
+        println!("Recipe {} already matches the spec", recipe);
+    }
+    for step in &plan.steps {
+        println!("- {}", step.description);
+    }
+    Ok(())
+}
+
+pub fn process_apply(config: &Config, recipe: &Pubkey, spec_path: &str) -> CommandResult {
+    let plan = plan_recipe(
+        &config.rpc_client,
+        recipe,
+        &read_spec(spec_path)?,
+        &config.authority().pubkey(),
+        &config.payer.pubkey(),
+    )?;
+    if plan.is_empty() {
+        println!("Recipe {} already matches the spec", recipe);
+    }
+    for step in plan.steps {
+        println!("{}", step.description);
+        config.send(&[step.instruction], &[])?;
+    }
+    Ok(())
+}
+
+pub fn process_export(
+    config: &Config,
+    recipe: &Pubkey,
+    output_path: Option<&str>,
+    json: bool,
+) -> CommandResult {
+    let spec = RecipeSpec::from(&fetch_recipe(&config.rpc_client, recipe)?);
+    let json = json || matches!(output_path, Some(path) if is_json(path));
+    let content = if json {
+        spec.to_json()?
+    } else {
+        spec.to_toml()?
+    };
+    match output_path {
+        Some(path) => fs::write(path, content)?,
+        None => println!("{}", content),
+    }
+    Ok(())
+}
+
+pub const FEATURES: [&str; 6] = [
+    "fees",
+    "additional-outputs",
+    "transfer-inputs",
+    "max-supply",
+    "sol-payment",
+    "wisdom",
+];
+
+fn feature_pda(feature: &str) -> Pubkey {
+    match feature {
+        "fees" => find_fees_feature_pda(),
+        "additional-outputs" => find_additional_outputs_feature_pda(),
+        "transfer-inputs" => find_transfer_inputs_feature_pda(),
+        "max-supply" => find_max_supply_feature_pda(),
+        "sol-payment" => find_sol_payment_feature_pda(),
+        "wisdom" => find_wisdom_feature_pda(),
+        _ => unreachable!(),
+    }
+    .0
+}
+
+fn fetch_fees_feature(config: &Config) -> Result<FeesFeature, ClientError> {
+    match fetch_program_account(&config.rpc_client, &find_fees_feature_pda().0)? {
+        ProgramAccount::Feature(Feature::Fees(feature)) => Ok(feature),
+        _ => Err(ClientError::InvalidAccountData(
+            find_fees_feature_pda().0,
+            "not a fees feature".to_string(),
+        )),
+    }
+}
+
+fn fetch_wisdom_feature(config: &Config) -> Result<WisdomFeature, ClientError> {
+    match fetch_program_account(&config.rpc_client, &find_wisdom_feature_pda().0)? {
+        ProgramAccount::Feature(Feature::Wisdom(feature)) => Ok(feature),
+        _ => Err(ClientError::InvalidAccountData(
+            find_wisdom_feature_pda().0,
+            "not a wisdom feature".to_string(),
+        )),
+    }
+}
+
+fn assert_mint_provided(
+    ingredient_type: &IngredientType,
+    mint: Option<&Pubkey>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match (ingredient_type, mint) {
+        (IngredientType::TransferSolInput, _) | (_, Some(_)) => Ok(()),
+        _ => Err(format!("--mint is required for {:?} ingredients", ingredient_type).into()),
+    }
+}
+
+/// The token program owning the mint, or SPL Token for ingredients without a mint.
+fn token_program(config: &Config, mint: Option<&Pubkey>) -> Result<Pubkey, ClientError> {
+    match mint {
+        Some(mint) => Ok(AccountFetcher::get_account(&config.rpc_client, mint)?
+            .ok_or(ClientError::AccountNotFound(*mint))?
+            .owner),
+        None => Ok(spl_token::id()),
+    }
+}
+
+fn read_spec(path: &str) -> Result<RecipeSpec, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    Ok(if is_json(path) {
+        RecipeSpec::from_json(&content)?
+    } else {
+        RecipeSpec::from_toml(&content)?
+    })
+}
+
+fn is_json(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        == Some("json")
+}
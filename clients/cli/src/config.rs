@@ -0,0 +1,57 @@
+use solana_client::rpc_client::RpcClient;
+use solana_program::instruction::Instruction;
+use solana_sdk::{
+    message::Message,
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+use std::error::Error;
+
+pub type CommandResult = Result<(), Box<dyn Error>>;
+
+pub struct Config {
+    pub rpc_client: RpcClient,
+    pub payer: Box<dyn Signer>,
+    /// The recipe authority and ingredient owner, defaults to the payer.
+    pub authority: Option<Box<dyn Signer>>,
+}
+
+impl Config {
+    pub fn authority(&self) -> &dyn Signer {
+        self.authority.as_deref().unwrap_or(self.payer.as_ref())
+    }
+
+    /// Sign and send a transaction with the payer, the authority and the given signers.
+    /// Signers that are not required by the instructions are ignored.
+    pub fn send(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+    ) -> Result<Signature, Box<dyn Error>> {
+        let message = Message::new(instructions, Some(&self.payer.pubkey()));
+        let required_signers = message.signer_keys();
+        let mut transaction_signers: Vec<&dyn Signer> = vec![];
+        for signer in [self.payer.as_ref(), self.authority()]
+            .into_iter()
+            .chain(signers.iter().copied())
+        {
+            let pubkey = signer.pubkey();
+            if required_signers.contains(&&pubkey)
+                && !transaction_signers
+                    .iter()
+                    .any(|existing| existing.pubkey() == pubkey)
+            {
+                transaction_signers.push(signer);
+            }
+        }
+
+        let blockhash = self.rpc_client.get_latest_blockhash()?;
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.try_sign(&transaction_signers, blockhash)?;
+        let signature = self
+            .rpc_client
+            .send_and_confirm_transaction_with_spinner(&transaction)?;
+        println!("Signature: {}", signature);
+        Ok(signature)
+    }
+}
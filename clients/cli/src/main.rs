@@ -0,0 +1,431 @@
+mod commands;
+mod config;
+
+use crate::{
+    commands::*,
+    config::{CommandResult, Config},
+};
+use clap::{
+    crate_description, crate_name, crate_version, value_t, value_t_or_exit, values_t, App,
+    AppSettings, Arg, ArgMatches, SubCommand,
+};
+use solana_clap_utils::{
+    input_parsers::{pubkey_of, signer_of},
+    input_validators::{is_hash, is_parsable, is_url_or_moniker, is_valid_pubkey, is_valid_signer},
+    keypair::signer_from_path,
+};
+use solana_cli_config::ConfigInput;
+use solana_client::rpc_client::RpcClient;
+use solana_program::{hash::Hash, pubkey::Pubkey};
+use std::process::exit;
+use token_recipes_client::token_recipes::state::recipe::IngredientType;
+
+const INGREDIENT_TYPES: [&str; 7] = [
+    "burn-token-input",
+    "transfer-token-input",
+    "transfer-sol-input",
+    "hold-token-input",
+    "mint-token-output",
+    "mint-token-with-max-supply-output",
+    "transfer-token-output",
+];
+
+fn recipe_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("recipe")
+        .value_name("RECIPE_ADDRESS")
+        .validator(is_valid_pubkey)
+        .required(true)
+        .help("The address of the recipe")
+}
+
+fn ingredient_type_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("ingredient_type")
+        .value_name("INGREDIENT_TYPE")
+        .possible_values(&INGREDIENT_TYPES)
+        .required(true)
+        .help("The type of the ingredient")
+}
+
+fn mint_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("mint")
+        .long("mint")
+        .value_name("MINT_ADDRESS")
+        .validator(is_valid_pubkey)
+        .help("The mint of the ingredient, required for every token ingredient")
+}
+
+fn spec_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("spec")
+        .value_name("SPEC_FILE")
+        .required(true)
+        .help("The path of a TOML or JSON recipe spec")
+}
+
+fn app<'a, 'b>() -> App<'a, 'b> {
+    App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use"),
+        )
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .short("u")
+                .long("url")
+                .value_name("URL_OR_MONIKER")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url_or_moniker)
+                .help(
+                    "URL for Solana's JSON RPC or moniker, e.g. localhost for the local validator",
+                ),
+        )
+        .arg(
+            Arg::with_name("keypair")
+                .short("k")
+                .long("keypair")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .global(true)
+                .validator(is_valid_signer)
+                .help("The fee payer, defaults to the client keypair"),
+        )
+        .arg(
+            Arg::with_name("authority")
+                .long("authority")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .global(true)
+                .validator(is_valid_signer)
+                .help("The recipe authority and ingredient owner, defaults to the fee payer"),
+        )
+        .arg(
+            Arg::with_name("commitment")
+                .long("commitment")
+                .value_name("COMMITMENT_LEVEL")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["processed", "confirmed", "finalized"])
+                .help("Return information at the selected commitment level"),
+        )
+        .subcommand(
+            SubCommand::with_name("create")
+                .about("Create a new paused recipe")
+                .arg(
+                    Arg::with_name("base")
+                        .long("base")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .help("The base keypair of the recipe, defaults to a new keypair"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("add-ingredient")
+                .about("Add an input or output ingredient to a recipe")
+                .arg(recipe_arg())
+                .arg(ingredient_type_arg())
+                .arg(mint_arg())
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .value_name("AMOUNT")
+                        .validator(is_parsable::<u64>)
+                        .required(true)
+                        .help("The amount of tokens or lamports"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .value_name("ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .help("The destination of transfer inputs"),
+                )
+                .arg(
+                    Arg::with_name("max_supply")
+                        .long("max-supply")
+                        .value_name("MAX_SUPPLY")
+                        .validator(is_parsable::<u64>)
+                        .help("The maximum supply of mint token outputs"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-ingredient")
+                .about("Remove an input or output ingredient from a recipe")
+                .arg(recipe_arg())
+                .arg(ingredient_type_arg())
+                .arg(mint_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("activate")
+                .about("Activate a recipe")
+                .arg(recipe_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("pause")
+                .about("Pause a recipe")
+                .arg(recipe_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("craft")
+                .about("Craft a recipe using the associated token accounts of the authority")
+                .arg(recipe_arg())
+                .arg(
+                    Arg::with_name("quantity")
+                        .long("quantity")
+                        .value_name("QUANTITY")
+                        .validator(is_parsable::<u64>)
+                        .default_value("1")
+                        .help("How many times to craft the recipe"),
+                )
+                .arg(
+                    Arg::with_name("proof")
+                        .long("proof")
+                        .value_name("HASH")
+                        .validator(is_hash)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("A base58 node of the allowlist merkle proof, in order"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("unlock-feature")
+                .about("Unlock a feature level by burning or holding a feature mint")
+                .arg(recipe_arg())
+                .arg(
+                    Arg::with_name("feature")
+                        .value_name("FEATURE")
+                        .possible_values(&FEATURES)
+                        .required(true)
+                        .help("The feature to unlock"),
+                )
+                .arg(
+                    Arg::with_name("mint")
+                        .value_name("MINT_ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .help("The feature mint owned by the authority"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-fees")
+                .about("Set the custom fees of a recipe")
+                .arg(recipe_arg())
+                .arg(
+                    Arg::with_name("fees")
+                        .value_name("LAMPORTS")
+                        .validator(is_parsable::<u64>)
+                        .required(true)
+                        .help("The fees in lamports"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("collect-fees")
+                .about("Collect the fees and shards of a recipe")
+                .arg(recipe_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("collect-experience")
+                .about("Collect the experience of a recipe")
+                .arg(recipe_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("delete")
+                .about("Delete a recipe without ingredients")
+                .arg(recipe_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Decode and display any account of the program")
+                .arg(
+                    Arg::with_name("address")
+                        .value_name("ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .help("The address of a recipe, record or feature account"),
+                ),
+        )
+        .subcommand(SubCommand::with_name("show-features").about("Display every feature PDA"))
+        .subcommand(
+            SubCommand::with_name("migrate")
+                .about("Rewrite any account of the program with its current layout")
+                .arg(
+                    Arg::with_name("address")
+                        .value_name("ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .help("The address of a recipe, record or feature account"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("plan")
+                .about("Display the instructions needed for a recipe to match a spec")
+                .arg(recipe_arg())
+                .arg(spec_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("apply")
+                .about("Send the instructions needed for a recipe to match a spec")
+                .arg(recipe_arg())
+                .arg(spec_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Export a recipe as a spec")
+                .arg(recipe_arg())
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .value_name("FILE")
+                        .help("Write the spec to a file, using JSON for .json files"),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help("Output JSON instead of TOML"),
+                ),
+        )
+}
+
+fn ingredient_type_of(matches: &ArgMatches) -> IngredientType {
+    match matches.value_of("ingredient_type").unwrap() {
+        "burn-token-input" => IngredientType::BurnTokenInput,
+        "transfer-token-input" => IngredientType::TransferTokenInput,
+        "transfer-sol-input" => IngredientType::TransferSolInput,
+        "hold-token-input" => IngredientType::HoldTokenInput,
+        "mint-token-output" => IngredientType::MintTokenOutput,
+        "mint-token-with-max-supply-output" => IngredientType::MintTokenWithMaxSupplyOutput,
+        "transfer-token-output" => IngredientType::TransferTokenOutput,
+        _ => unreachable!(),
+    }
+}
+
+fn recipe_of(matches: &ArgMatches) -> Pubkey {
+    pubkey_of(matches, "recipe").unwrap()
+}
+
+fn main() {
+    let matches = app().get_matches();
+    let mut wallet_manager = None;
+
+    let config = {
+        let cli_config = match matches.value_of("config_file") {
+            Some(config_file) => solana_cli_config::Config::load(config_file).unwrap_or_default(),
+            None => solana_cli_config::CONFIG_FILE
+                .as_ref()
+                .and_then(|config_file| solana_cli_config::Config::load(config_file).ok())
+                .unwrap_or_default(),
+        };
+        let (_, json_rpc_url) = ConfigInput::compute_json_rpc_url_setting(
+            matches.value_of("json_rpc_url").unwrap_or(""),
+            &cli_config.json_rpc_url,
+        );
+        let (_, keypair_path) = ConfigInput::compute_keypair_path_setting(
+            matches.value_of("keypair").unwrap_or(""),
+            &cli_config.keypair_path,
+        );
+        let (_, commitment) = ConfigInput::compute_commitment_config(
+            matches.value_of("commitment").unwrap_or(""),
+            &cli_config.commitment,
+        );
+        let payer = signer_from_path(&matches, &keypair_path, "keypair", &mut wallet_manager)
+            .unwrap_or_else(|error| {
+                eprintln!("error: {}", error);
+                exit(1);
+            });
+        let (authority, _) =
+            signer_of(&matches, "authority", &mut wallet_manager).unwrap_or_else(|error| {
+                eprintln!("error: {}", error);
+                exit(1);
+            });
+        Config {
+            rpc_client: RpcClient::new_with_commitment(json_rpc_url, commitment),
+            payer,
+            authority,
+        }
+    };
+
+    let result: CommandResult = match matches.subcommand() {
+        ("create", Some(matches)) => {
+            let (base, _) =
+                signer_of(matches, "base", &mut wallet_manager).unwrap_or_else(|error| {
+                    eprintln!("error: {}", error);
+                    exit(1);
+                });
+            process_create(&config, base)
+        }
+        ("add-ingredient", Some(matches)) => process_add_ingredient(
+            &config,
+            &recipe_of(matches),
+            ingredient_type_of(matches),
+            pubkey_of(matches, "mint"),
+            value_t_or_exit!(matches, "amount", u64),
+            pubkey_of(matches, "destination"),
+            value_t!(matches, "max_supply", u64).ok(),
+        ),
+        ("remove-ingredient", Some(matches)) => process_remove_ingredient(
+            &config,
+            &recipe_of(matches),
+            ingredient_type_of(matches),
+            pubkey_of(matches, "mint"),
+        ),
+        ("activate", Some(matches)) => process_activate(&config, &recipe_of(matches)),
+        ("pause", Some(matches)) => process_pause(&config, &recipe_of(matches)),
+        ("craft", Some(matches)) => process_craft(
+            &config,
+            &recipe_of(matches),
+            value_t_or_exit!(matches, "quantity", u64),
+            values_t!(matches, "proof", Hash).unwrap_or_default(),
+        ),
+        ("unlock-feature", Some(matches)) => process_unlock_feature(
+            &config,
+            &recipe_of(matches),
+            matches.value_of("feature").unwrap(),
+            &pubkey_of(matches, "mint").unwrap(),
+        ),
+        ("set-fees", Some(matches)) => process_set_fees(
+            &config,
+            &recipe_of(matches),
+            value_t_or_exit!(matches, "fees", u64),
+        ),
+        ("collect-fees", Some(matches)) => process_collect_fees(&config, &recipe_of(matches)),
+        ("collect-experience", Some(matches)) => {
+            process_collect_experience(&config, &recipe_of(matches))
+        }
+        ("delete", Some(matches)) => process_delete(&config, &recipe_of(matches)),
+        ("show", Some(matches)) => process_show(&config, &pubkey_of(matches, "address").unwrap()),
+        ("show-features", Some(_)) => process_show_features(&config),
+        ("migrate", Some(matches)) => {
+            process_migrate(&config, &pubkey_of(matches, "address").unwrap())
+        }
+        ("plan", Some(matches)) => process_plan(
+            &config,
+            &recipe_of(matches),
+            matches.value_of("spec").unwrap(),
+        ),
+        ("apply", Some(matches)) => process_apply(
+            &config,
+            &recipe_of(matches),
+            matches.value_of("spec").unwrap(),
+        ),
+        ("export", Some(matches)) => process_export(
+            &config,
+            &recipe_of(matches),
+            matches.value_of("output"),
+            matches.is_present("json"),
+        ),
+        _ => unreachable!(),
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        exit(1);
+    }
+}
@@ -0,0 +1,97 @@
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Run the CLI with a fresh fee payer and no local configuration.
+fn run(args: &[&str]) -> Output {
+    let keypair = temp_path("json");
+    write_keypair_file(&Keypair::new(), &keypair).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_token-recipes"))
+        .args(["--config", "missing-config.yml", "--keypair"])
+        .arg(&keypair)
+        .args(args)
+        .output()
+        .unwrap();
+    fs::remove_file(keypair).unwrap();
+    output
+}
+
+fn temp_path(extension: &str) -> PathBuf {
+    let name = format!(
+        "token-recipes-cli-{}.{}",
+        Keypair::new().pubkey(),
+        extension
+    );
+    env::temp_dir().join(name)
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn help_lists_every_command() {
+    let output = run(&["--help"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for command in [
+        "create",
+        "add-ingredient",
+        "remove-ingredient",
+        "activate",
+        "pause",
+        "craft",
+        "unlock-feature",
+        "set-fees",
+        "collect-fees",
+        "collect-experience",
+        "delete",
+        "show",
+        "show-features",
+        "plan",
+        "apply",
+        "export",
+    ] {
+        assert!(stdout.contains(command), "missing command {}", command);
+    }
+}
+
+#[test]
+fn cannot_add_an_unknown_ingredient_type() {
+    let recipe = Keypair::new().pubkey().to_string();
+    let output = run(&[
+        "add-ingredient",
+        &recipe,
+        "steal-token-input",
+        "--amount",
+        "1",
+    ]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("isn't a valid value"));
+}
+
+#[test]
+fn cannot_use_an_invalid_recipe_address() {
+    let output = run(&["activate", "not-a-recipe"]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Invalid value for '<RECIPE_ADDRESS>'"));
+}
+
+#[test]
+fn cannot_plan_an_invalid_spec() {
+    let spec = temp_path("toml");
+    fs::write(&spec, "inputs = 42").unwrap();
+    let recipe = Keypair::new().pubkey().to_string();
+
+    let output = run(&["plan", &recipe, spec.to_str().unwrap()]);
+    fs::remove_file(spec).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("error: "));
+}
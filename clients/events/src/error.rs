@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EventError {
+    /// The logged data is not valid base64.
+    #[error("Invalid base64 event data: {0}")]
+    InvalidBase64(String),
+    /// The logged data is empty.
+    #[error("Empty event data")]
+    EmptyData,
+    /// The event was emitted with a version this crate does not know about.
+    #[error("Unsupported event version {0}")]
+    UnsupportedVersion(u8),
+    /// The event could not be deserialized.
+    #[error("Invalid event data: {0}")]
+    InvalidEvent(String),
+}
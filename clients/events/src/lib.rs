@@ -0,0 +1,68 @@
+pub mod error;
+
+use borsh::BorshDeserialize;
+use error::EventError;
+use solana_program::pubkey::Pubkey;
+
+pub use token_recipes;
+pub use token_recipes::events::{TokenRecipesEvent, EVENT_VERSION};
+
+const INVOKE_PREFIX: &str = "Program ";
+const DATA_PREFIX: &str = "Program data: ";
+
+/// Decode an event from the raw data logged by the program,
+/// i.e. the event version followed by the Borsh-serialized event.
+///
+/// Every event version keeps its own decoder so that events logged
+/// by older deployments of the program can still be decoded.
+pub fn decode_event(data: &[u8]) -> Result<TokenRecipesEvent, EventError> {
+    let (version, mut event_data) = data.split_first().ok_or(EventError::EmptyData)?;
+    match version {
+        1 => TokenRecipesEvent::deserialize(&mut event_data)
+            .map_err(|error| EventError::InvalidEvent(error.to_string())),
+        _ => Err(EventError::UnsupportedVersion(*version)),
+    }
+}
+
+/// Parse the events emitted by the Token Recipes program from the log messages of a transaction.
+pub fn parse_events<S: AsRef<str>>(logs: &[S]) -> Result<Vec<TokenRecipesEvent>, EventError> {
+    parse_events_for_program(logs, &token_recipes::id())
+}
+
+/// Parse the events emitted by a deployment of the Token Recipes program at the given address.
+///
+/// The invocation stack is tracked so that data logged by other programs, including
+/// the programs invoked by Token Recipes such as transfer hooks, is ignored.
+/// Events of failed transactions are returned as well, even though they had no effect.
+pub fn parse_events_for_program<S: AsRef<str>>(
+    logs: &[S],
+    program_id: &Pubkey,
+) -> Result<Vec<TokenRecipesEvent>, EventError> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = vec![];
+    let mut events = vec![];
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix(DATA_PREFIX) {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            // Events are logged as a single data field.
+            let field = data.split(' ').next().unwrap_or_default();
+            let bytes = base64::decode(field)
+                .map_err(|error| EventError::InvalidBase64(error.to_string()))?;
+            events.push(decode_event(&bytes)?);
+        } else if let Some(rest) = log.strip_prefix(INVOKE_PREFIX) {
+            let mut words = rest.split(' ');
+            let (program, status) = (words.next(), words.next());
+            match (program, status) {
+                (Some(program), Some("invoke")) => stack.push(program),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    stack.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(events)
+}
@@ -0,0 +1,77 @@
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use token_recipes_events::{
+    decode_event, error::EventError, parse_events, token_recipes::state::craft_quote::TokenAmount,
+    TokenRecipesEvent, EVENT_VERSION,
+};
+
+fn log_data(event: &TokenRecipesEvent) -> String {
+    let mut data = vec![EVENT_VERSION];
+    event.serialize(&mut data).unwrap();
+    format!("Program data: {}", base64::encode(data))
+}
+
+#[test]
+fn it_parses_events_of_the_program_only() {
+    let program = token_recipes_events::token_recipes::id();
+    let hook_program = Pubkey::new_unique();
+    let (recipe, owner, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let craft = TokenRecipesEvent::Craft {
+        recipe,
+        owner,
+        quantity: 2,
+        token_inputs: vec![TokenAmount { mint, amount: 6 }],
+        lamport_inputs: 0,
+        token_outputs: vec![],
+        fees: 20_000_000,
+        admin_fees: 20_000_000,
+        shards: 0,
+        experience: 0,
+    };
+    let pause = TokenRecipesEvent::PauseRecipe { recipe };
+
+    let logs = vec![
+        format!("Program {program} invoke [1]"),
+        "Program log: Instruction: Craft".to_string(),
+        format!("Program {hook_program} invoke [2]"),
+        "Program data: AQID".to_string(),
+        format!("Program {hook_program} success"),
+        log_data(&craft),
+        format!("Program {program} consumed 42000 of 200000 compute units"),
+        format!("Program {program} success"),
+        format!("Program {hook_program} invoke [1]"),
+        "Program data: AQID".to_string(),
+        format!("Program {hook_program} success"),
+        format!("Program {program} invoke [1]"),
+        log_data(&pause),
+        format!("Program {program} success"),
+    ];
+
+    assert_eq!(parse_events(&logs).unwrap(), vec![craft, pause]);
+}
+
+#[test]
+fn it_decodes_version_1_events() {
+    let recipe = Pubkey::new_unique();
+    // The PauseRecipe variant followed by the recipe address.
+    let mut data = vec![1, 4];
+    data.extend_from_slice(recipe.as_ref());
+
+    assert_eq!(
+        decode_event(&data),
+        Ok(TokenRecipesEvent::PauseRecipe { recipe })
+    );
+}
+
+#[test]
+fn it_rejects_unknown_versions() {
+    assert_eq!(
+        decode_event(&[EVENT_VERSION + 1, 0]),
+        Err(EventError::UnsupportedVersion(EVENT_VERSION + 1))
+    );
+    assert_eq!(decode_event(&[]), Err(EventError::EmptyData));
+}
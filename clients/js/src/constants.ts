@@ -1,3 +1,7 @@
 import { sol } from '@metaplex-foundation/umi';
 
+/** The default base fees of a fees feature. */
 export const BASE_FEES = sol(0.02);
+
+/** The default percentage of the fees going to the recipe at each level of the fees feature. */
+export const FEE_PERCENTAGES = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 90, 100];
@@ -1,11 +1,16 @@
 import { findAssociatedTokenPda } from '@metaplex-foundation/mpl-toolbox';
 import {
+  AccountMeta,
   Context,
   Pda,
   PublicKey,
   TransactionBuilder,
   publicKey,
 } from '@metaplex-foundation/umi';
+import {
+  publicKey as publicKeySerializer,
+  string,
+} from '@metaplex-foundation/umi/serializers';
 import { findDelegatedIngredientPda } from './generated';
 import {
   CraftInstructionAccounts,
@@ -28,42 +33,91 @@ export type BurnTokenInputCraftArgs = {
   __kind: 'BurnToken';
   mint: PublicKey;
   token?: PublicKey | Pda;
+  tokenProgram?: PublicKey;
 };
 export type TransferTokenInputCraftArgs = {
   __kind: 'TransferToken';
   mint: PublicKey;
   token?: PublicKey | Pda;
+  tokenProgram?: PublicKey;
   destination: PublicKey;
   destinationToken?: PublicKey | Pda;
+  /** The memo program and transfer hook accounts required by Token-2022 extensions, if any. */
+  additionalAccounts?: AccountMeta[];
 };
 export type TransferSolInputCraftArgs = {
   __kind: 'TransferSol';
   destination: PublicKey;
 };
+export type HoldTokenInputCraftArgs = {
+  __kind: 'HoldToken';
+  mint: PublicKey;
+  token?: PublicKey | Pda;
+  tokenProgram?: PublicKey;
+};
 export type IngredientInputCraftArgs =
   | BurnTokenInputCraftArgs
   | TransferTokenInputCraftArgs
-  | TransferSolInputCraftArgs;
+  | TransferSolInputCraftArgs
+  | HoldTokenInputCraftArgs;
 
 export type MintTokenOutputCraftArgs = {
   __kind: 'MintToken';
   mint: PublicKey;
   token?: PublicKey | Pda;
+  tokenProgram?: PublicKey;
 };
 export type MintTokenWithMaxSupplyOutputCraftArgs = {
   __kind: 'MintTokenWithMaxSupply';
   mint: PublicKey;
   token?: PublicKey | Pda;
+  tokenProgram?: PublicKey;
+};
+export type TransferTokenOutputCraftArgs = {
+  __kind: 'TransferToken';
+  mint: PublicKey;
+  token?: PublicKey | Pda;
+  tokenProgram?: PublicKey;
+  vault?: PublicKey | Pda;
+  /** The memo program and transfer hook accounts required by Token-2022 extensions, if any. */
+  additionalAccounts?: AccountMeta[];
+};
+/** Loot tables are committed to the loot roll PDA of the owner and rolled by the RevealLoot instruction. */
+export type MintTokenFromLootTableOutputCraftArgs = {
+  __kind: 'MintTokenFromLootTable';
+  lootRoll?: PublicKey | Pda;
 };
 export type IngredientOutputCraftArgs =
   | MintTokenOutputCraftArgs
-  | MintTokenWithMaxSupplyOutputCraftArgs;
+  | MintTokenWithMaxSupplyOutputCraftArgs
+  | TransferTokenOutputCraftArgs
+  | MintTokenFromLootTableOutputCraftArgs;
 
 export type CraftInstructionInput = Parameters<typeof baseCraft>[1] & {
   inputs?: IngredientInputCraftArgs[];
   outputs?: IngredientOutputCraftArgs[];
 };
 
+export function findLootRollPda(
+  context: Pick<Context, 'eddsa' | 'programs'>,
+  seeds: {
+    /** The address of the crafted recipe */
+    recipe: PublicKey;
+    /** The owner of the crafted tokens */
+    owner: PublicKey;
+  }
+): Pda {
+  const programId = context.programs.getPublicKey(
+    'tokenRecipes',
+    '6EgVKvZu2V6cpZzarvDHuyeJwa1NB2ujj8hXY98pQpLE'
+  );
+  return context.eddsa.findPda(programId, [
+    string({ size: 'variable' }).serialize('loot_roll'),
+    publicKeySerializer().serialize(seeds.recipe),
+    publicKeySerializer().serialize(seeds.owner),
+  ]);
+}
+
 // Instruction.
 export function craft(
   context: Parameters<typeof baseCraft>[0] & Pick<Context, 'eddsa'>,
@@ -72,6 +126,10 @@ export function craft(
   const { inputs, outputs, ...baseInput } = input;
   const owner = (input.owner ?? context.identity).publicKey;
   let builder = baseCraft(context, baseInput);
+  const defaultTokenProgram = context.programs.getPublicKey(
+    'splToken',
+    'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+  );
 
   // Inputs.
   inputs?.forEach((inputArgs) => {
@@ -82,6 +140,11 @@ export function craft(
       builder = builder.addRemainingAccounts([
         { pubkey: mint, isWritable: true, isSigner: false },
         { pubkey: publicKey(token, false), isWritable: true, isSigner: false },
+        {
+          pubkey: inputArgs.tokenProgram ?? defaultTokenProgram,
+          isWritable: false,
+          isSigner: false,
+        },
       ]);
     } else if (inputArgs.__kind === 'TransferToken') {
       const { mint, destination } = inputArgs;
@@ -93,18 +156,37 @@ export function craft(
       builder = builder.addRemainingAccounts([
         { pubkey: mint, isWritable: true, isSigner: false },
         { pubkey: publicKey(token, false), isWritable: true, isSigner: false },
+        {
+          pubkey: inputArgs.tokenProgram ?? defaultTokenProgram,
+          isWritable: false,
+          isSigner: false,
+        },
         { pubkey: destination, isWritable: false, isSigner: false },
         {
           pubkey: publicKey(destinationToken, false),
           isWritable: true,
           isSigner: false,
         },
+        ...(inputArgs.additionalAccounts ?? []),
       ]);
     } else if (inputArgs.__kind === 'TransferSol') {
       const { destination } = inputArgs;
       builder = builder.addRemainingAccounts([
         { pubkey: destination, isWritable: true, isSigner: false },
       ]);
+    } else if (inputArgs.__kind === 'HoldToken') {
+      const { mint } = inputArgs;
+      const token =
+        inputArgs.token ?? findAssociatedTokenPda(context, { owner, mint });
+      builder = builder.addRemainingAccounts([
+        { pubkey: mint, isWritable: false, isSigner: false },
+        { pubkey: publicKey(token, false), isWritable: false, isSigner: false },
+        {
+          pubkey: inputArgs.tokenProgram ?? defaultTokenProgram,
+          isWritable: false,
+          isSigner: false,
+        },
+      ]);
     }
   });
 
@@ -120,12 +202,52 @@ export function craft(
       builder = builder.addRemainingAccounts([
         { pubkey: mint, isWritable: true, isSigner: false },
         { pubkey: publicKey(token, false), isWritable: true, isSigner: false },
+        {
+          pubkey: outputArgs.tokenProgram ?? defaultTokenProgram,
+          isWritable: false,
+          isSigner: false,
+        },
         {
           pubkey: findDelegatedIngredientPda(context, { mint })[0],
           isWritable: false,
           isSigner: false,
         },
       ]);
+    } else if (outputArgs.__kind === 'TransferToken') {
+      const { mint } = outputArgs;
+      const token =
+        outputArgs.token ?? findAssociatedTokenPda(context, { owner, mint });
+      const vault =
+        outputArgs.vault ??
+        findAssociatedTokenPda(context, {
+          owner: publicKey(input.recipe, false),
+          mint,
+        });
+      builder = builder.addRemainingAccounts([
+        { pubkey: mint, isWritable: false, isSigner: false },
+        { pubkey: publicKey(token, false), isWritable: true, isSigner: false },
+        {
+          pubkey: outputArgs.tokenProgram ?? defaultTokenProgram,
+          isWritable: false,
+          isSigner: false,
+        },
+        { pubkey: publicKey(vault, false), isWritable: true, isSigner: false },
+        ...(outputArgs.additionalAccounts ?? []),
+      ]);
+    } else if (outputArgs.__kind === 'MintTokenFromLootTable') {
+      const lootRoll =
+        outputArgs.lootRoll ??
+        findLootRollPda(context, {
+          recipe: publicKey(input.recipe, false),
+          owner,
+        });
+      builder = builder.addRemainingAccounts([
+        {
+          pubkey: publicKey(lootRoll, false),
+          isWritable: true,
+          isSigner: false,
+        },
+      ]);
     }
   });
 
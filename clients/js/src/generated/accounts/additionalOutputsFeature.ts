@@ -24,6 +24,7 @@ import {
   publicKey as publicKeySerializer,
   string,
   struct,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -37,6 +38,8 @@ export type AdditionalOutputsFeatureAccountData = {
   mintBurn3: PublicKey;
   mintSkill1: PublicKey;
   mintSkill2: PublicKey;
+  bump: number;
+  version: number;
 };
 
 export type AdditionalOutputsFeatureAccountDataArgs = {
@@ -46,6 +49,8 @@ export type AdditionalOutputsFeatureAccountDataArgs = {
   mintBurn3: PublicKey;
   mintSkill1: PublicKey;
   mintSkill2: PublicKey;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getAdditionalOutputsFeatureAccountDataSerializer()` without any argument instead. */
@@ -73,6 +78,8 @@ export function getAdditionalOutputsFeatureAccountDataSerializer(
       ['mintBurn3', publicKeySerializer()],
       ['mintSkill1', publicKeySerializer()],
       ['mintSkill2', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
     ],
     { description: 'AdditionalOutputsFeatureAccountData' }
   ) as Serializer<
@@ -172,6 +179,8 @@ export function getAdditionalOutputsFeatureGpaBuilder(
       mintBurn3: PublicKey;
       mintSkill1: PublicKey;
       mintSkill2: PublicKey;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       mintBurn1: [1, publicKeySerializer()],
@@ -179,6 +188,8 @@ export function getAdditionalOutputsFeatureGpaBuilder(
       mintBurn3: [65, publicKeySerializer()],
       mintSkill1: [97, publicKeySerializer()],
       mintSkill2: [129, publicKeySerializer()],
+      bump: [161, u8()],
+      version: [162, u8()],
     })
     .deserializeUsing<AdditionalOutputsFeature>((account) =>
       deserializeAdditionalOutputsFeature(account)
@@ -186,7 +197,7 @@ export function getAdditionalOutputsFeatureGpaBuilder(
 }
 
 export function getAdditionalOutputsFeatureSize(): number {
-  return 161;
+  return 163;
 }
 
 export function findAdditionalOutputsFeaturePda(
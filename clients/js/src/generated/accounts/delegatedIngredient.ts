@@ -26,6 +26,7 @@ import {
   string,
   struct,
   u32,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -36,12 +37,16 @@ export type DelegatedIngredientAccountData = {
   mint: PublicKey;
   authority: PublicKey;
   counter: number;
+  bump: number;
+  version: number;
 };
 
 export type DelegatedIngredientAccountDataArgs = {
   mint: PublicKey;
   authority: PublicKey;
   counter: number;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getDelegatedIngredientAccountDataSerializer()` without any argument instead. */
@@ -72,6 +77,8 @@ export function getDelegatedIngredientAccountDataSerializer(
         ['mint', publicKeySerializer()],
         ['authority', publicKeySerializer()],
         ['counter', u32()],
+        ['bump', u8()],
+        ['version', u8()],
       ],
       { description: 'DelegatedIngredientAccountData' }
     ),
@@ -171,11 +178,15 @@ export function getDelegatedIngredientGpaBuilder(
       mint: PublicKey;
       authority: PublicKey;
       counter: number;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       mint: [1, publicKeySerializer()],
       authority: [33, publicKeySerializer()],
       counter: [65, u32()],
+      bump: [69, u8()],
+      version: [70, u8()],
     })
     .deserializeUsing<DelegatedIngredient>((account) =>
       deserializeDelegatedIngredient(account)
@@ -184,7 +195,7 @@ export function getDelegatedIngredientGpaBuilder(
 }
 
 export function getDelegatedIngredientSize(): number {
-  return 69;
+  return 71;
 }
 
 export function findDelegatedIngredientPda(
@@ -21,9 +21,12 @@ import {
 } from '@metaplex-foundation/umi';
 import {
   Serializer,
+  array,
   publicKey as publicKeySerializer,
   string,
   struct,
+  u64,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -41,6 +44,10 @@ export type FeesFeatureAccountData = {
   mintSkill1: PublicKey;
   mintSkill2: PublicKey;
   mintSkill3: PublicKey;
+  bump: number;
+  version: number;
+  baseFees: bigint;
+  feePercentages: Array<number>;
 };
 
 export type FeesFeatureAccountDataArgs = {
@@ -55,6 +62,10 @@ export type FeesFeatureAccountDataArgs = {
   mintSkill1: PublicKey;
   mintSkill2: PublicKey;
   mintSkill3: PublicKey;
+  bump: number;
+  version: number;
+  baseFees: number | bigint;
+  feePercentages: Array<number>;
 };
 
 /** @deprecated Use `getFeesFeatureAccountDataSerializer()` without any argument instead. */
@@ -81,6 +92,10 @@ export function getFeesFeatureAccountDataSerializer(
       ['mintSkill1', publicKeySerializer()],
       ['mintSkill2', publicKeySerializer()],
       ['mintSkill3', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
+      ['baseFees', u64()],
+      ['feePercentages', array(u8(), { size: 12 })],
     ],
     { description: 'FeesFeatureAccountData' }
   ) as Serializer<FeesFeatureAccountDataArgs, FeesFeatureAccountData>;
@@ -176,6 +191,10 @@ export function getFeesFeatureGpaBuilder(
       mintSkill1: PublicKey;
       mintSkill2: PublicKey;
       mintSkill3: PublicKey;
+      bump: number;
+      version: number;
+      baseFees: number | bigint;
+      feePercentages: Array<number>;
     }>({
       key: [0, getKeySerializer()],
       adminDestination: [1, publicKeySerializer()],
@@ -188,6 +207,10 @@ export function getFeesFeatureGpaBuilder(
       mintSkill1: [225, publicKeySerializer()],
       mintSkill2: [257, publicKeySerializer()],
       mintSkill3: [289, publicKeySerializer()],
+      bump: [321, u8()],
+      version: [322, u8()],
+      baseFees: [323, u64()],
+      feePercentages: [331, array(u8(), { size: 12 })],
     })
     .deserializeUsing<FeesFeature>((account) =>
       deserializeFeesFeature(account)
@@ -195,7 +218,7 @@ export function getFeesFeatureGpaBuilder(
 }
 
 export function getFeesFeatureSize(): number {
-  return 321;
+  return 343;
 }
 
 export function findFeesFeaturePda(
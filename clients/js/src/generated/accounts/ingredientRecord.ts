@@ -25,6 +25,7 @@ import {
   publicKey as publicKeySerializer,
   string,
   struct,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -36,6 +37,8 @@ export type IngredientRecordAccountData = {
   output: boolean;
   mint: PublicKey;
   recipe: PublicKey;
+  bump: number;
+  version: number;
 };
 
 export type IngredientRecordAccountDataArgs = {
@@ -44,6 +47,8 @@ export type IngredientRecordAccountDataArgs = {
   output: boolean;
   mint: PublicKey;
   recipe: PublicKey;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getIngredientRecordAccountDataSerializer()` without any argument instead. */
@@ -64,6 +69,8 @@ export function getIngredientRecordAccountDataSerializer(
       ['output', bool()],
       ['mint', publicKeySerializer()],
       ['recipe', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
     ],
     { description: 'IngredientRecordAccountData' }
   ) as Serializer<IngredientRecordAccountDataArgs, IngredientRecordAccountData>;
@@ -157,12 +164,16 @@ export function getIngredientRecordGpaBuilder(
       output: boolean;
       mint: PublicKey;
       recipe: PublicKey;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       input: [1, bool()],
       output: [2, bool()],
       mint: [3, publicKeySerializer()],
       recipe: [35, publicKeySerializer()],
+      bump: [67, u8()],
+      version: [68, u8()],
     })
     .deserializeUsing<IngredientRecord>((account) =>
       deserializeIngredientRecord(account)
@@ -170,7 +181,7 @@ export function getIngredientRecordGpaBuilder(
 }
 
 export function getIngredientRecordSize(): number {
-  return 67;
+  return 69;
 }
 
 export function findIngredientRecordPda(
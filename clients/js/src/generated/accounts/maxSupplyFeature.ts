@@ -24,6 +24,7 @@ import {
   publicKey as publicKeySerializer,
   string,
   struct,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -33,12 +34,16 @@ export type MaxSupplyFeatureAccountData = {
   key: Key;
   mintBurn1: PublicKey;
   mintSkill1: PublicKey;
+  bump: number;
+  version: number;
 };
 
 export type MaxSupplyFeatureAccountDataArgs = {
   key: KeyArgs;
   mintBurn1: PublicKey;
   mintSkill1: PublicKey;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getMaxSupplyFeatureAccountDataSerializer()` without any argument instead. */
@@ -57,6 +62,8 @@ export function getMaxSupplyFeatureAccountDataSerializer(
       ['key', getKeySerializer()],
       ['mintBurn1', publicKeySerializer()],
       ['mintSkill1', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
     ],
     { description: 'MaxSupplyFeatureAccountData' }
   ) as Serializer<MaxSupplyFeatureAccountDataArgs, MaxSupplyFeatureAccountData>;
@@ -148,10 +155,14 @@ export function getMaxSupplyFeatureGpaBuilder(
       key: KeyArgs;
       mintBurn1: PublicKey;
       mintSkill1: PublicKey;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       mintBurn1: [1, publicKeySerializer()],
       mintSkill1: [33, publicKeySerializer()],
+      bump: [65, u8()],
+      version: [66, u8()],
     })
     .deserializeUsing<MaxSupplyFeature>((account) =>
       deserializeMaxSupplyFeature(account)
@@ -159,7 +170,7 @@ export function getMaxSupplyFeatureGpaBuilder(
 }
 
 export function getMaxSupplyFeatureSize(): number {
-  return 65;
+  return 67;
 }
 
 export function findMaxSupplyFeaturePda(
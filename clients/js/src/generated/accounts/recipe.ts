@@ -22,11 +22,13 @@ import {
 import {
   Serializer,
   array,
+  i64,
   mapSerializer,
   publicKey as publicKeySerializer,
   string,
   struct,
   u64,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import {
   FeatureLevels,
@@ -60,6 +62,13 @@ export type RecipeAccountData = {
   accumulatedShards: bigint;
   accumulatedExperience: bigint;
   featureLevels: FeatureLevels;
+  version: number;
+  bump: number;
+  pendingAuthority: PublicKey;
+  maxCraftsPerWallet: bigint;
+  startTime: bigint;
+  endTime: bigint;
+  allowlistRoot: Array<number>;
   inputs: Array<IngredientInput>;
   outputs: Array<IngredientOutput>;
 };
@@ -75,6 +84,13 @@ export type RecipeAccountDataArgs = {
   accumulatedShards: number | bigint;
   accumulatedExperience: number | bigint;
   featureLevels: FeatureLevelsArgs;
+  version: number;
+  bump: number;
+  pendingAuthority: PublicKey;
+  maxCraftsPerWallet: number | bigint;
+  startTime: number | bigint;
+  endTime: number | bigint;
+  allowlistRoot: Array<number>;
   inputs: Array<IngredientInputArgs>;
   outputs: Array<IngredientOutputArgs>;
 };
@@ -104,6 +120,13 @@ export function getRecipeAccountDataSerializer(
         ['accumulatedShards', u64()],
         ['accumulatedExperience', u64()],
         ['featureLevels', getFeatureLevelsSerializer()],
+        ['version', u8()],
+        ['bump', u8()],
+        ['pendingAuthority', publicKeySerializer()],
+        ['maxCraftsPerWallet', u64()],
+        ['startTime', i64()],
+        ['endTime', i64()],
+        ['allowlistRoot', array(u8(), { size: 32 })],
         ['inputs', array(getIngredientInputSerializer())],
         ['outputs', array(getIngredientOutputSerializer())],
       ],
@@ -203,6 +226,13 @@ export function getRecipeGpaBuilder(
       accumulatedShards: number | bigint;
       accumulatedExperience: number | bigint;
       featureLevels: FeatureLevelsArgs;
+      version: number;
+      bump: number;
+      pendingAuthority: PublicKey;
+      maxCraftsPerWallet: number | bigint;
+      startTime: number | bigint;
+      endTime: number | bigint;
+      allowlistRoot: Array<number>;
       inputs: Array<IngredientInputArgs>;
       outputs: Array<IngredientOutputArgs>;
     }>({
@@ -217,7 +247,14 @@ export function getRecipeGpaBuilder(
       accumulatedShards: [98, u64()],
       accumulatedExperience: [106, u64()],
       featureLevels: [114, getFeatureLevelsSerializer()],
-      inputs: [130, array(getIngredientInputSerializer())],
+      version: [128, u8()],
+      bump: [129, u8()],
+      pendingAuthority: [130, publicKeySerializer()],
+      maxCraftsPerWallet: [162, u64()],
+      startTime: [170, i64()],
+      endTime: [178, i64()],
+      allowlistRoot: [186, array(u8(), { size: 32 })],
+      inputs: [218, array(getIngredientInputSerializer())],
       outputs: [null, array(getIngredientOutputSerializer())],
     })
     .deserializeUsing<Recipe>((account) => deserializeRecipe(account))
@@ -24,6 +24,7 @@ import {
   publicKey as publicKeySerializer,
   string,
   struct,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -45,6 +46,8 @@ export type SolPaymentFeatureAccountData = {
   mintSkill3: PublicKey;
   mintSkill4: PublicKey;
   mintSkill5: PublicKey;
+  bump: number;
+  version: number;
 };
 
 export type SolPaymentFeatureAccountDataArgs = {
@@ -63,6 +66,8 @@ export type SolPaymentFeatureAccountDataArgs = {
   mintSkill3: PublicKey;
   mintSkill4: PublicKey;
   mintSkill5: PublicKey;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getSolPaymentFeatureAccountDataSerializer()` without any argument instead. */
@@ -93,6 +98,8 @@ export function getSolPaymentFeatureAccountDataSerializer(
       ['mintSkill3', publicKeySerializer()],
       ['mintSkill4', publicKeySerializer()],
       ['mintSkill5', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
     ],
     { description: 'SolPaymentFeatureAccountData' }
   ) as Serializer<
@@ -201,6 +208,8 @@ export function getSolPaymentFeatureGpaBuilder(
       mintSkill3: PublicKey;
       mintSkill4: PublicKey;
       mintSkill5: PublicKey;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       mintBurn1: [1, publicKeySerializer()],
@@ -217,6 +226,8 @@ export function getSolPaymentFeatureGpaBuilder(
       mintSkill3: [353, publicKeySerializer()],
       mintSkill4: [385, publicKeySerializer()],
       mintSkill5: [417, publicKeySerializer()],
+      bump: [449, u8()],
+      version: [450, u8()],
     })
     .deserializeUsing<SolPaymentFeature>((account) =>
       deserializeSolPaymentFeature(account)
@@ -224,7 +235,7 @@ export function getSolPaymentFeatureGpaBuilder(
 }
 
 export function getSolPaymentFeatureSize(): number {
-  return 449;
+  return 451;
 }
 
 export function findSolPaymentFeaturePda(
@@ -24,6 +24,7 @@ import {
   publicKey as publicKeySerializer,
   string,
   struct,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -36,6 +37,8 @@ export type TransferInputsFeatureAccountData = {
   mintBurn3: PublicKey;
   mintSkill1: PublicKey;
   mintSkill2: PublicKey;
+  bump: number;
+  version: number;
 };
 
 export type TransferInputsFeatureAccountDataArgs = {
@@ -45,6 +48,8 @@ export type TransferInputsFeatureAccountDataArgs = {
   mintBurn3: PublicKey;
   mintSkill1: PublicKey;
   mintSkill2: PublicKey;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getTransferInputsFeatureAccountDataSerializer()` without any argument instead. */
@@ -72,6 +77,8 @@ export function getTransferInputsFeatureAccountDataSerializer(
       ['mintBurn3', publicKeySerializer()],
       ['mintSkill1', publicKeySerializer()],
       ['mintSkill2', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
     ],
     { description: 'TransferInputsFeatureAccountData' }
   ) as Serializer<
@@ -171,6 +178,8 @@ export function getTransferInputsFeatureGpaBuilder(
       mintBurn3: PublicKey;
       mintSkill1: PublicKey;
       mintSkill2: PublicKey;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       mintBurn1: [1, publicKeySerializer()],
@@ -178,6 +187,8 @@ export function getTransferInputsFeatureGpaBuilder(
       mintBurn3: [65, publicKeySerializer()],
       mintSkill1: [97, publicKeySerializer()],
       mintSkill2: [129, publicKeySerializer()],
+      bump: [161, u8()],
+      version: [162, u8()],
     })
     .deserializeUsing<TransferInputsFeature>((account) =>
       deserializeTransferInputsFeature(account)
@@ -185,7 +196,7 @@ export function getTransferInputsFeatureGpaBuilder(
 }
 
 export function getTransferInputsFeatureSize(): number {
-  return 161;
+  return 163;
 }
 
 export function findTransferInputsFeaturePda(
@@ -24,6 +24,7 @@ import {
   publicKey as publicKeySerializer,
   string,
   struct,
+  u8,
 } from '@metaplex-foundation/umi/serializers';
 import { Key, KeyArgs, getKeySerializer } from '../types';
 
@@ -34,6 +35,8 @@ export type WisdomFeatureAccountData = {
   experienceMint: PublicKey;
   mintBurn1: PublicKey;
   mintBurn2: PublicKey;
+  bump: number;
+  version: number;
 };
 
 export type WisdomFeatureAccountDataArgs = {
@@ -41,6 +44,8 @@ export type WisdomFeatureAccountDataArgs = {
   experienceMint: PublicKey;
   mintBurn1: PublicKey;
   mintBurn2: PublicKey;
+  bump: number;
+  version: number;
 };
 
 /** @deprecated Use `getWisdomFeatureAccountDataSerializer()` without any argument instead. */
@@ -60,6 +65,8 @@ export function getWisdomFeatureAccountDataSerializer(
       ['experienceMint', publicKeySerializer()],
       ['mintBurn1', publicKeySerializer()],
       ['mintBurn2', publicKeySerializer()],
+      ['bump', u8()],
+      ['version', u8()],
     ],
     { description: 'WisdomFeatureAccountData' }
   ) as Serializer<WisdomFeatureAccountDataArgs, WisdomFeatureAccountData>;
@@ -150,11 +157,15 @@ export function getWisdomFeatureGpaBuilder(
       experienceMint: PublicKey;
       mintBurn1: PublicKey;
       mintBurn2: PublicKey;
+      bump: number;
+      version: number;
     }>({
       key: [0, getKeySerializer()],
       experienceMint: [1, publicKeySerializer()],
       mintBurn1: [33, publicKeySerializer()],
       mintBurn2: [65, publicKeySerializer()],
+      bump: [97, u8()],
+      version: [98, u8()],
     })
     .deserializeUsing<WisdomFeature>((account) =>
       deserializeWisdomFeature(account)
@@ -162,7 +173,7 @@ export function getWisdomFeatureGpaBuilder(
 }
 
 export function getWisdomFeatureSize(): number {
-  return 97;
+  return 99;
 }
 
 export function findWisdomFeaturePda(
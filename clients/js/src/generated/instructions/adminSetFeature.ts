@@ -26,8 +26,10 @@ import { Feature, FeatureArgs, getFeatureSerializer } from '../types';
 
 // Accounts.
 export type AdminSetFeatureInstructionAccounts = {
-  /** The program as a signer */
-  programId: Signer;
+  /** The program config PDA */
+  programConfig: PublicKey | Pda;
+  /** The admin authority of the program */
+  admin: Signer;
   /** The feature PDA */
   featurePda: PublicKey | Pda;
   /** The account that pays for the storage fees */
@@ -99,7 +101,8 @@ export function adminSetFeature(
 
   // Resolved inputs.
   const resolvedAccounts = {
-    programId: [input.programId, false] as const,
+    programConfig: [input.programConfig, false] as const,
+    admin: [input.admin, false] as const,
     featurePda: [input.featurePda, true] as const,
   };
   const resolvingArgs = {};
@@ -125,7 +128,8 @@ export function adminSetFeature(
   );
   const resolvedArgs = { ...input, ...resolvingArgs };
 
-  addAccountMeta(keys, signers, resolvedAccounts.programId, false);
+  addAccountMeta(keys, signers, resolvedAccounts.programConfig, false);
+  addAccountMeta(keys, signers, resolvedAccounts.admin, false);
   addAccountMeta(keys, signers, resolvedAccounts.featurePda, false);
   addAccountMeta(keys, signers, resolvedAccounts.payer, false);
   addAccountMeta(keys, signers, resolvedAccounts.systemProgram, false);
@@ -22,6 +22,7 @@ import {
   u64,
   u8,
 } from '@metaplex-foundation/umi/serializers';
+import { findFeesFeaturePda } from '../accounts';
 import { addAccountMeta, addObjectProperty } from '../shared';
 
 // Accounts.
@@ -38,6 +39,10 @@ export type CraftInstructionAccounts = {
   tokenProgram?: PublicKey | Pda;
   /** The associated token program */
   ataProgram?: PublicKey | Pda;
+  /** The crafter record PDA of the owner, required if the recipe limits the crafts per wallet */
+  crafterRecord?: PublicKey | Pda;
+  /** The fees feature PDA storing the base fees and fee percentages */
+  feesFeaturePda?: PublicKey | Pda;
 };
 
 // Data.
@@ -73,7 +78,7 @@ export type CraftInstructionArgs = CraftInstructionDataArgs;
 
 // Instruction.
 export function craft(
-  context: Pick<Context, 'programs' | 'identity' | 'payer'>,
+  context: Pick<Context, 'eddsa' | 'programs' | 'identity' | 'payer'>,
   input: CraftInstructionAccounts & CraftInstructionArgs
 ): TransactionBuilder {
   const signers: Signer[] = [];
@@ -143,6 +148,20 @@ export function craft(
           false,
         ] as const)
   );
+  addObjectProperty(
+    resolvedAccounts,
+    'crafterRecord',
+    input.crafterRecord
+      ? ([input.crafterRecord, true] as const)
+      : ([programId, false] as const)
+  );
+  addObjectProperty(
+    resolvedAccounts,
+    'feesFeaturePda',
+    input.feesFeaturePda
+      ? ([input.feesFeaturePda, false] as const)
+      : ([findFeesFeaturePda(context), false] as const)
+  );
   const resolvedArgs = { ...input, ...resolvingArgs };
 
   addAccountMeta(keys, signers, resolvedAccounts.recipe, false);
@@ -151,6 +170,8 @@ export function craft(
   addAccountMeta(keys, signers, resolvedAccounts.systemProgram, false);
   addAccountMeta(keys, signers, resolvedAccounts.tokenProgram, false);
   addAccountMeta(keys, signers, resolvedAccounts.ataProgram, false);
+  addAccountMeta(keys, signers, resolvedAccounts.crafterRecord, false);
+  addAccountMeta(keys, signers, resolvedAccounts.feesFeaturePda, false);
 
   // Data.
   const data = getCraftInstructionDataSerializer().serialize(resolvedArgs);
@@ -44,7 +44,7 @@ export function getFeatureLevelsSerializer(
       ['maxSupply', u8()],
       ['solPayment', u8()],
       ['wisdom', u8()],
-      ['padding', array(u8(), { size: 10 })],
+      ['padding', array(u8(), { size: 8 })],
     ],
     { description: 'FeatureLevels' }
   ) as Serializer<FeatureLevelsArgs, FeatureLevels>;
@@ -19,6 +19,8 @@ export enum Key {
   MaxSupplyFeature,
   SolPaymentFeature,
   WisdomFeature,
+  CrafterRecord,
+  ProgramConfig,
 }
 
 export type KeyArgs = Key;
@@ -3,11 +3,18 @@
 import { createMint } from '@metaplex-foundation/mpl-toolbox';
 import {
   Signer,
+  publicKey,
   transactionBuilder,
   transactionBuilderGroup,
 } from '@metaplex-foundation/umi';
+import {
+  publicKey as publicKeySerializer,
+  string,
+} from '@metaplex-foundation/umi/serializers';
 import {
   AdditionalOutputsFeatureAccountData,
+  BASE_FEES,
+  FEE_PERCENTAGES,
   FeesFeatureAccountData,
   Key,
   MaxSupplyFeatureAccountData,
@@ -23,11 +30,17 @@ import {
   findTransferInputsFeaturePda,
   findWisdomFeaturePda,
 } from '../src';
-import { createUmi, localnetSigner, seededSigner } from './_setup';
+import {
+  createUmi,
+  localnetSigner,
+  seededSigner,
+  upgradeAuthoritySigner,
+} from './_setup';
 
 export const setupFeatures = async () => {
   const umi = await createUmi();
   const programId = localnetSigner(umi);
+  const upgradeAuthority = upgradeAuthoritySigner(umi);
   const { payer } = umi;
   const mints = [] as Signer[];
 
@@ -40,6 +53,46 @@ export const setupFeatures = async () => {
   }
   console.log('>> Setting up features...');
 
+  // Program config.
+  // The local validator deploys the program as upgradeable with the upgrade
+  // authority of configs/program-scripts/upgrade-authority.json, which makes the
+  // localnet program keypair the admin.
+  const admin = programId;
+  const programConfig = umi.eddsa.findPda(programId.publicKey, [
+    string({ size: 'variable' }).serialize('program_config'),
+  ]);
+  const [programData] = umi.eddsa.findPda(
+    publicKey('BPFLoaderUpgradeab1e11111111111111111111111'),
+    [publicKeySerializer().serialize(programId.publicKey)]
+  );
+  const programConfigBuilder = transactionBuilder([
+    {
+      instruction: {
+        programId: programId.publicKey,
+        keys: [
+          { pubkey: programConfig[0], isSigner: false, isWritable: true },
+          { pubkey: admin.publicKey, isSigner: false, isWritable: false },
+          {
+            pubkey: upgradeAuthority.publicKey,
+            isSigner: true,
+            isWritable: false,
+          },
+          { pubkey: programData, isSigner: false, isWritable: false },
+          { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+          {
+            pubkey: publicKey('11111111111111111111111111111111'),
+            isSigner: false,
+            isWritable: false,
+          },
+        ],
+        // The InitializeProgramConfig discriminator.
+        data: new Uint8Array([24]),
+      },
+      signers: [upgradeAuthority, payer],
+      bytesCreatedOnChain: 0,
+    },
+  ]);
+
   // Fees.
   const feesFeature: FeesFeatureAccountData = {
     key: Key.FeesFeature,
@@ -53,6 +106,10 @@ export const setupFeatures = async () => {
     mintSkill1: seededSigner(umi, 'FEES-mintSkill1').publicKey,
     mintSkill2: seededSigner(umi, 'FEES-mintSkill2').publicKey,
     mintSkill3: seededSigner(umi, 'FEES-mintSkill3').publicKey,
+    bump: 0,
+    version: 0,
+    baseFees: BASE_FEES.basisPoints,
+    feePercentages: FEE_PERCENTAGES,
   };
   mints.push(
     seededSigner(umi, 'FEES-mintBurn1'),
@@ -65,7 +122,8 @@ export const setupFeatures = async () => {
     seededSigner(umi, 'FEES-mintSkill3')
   );
   const feesBuilder = adminSetFeature(umi, {
-    programId,
+    programConfig,
+    admin,
     featurePda: feesFeaturePda,
     payer,
     feature: feature('Fees', [feesFeature]),
@@ -86,6 +144,8 @@ export const setupFeatures = async () => {
     mintBurn3: seededSigner(umi, 'ADDO-mintBurn3').publicKey,
     mintSkill1: seededSigner(umi, 'ADDO-mintSkill1').publicKey,
     mintSkill2: seededSigner(umi, 'ADDO-mintSkill2').publicKey,
+    bump: 0,
+    version: 0,
   };
   mints.push(
     seededSigner(umi, 'ADDO-mintBurn1'),
@@ -95,7 +155,8 @@ export const setupFeatures = async () => {
     seededSigner(umi, 'ADDO-mintSkill2')
   );
   const additionalOutputsBuilder = adminSetFeature(umi, {
-    programId,
+    programConfig,
+    admin,
     featurePda: additionalOutputsFeaturePda,
     payer,
     feature: feature('AdditionalOutputs', [additionalOutputsFeature]),
@@ -110,6 +171,8 @@ export const setupFeatures = async () => {
     mintBurn3: seededSigner(umi, 'TRIN-mintBurn3').publicKey,
     mintSkill1: seededSigner(umi, 'TRIN-mintSkill1').publicKey,
     mintSkill2: seededSigner(umi, 'TRIN-mintSkill2').publicKey,
+    bump: 0,
+    version: 0,
   };
   mints.push(
     seededSigner(umi, 'TRIN-mintBurn1'),
@@ -119,7 +182,8 @@ export const setupFeatures = async () => {
     seededSigner(umi, 'TRIN-mintSkill2')
   );
   const transferInputsBuilder = adminSetFeature(umi, {
-    programId,
+    programConfig,
+    admin,
     featurePda: transferInputsFeaturePda,
     payer,
     feature: feature('TransferInputs', [transferInputsFeature]),
@@ -131,13 +195,16 @@ export const setupFeatures = async () => {
     key: Key.MaxSupplyFeature,
     mintBurn1: seededSigner(umi, 'MAXS-mintBurn1').publicKey,
     mintSkill1: seededSigner(umi, 'MAXS-mintSkill1').publicKey,
+    bump: 0,
+    version: 0,
   };
   mints.push(
     seededSigner(umi, 'MAXS-mintBurn1'),
     seededSigner(umi, 'MAXS-mintSkill1')
   );
   const maxSupplyBuilder = adminSetFeature(umi, {
-    programId,
+    programConfig,
+    admin,
     featurePda: maxSupplyFeaturePda,
     payer,
     feature: feature('MaxSupply', [maxSupplyFeature]),
@@ -161,6 +228,8 @@ export const setupFeatures = async () => {
     mintSkill3: seededSigner(umi, 'SOLP-mintSkill3').publicKey,
     mintSkill4: seededSigner(umi, 'SOLP-mintSkill4').publicKey,
     mintSkill5: seededSigner(umi, 'SOLP-mintSkill5').publicKey,
+    bump: 0,
+    version: 0,
   };
   mints.push(
     seededSigner(umi, 'SOLP-mintBurn1'),
@@ -179,7 +248,8 @@ export const setupFeatures = async () => {
     seededSigner(umi, 'SOLP-mintSkill5')
   );
   const solPaymentBuilder = adminSetFeature(umi, {
-    programId,
+    programConfig,
+    admin,
     featurePda: solPaymentFeaturePda,
     payer,
     feature: feature('SolPayment', [solPaymentFeature]),
@@ -192,13 +262,16 @@ export const setupFeatures = async () => {
     experienceMint: seededSigner(umi, 'WISD-experienceMint').publicKey,
     mintBurn1: seededSigner(umi, 'WISD-mintBurn1').publicKey,
     mintBurn2: seededSigner(umi, 'WISD-mintBurn2').publicKey,
+    bump: 0,
+    version: 0,
   };
   mints.push(
     seededSigner(umi, 'WISD-mintBurn1'),
     seededSigner(umi, 'WISD-mintBurn2')
   );
   const wisdomBuilder = adminSetFeature(umi, {
-    programId,
+    programConfig,
+    admin,
     featurePda: wisdomFeaturePda,
     payer,
     feature: feature('Wisdom', [wisdomFeature]),
@@ -222,6 +295,7 @@ export const setupFeatures = async () => {
   // Send all transactions.
   await transactionBuilderGroup(
     transactionBuilder()
+      .add(programConfigBuilder)
       .add(feesBuilder)
       .add(additionalOutputsBuilder)
       .add(transferInputsBuilder)
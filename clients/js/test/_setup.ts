@@ -216,6 +216,7 @@ export const createRecipe = async (
 const rootDir = path.join(__dirname, '..', '..', '..', '..');
 const programScripts = path.join(rootDir, 'configs', 'program-scripts');
 const localnet = path.join(programScripts, 'localnet.json');
+const upgradeAuthority = path.join(programScripts, 'upgrade-authority.json');
 
 export const localnetSigner = (umi: Umi) => {
   const secretKey = new Uint8Array(JSON.parse(readFileSync(localnet, 'utf8')));
@@ -223,6 +224,14 @@ export const localnetSigner = (umi: Umi) => {
   return createSignerFromKeypair(umi, keypair);
 };
 
+export const upgradeAuthoritySigner = (umi: Umi) => {
+  const secretKey = new Uint8Array(
+    JSON.parse(readFileSync(upgradeAuthority, 'utf8'))
+  );
+  const keypair = umi.eddsa.createKeypairFromSecretKey(secretKey);
+  return createSignerFromKeypair(umi, keypair);
+};
+
 export const seededSigner = (umi: Umi, seed: string) => {
   const keypair = umi.eddsa.createKeypairFromSeed(
     string({ size: 32 }).serialize(`TR42-${seed}`)
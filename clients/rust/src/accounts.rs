@@ -0,0 +1,119 @@
+use crate::{error::ClientError, fetcher::AccountFetcher};
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use token_recipes::{
+    state::{
+        crafter_record::CrafterRecord,
+        delegated_ingredient::DelegatedIngredient,
+        features::{
+            additional_outputs::AdditionalOutputsFeature, fees::FeesFeature,
+            max_supply::MaxSupplyFeature, sol_payment::SolPaymentFeature,
+            transfer_inputs::TransferInputsFeature, wisdom::WisdomFeature, Feature,
+        },
+        ingredient_record::IngredientRecord,
+        key::Key,
+        loot_roll::LootRoll,
+        program_config::ProgramConfig,
+        recipe::{deserialize_recipe, Recipe},
+    },
+    utils::deserialize_account,
+};
+
+/// Any account owned by the Token Recipes program.
+#[derive(Debug, Clone)]
+pub enum ProgramAccount {
+    Recipe(Recipe),
+    IngredientRecord(IngredientRecord),
+    DelegatedIngredient(DelegatedIngredient),
+    CrafterRecord(CrafterRecord),
+    Feature(Feature),
+    ProgramConfig(ProgramConfig),
+    LootRoll(LootRoll),
+}
+
+/// Fetch and decode any account owned by the Token Recipes program.
+pub fn fetch_program_account<F: AccountFetcher>(
+    fetcher: &F,
+    address: &Pubkey,
+) -> Result<ProgramAccount, ClientError> {
+    let account = fetcher
+        .get_account(address)?
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    decode_program_account(address, &account)
+}
+
+/// Decode any account owned by the Token Recipes program using its key discriminator.
+pub fn decode_program_account(
+    address: &Pubkey,
+    account: &Account,
+) -> Result<ProgramAccount, ClientError> {
+    if account.owner != token_recipes::id() {
+        return Err(ClientError::InvalidAccountData(
+            *address,
+            "not owned by the Token Recipes program".to_string(),
+        ));
+    }
+    let key = account
+        .data
+        .first()
+        .and_then(|key| Key::try_from_slice(&[*key]).ok());
+    // Accounts created with an older layout are decoded with zero-filled missing
+    // fields, e.g. a zero bump and version.
+    let data = account.data.as_slice();
+    let result = match key {
+        Some(Key::Recipe) => deserialize_recipe(data).map(ProgramAccount::Recipe),
+        Some(Key::IngredientRecord) => {
+            deserialize_account(data, IngredientRecord::LEN, IngredientRecord::LEGACY_LEN)
+                .map(ProgramAccount::IngredientRecord)
+        }
+        Some(Key::DelegatedIngredient) => deserialize_account(
+            data,
+            DelegatedIngredient::LEN,
+            DelegatedIngredient::LEGACY_LEN,
+        )
+        .map(ProgramAccount::DelegatedIngredient),
+        Some(Key::CrafterRecord) => {
+            CrafterRecord::deserialize(&mut &data[..]).map(ProgramAccount::CrafterRecord)
+        }
+        Some(Key::FeesFeature) => {
+            deserialize_account(data, FeesFeature::LEN, FeesFeature::LEGACY_LEN)
+                .map(|feature| ProgramAccount::Feature(Feature::Fees(feature)))
+        }
+        Some(Key::AdditionalOutputsFeature) => deserialize_account(
+            data,
+            AdditionalOutputsFeature::LEN,
+            AdditionalOutputsFeature::LEGACY_LEN,
+        )
+        .map(|feature| ProgramAccount::Feature(Feature::AdditionalOutputs(feature))),
+        Some(Key::TransferInputsFeature) => deserialize_account(
+            data,
+            TransferInputsFeature::LEN,
+            TransferInputsFeature::LEGACY_LEN,
+        )
+        .map(|feature| ProgramAccount::Feature(Feature::TransferInputs(feature))),
+        Some(Key::MaxSupplyFeature) => {
+            deserialize_account(data, MaxSupplyFeature::LEN, MaxSupplyFeature::LEGACY_LEN)
+                .map(|feature| ProgramAccount::Feature(Feature::MaxSupply(feature)))
+        }
+        Some(Key::SolPaymentFeature) => {
+            deserialize_account(data, SolPaymentFeature::LEN, SolPaymentFeature::LEGACY_LEN)
+                .map(|feature| ProgramAccount::Feature(Feature::SolPayment(feature)))
+        }
+        Some(Key::WisdomFeature) => {
+            deserialize_account(data, WisdomFeature::LEN, WisdomFeature::LEGACY_LEN)
+                .map(|feature| ProgramAccount::Feature(Feature::Wisdom(feature)))
+        }
+        Some(Key::ProgramConfig) => {
+            ProgramConfig::deserialize(&mut &data[..]).map(ProgramAccount::ProgramConfig)
+        }
+        Some(Key::LootRoll) => LootRoll::deserialize(&mut &data[..]).map(ProgramAccount::LootRoll),
+        Some(Key::Uninitialized) | None => {
+            return Err(ClientError::InvalidAccountData(
+                *address,
+                "unknown account key".to_string(),
+            ))
+        }
+    };
+    result.map_err(|error| ClientError::InvalidAccountData(*address, error.to_string()))
+}
@@ -0,0 +1,237 @@
+use crate::{
+    discovery::{IngredientKind, RecipeGraph},
+    error::ClientError,
+};
+use solana_program::pubkey::Pubkey;
+use std::collections::{BTreeMap, BTreeSet};
+use token_recipes::state::{
+    features::fees::{get_fees_and_shards_per_craft, FeesFeature},
+    ingredient_input::IngredientInput,
+    recipe::{Recipe, RecipeStatus},
+};
+
+/// The maximum number of recipes in a loop, to bound the search on large graphs.
+pub const MAX_LOOP_LENGTH: usize = 8;
+
+/// A recipe of a loop, crafted to turn one mint of the loop into the next one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopStep {
+    pub recipe: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// How many times the recipe is crafted per loop, on average.
+    pub crafts: f64,
+}
+
+/// A sequence of recipes whose outputs feed the inputs of the next recipe,
+/// ending with the mint it started from.
+///
+/// A loop starts by crafting its first recipe once and every amount is
+/// computed from the point of view of the crafter, per loop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipeLoop {
+    pub steps: Vec<LoopStep>,
+    /// The tokens of the first mint obtained for every token of the first mint consumed.
+    pub multiplier: f64,
+    /// The tokens of the first mint gained per loop, negative when the loop loses tokens.
+    pub net_tokens: f64,
+    /// The lamports paid per loop, i.e. `TransferSol` inputs and crafting fees.
+    pub lamports_cost: f64,
+    /// The tokens consumed per loop by the other inputs of each recipe.
+    pub other_inputs: BTreeMap<Pubkey, f64>,
+}
+
+impl RecipeLoop {
+    pub fn mint(&self) -> Pubkey {
+        self.steps[0].input_mint
+    }
+
+    pub fn recipes(&self) -> BTreeSet<Pubkey> {
+        self.steps.iter().map(|step| step.recipe).collect()
+    }
+
+    /// Whether crafting the loop creates tokens out of thin air, ignoring lamports and
+    /// the tokens consumed by inputs that are not part of the loop.
+    pub fn is_arbitrage(&self) -> bool {
+        self.net_tokens > 0.0
+    }
+}
+
+/// Find every loop between the active recipes of the graph.
+/// Crafting fees are computed using the given fees feature and
+/// recipes whose lamports per craft overflow are reported as an error.
+pub fn find_loops(
+    graph: &RecipeGraph,
+    fees_feature: &FeesFeature,
+) -> Result<Vec<RecipeLoop>, ClientError> {
+    find_loops_between(graph, fees_feature, |_, recipe| {
+        recipe.status == RecipeStatus::Active
+    })
+}
+
+/// Find the loops between the active recipes of the graph that create tokens.
+pub fn find_arbitrage(
+    graph: &RecipeGraph,
+    fees_feature: &FeesFeature,
+) -> Result<Vec<RecipeLoop>, ClientError> {
+    Ok(find_loops(graph, fees_feature)?
+        .into_iter()
+        .filter(RecipeLoop::is_arbitrage)
+        .collect())
+}
+
+/// Find the loops that would create tokens if the given recipe was activated.
+pub fn check_recipe_activation(
+    graph: &RecipeGraph,
+    recipe: &Pubkey,
+    recipe_account: &Recipe,
+    fees_feature: &FeesFeature,
+) -> Result<Vec<RecipeLoop>, ClientError> {
+    let mut recipes = graph.recipes.clone();
+    recipes.insert(*recipe, recipe_account.clone());
+    let graph = RecipeGraph::new(recipes);
+    let loops = find_loops_between(&graph, fees_feature, |address, account| {
+        address == recipe || account.status == RecipeStatus::Active
+    })?;
+    Ok(loops
+        .into_iter()
+        .filter(|recipe_loop| recipe_loop.is_arbitrage() && recipe_loop.recipes().contains(recipe))
+        .collect())
+}
+
+/// An edge of the mint graph: crafting `recipe` consumes `input_amount` tokens of one mint
+/// and produces `output_amount` tokens of another mint, on average.
+#[derive(Debug, Clone, Copy)]
+struct Exchange {
+    recipe: Pubkey,
+    output_mint: Pubkey,
+    input_amount: f64,
+    output_amount: f64,
+    lamports_per_craft: u64,
+}
+
+fn find_loops_between(
+    graph: &RecipeGraph,
+    fees_feature: &FeesFeature,
+    include: impl Fn(&Pubkey, &Recipe) -> bool,
+) -> Result<Vec<RecipeLoop>, ClientError> {
+    // Build the mint graph from the consumed inputs to the outputs of every recipe.
+    let mut exchanges: BTreeMap<Pubkey, Vec<Exchange>> = BTreeMap::new();
+    for (address, recipe) in &graph.recipes {
+        if !include(address, recipe) {
+            continue;
+        }
+        let lamports_per_craft = lamports_per_craft(address, recipe, fees_feature)?;
+        let mut outputs: BTreeMap<Pubkey, f64> = BTreeMap::new();
+        for edge in graph.outputs_of(address) {
+            *outputs.entry(edge.mint).or_default() += edge.expected_amount();
+        }
+        // Held tokens are not consumed and cannot be exchanged.
+        let inputs = graph
+            .inputs_of(address)
+            .filter(|edge| edge.kind != IngredientKind::HoldTokenInput);
+        for input in inputs {
+            for (output_mint, output_amount) in &outputs {
+                exchanges.entry(input.mint).or_default().push(Exchange {
+                    recipe: *address,
+                    output_mint: *output_mint,
+                    input_amount: input.amount as f64,
+                    output_amount: *output_amount,
+                    lamports_per_craft,
+                });
+            }
+        }
+    }
+
+    // Enumerate simple cycles, each one starting from its smallest mint.
+    let mut loops = vec![];
+    for start in exchanges.keys() {
+        let mut path = vec![];
+        visit(&exchanges, start, start, &mut path, &mut loops, graph);
+    }
+    Ok(loops)
+}
+
+fn visit(
+    exchanges: &BTreeMap<Pubkey, Vec<Exchange>>,
+    start: &Pubkey,
+    mint: &Pubkey,
+    path: &mut Vec<(Pubkey, Exchange)>,
+    loops: &mut Vec<RecipeLoop>,
+    graph: &RecipeGraph,
+) {
+    if path.len() >= MAX_LOOP_LENGTH {
+        return;
+    }
+    for exchange in exchanges.get(mint).into_iter().flatten() {
+        let next = exchange.output_mint;
+        if next < *start
+            || path
+                .iter()
+                .any(|(visited, _)| *visited == next && next != *start)
+        {
+            continue;
+        }
+        path.push((*mint, *exchange));
+        if next == *start {
+            loops.push(evaluate_loop(path, graph));
+        } else {
+            visit(exchanges, start, &next, path, loops, graph);
+        }
+        path.pop();
+    }
+}
+
+fn evaluate_loop(path: &[(Pubkey, Exchange)], graph: &RecipeGraph) -> RecipeLoop {
+    let initial_tokens = path[0].1.input_amount;
+    let mut tokens = initial_tokens;
+    let mut lamports_cost = 0.0;
+    let mut other_inputs: BTreeMap<Pubkey, f64> = BTreeMap::new();
+    let mut steps = vec![];
+
+    for (input_mint, exchange) in path {
+        let crafts = tokens / exchange.input_amount;
+        lamports_cost += crafts * exchange.lamports_per_craft as f64;
+        for edge in graph.inputs_of(&exchange.recipe) {
+            if edge.mint != *input_mint && edge.kind != IngredientKind::HoldTokenInput {
+                *other_inputs.entry(edge.mint).or_default() += crafts * edge.amount as f64;
+            }
+        }
+        tokens = crafts * exchange.output_amount;
+        steps.push(LoopStep {
+            recipe: exchange.recipe,
+            input_mint: *input_mint,
+            output_mint: exchange.output_mint,
+            crafts,
+        });
+    }
+
+    RecipeLoop {
+        steps,
+        multiplier: tokens / initial_tokens,
+        net_tokens: tokens - initial_tokens,
+        lamports_cost,
+        other_inputs,
+    }
+}
+
+/// The lamports paid by the crafter for every craft of the recipe.
+fn lamports_per_craft(
+    address: &Pubkey,
+    recipe: &Recipe,
+    fees_feature: &FeesFeature,
+) -> Result<u64, ClientError> {
+    let (total_fees, _, _) = get_fees_and_shards_per_craft(recipe, fees_feature)
+        .map_err(|_| ClientError::LamportsOverflow(*address))?;
+    recipe
+        .inputs
+        .iter()
+        .try_fold(total_fees, |lamports, input| match input {
+            IngredientInput::TransferSol {
+                lamports: sol_payment,
+                ..
+            } => lamports.checked_add(*sol_payment),
+            _ => Some(lamports),
+        })
+        .ok_or(ClientError::LamportsOverflow(*address))
+}
@@ -0,0 +1,375 @@
+use crate::{
+    accounts::{decode_program_account, ProgramAccount},
+    error::ClientError,
+    fetcher::AccountFetcher,
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_sdk::account::Account;
+use spl_token_2022::extension::ExtensionType;
+use std::collections::HashMap;
+use token_recipes::{
+    instruction::{
+        craft_input_accounts, craft_output_accounts, craft_with_remaining_accounts,
+        quote_craft_input_accounts, quote_craft_output_accounts,
+        quote_craft_with_remaining_accounts, reveal_loot_with_remaining_accounts,
+    },
+    pda::{find_associated_token_address, find_crafter_record_pda, find_loot_roll_pda},
+    state::{
+        ingredient_input::IngredientInput,
+        ingredient_output::IngredientOutput,
+        key::Key,
+        loot_roll::LootRoll,
+        recipe::{deserialize_recipe, Recipe},
+    },
+    utils::{find_token_extension, TRANSFER_HOOK_EXTENSION_TYPE, TRANSFER_HOOK_VALIDATION_SEED},
+};
+
+/// The size of an extra account meta in a transfer hook validation account.
+const EXTRA_ACCOUNT_META_LEN: usize = 1 + 32 + 1 + 1;
+
+/// Fetch and deserialize a recipe account.
+pub fn fetch_recipe<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+) -> Result<Recipe, ClientError> {
+    let account = fetcher
+        .get_account(recipe)?
+        .ok_or(ClientError::AccountNotFound(*recipe))?;
+    if account.owner != token_recipes::id() || account.data.first() != Some(&(Key::Recipe as u8)) {
+        return Err(ClientError::InvalidAccountData(
+            *recipe,
+            "not a recipe account".to_string(),
+        ));
+    }
+    deserialize_recipe(&account.data)
+        .map_err(|error| ClientError::InvalidAccountData(*recipe, error.to_string()))
+}
+
+/// Fetch the given recipe and resolve the complete `Craft` instruction for the given owner.
+pub fn resolve_craft<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<Instruction, ClientError> {
+    let recipe_account = fetch_recipe(fetcher, recipe)?;
+    resolve_craft_with_recipe(
+        fetcher,
+        recipe,
+        &recipe_account,
+        owner,
+        payer,
+        quantity,
+        proof,
+    )
+}
+
+/// Resolve the complete `Craft` instruction of an already fetched recipe.
+///
+/// The remaining accounts are listed in the exact order the program consumes them,
+/// i.e. the accounts of every input followed by the accounts of every output.
+pub fn resolve_craft_with_recipe<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    recipe_account: &Recipe,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<Instruction, ClientError> {
+    let mut resolver = CraftResolver::new(fetcher);
+    let mut remaining_accounts = vec![];
+    for input in &recipe_account.inputs {
+        remaining_accounts.extend(resolver.input_accounts(input, owner)?);
+    }
+    for output in &recipe_account.outputs {
+        remaining_accounts.extend(resolver.output_accounts(output, recipe, owner)?);
+    }
+
+    let crafter_record = match recipe_account.max_crafts_per_wallet {
+        0 => None,
+        _ => Some(find_crafter_record_pda(recipe, owner).0),
+    };
+
+    Ok(craft_with_remaining_accounts(
+        recipe,
+        owner,
+        payer,
+        crafter_record.as_ref(),
+        quantity,
+        proof,
+        remaining_accounts,
+    ))
+}
+
+/// Fetch the given recipe and resolve the `QuoteCraft` instruction for the given owner.
+///
+/// Simulate the instruction and deserialize its return data as a `CraftQuote`
+/// to get the costs of crafting the recipe without signing anything.
+/// The allowlist of the recipe is not checked, so no allowlist proof is needed.
+pub fn resolve_quote_craft<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quantity: u64,
+) -> Result<Instruction, ClientError> {
+    let recipe_account = fetch_recipe(fetcher, recipe)?;
+    let mut resolver = CraftResolver::new(fetcher);
+    let mut remaining_accounts = vec![];
+    for input in &recipe_account.inputs {
+        let token_program = match input {
+            IngredientInput::BurnToken { mint, .. }
+            | IngredientInput::TransferToken { mint, .. }
+            | IngredientInput::HoldToken { mint, .. } => resolver.token_program(mint)?,
+            IngredientInput::TransferSol { .. } => spl_token::id(),
+        };
+        remaining_accounts.extend(quote_craft_input_accounts(input, owner, &token_program));
+    }
+    for output in &recipe_account.outputs {
+        let token_program = match output {
+            IngredientOutput::TransferToken { mint, .. } => resolver.token_program(mint)?,
+            _ => spl_token::id(),
+        };
+        remaining_accounts.extend(quote_craft_output_accounts(output, recipe, &token_program));
+    }
+
+    let crafter_record = match recipe_account.max_crafts_per_wallet {
+        0 => None,
+        _ => Some(find_crafter_record_pda(recipe, owner).0),
+    };
+
+    Ok(quote_craft_with_remaining_accounts(
+        recipe,
+        owner,
+        payer,
+        crafter_record.as_ref(),
+        quantity,
+        remaining_accounts,
+    ))
+}
+
+/// Fetch the loot roll of the owner for the given recipe and resolve the complete
+/// `RevealLoot` instruction. The loot can be revealed from the slot after the craft.
+pub fn resolve_reveal_loot<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ClientError> {
+    let (loot_roll, _) = find_loot_roll_pda(recipe, owner);
+    let account = fetcher
+        .get_account(&loot_roll)?
+        .ok_or(ClientError::AccountNotFound(loot_roll))?;
+    let loot_roll_account = match decode_program_account(&loot_roll, &account)? {
+        ProgramAccount::LootRoll(loot_roll_account) => loot_roll_account,
+        _ => {
+            return Err(ClientError::InvalidAccountData(
+                loot_roll,
+                "not a loot roll account".to_string(),
+            ))
+        }
+    };
+    let remaining_accounts = CraftResolver::new(fetcher).loot_roll_accounts(&loot_roll_account)?;
+
+    Ok(reveal_loot_with_remaining_accounts(
+        &loot_roll,
+        owner,
+        payer,
+        &loot_roll_account.payer,
+        remaining_accounts,
+    ))
+}
+
+struct CraftResolver<'a, F: AccountFetcher> {
+    fetcher: &'a F,
+    mints: HashMap<Pubkey, Account>,
+}
+
+impl<'a, F: AccountFetcher> CraftResolver<'a, F> {
+    fn new(fetcher: &'a F) -> Self {
+        Self {
+            fetcher,
+            mints: HashMap::new(),
+        }
+    }
+
+    fn input_accounts(
+        &mut self,
+        input: &IngredientInput,
+        owner: &Pubkey,
+    ) -> Result<Vec<AccountMeta>, ClientError> {
+        match input {
+            IngredientInput::BurnToken { mint, .. } | IngredientInput::HoldToken { mint, .. } => {
+                let token_program = self.token_program(mint)?;
+                Ok(craft_input_accounts(input, owner, &token_program))
+            }
+            IngredientInput::TransferToken {
+                mint, destination, ..
+            } => {
+                let token_program = self.token_program(mint)?;
+                let destination_token =
+                    find_associated_token_address(destination, mint, &token_program);
+                let mut accounts = craft_input_accounts(input, owner, &token_program);
+                accounts.extend(self.transfer_additional_accounts(mint, &destination_token)?);
+                Ok(accounts)
+            }
+            IngredientInput::TransferSol { .. } => {
+                Ok(craft_input_accounts(input, owner, &spl_token::id()))
+            }
+        }
+    }
+
+    fn output_accounts(
+        &mut self,
+        output: &IngredientOutput,
+        recipe: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<Vec<AccountMeta>, ClientError> {
+        match output {
+            IngredientOutput::MintToken { mint, .. }
+            | IngredientOutput::MintTokenWithMaxSupply { mint, .. } => {
+                let token_program = self.token_program(mint)?;
+                Ok(craft_output_accounts(output, recipe, owner, &token_program))
+            }
+            IngredientOutput::TransferToken { mint, .. } => {
+                let token_program = self.token_program(mint)?;
+                let token = find_associated_token_address(owner, mint, &token_program);
+                let mut accounts = craft_output_accounts(output, recipe, owner, &token_program);
+                accounts.extend(self.transfer_additional_accounts(mint, &token)?);
+                Ok(accounts)
+            }
+            // Loot tables are committed to the loot roll of the owner.
+            IngredientOutput::MintTokenFromLootTable { .. } => Ok(craft_output_accounts(
+                output,
+                recipe,
+                owner,
+                &spl_token::id(),
+            )),
+        }
+    }
+
+    fn loot_roll_accounts(
+        &mut self,
+        loot_roll_account: &LootRoll,
+    ) -> Result<Vec<AccountMeta>, ClientError> {
+        // Every entry may use a different token program.
+        let mut accounts = vec![];
+        for loot_table in &loot_roll_account.loot_tables {
+            for entry in &loot_table.entries {
+                let token_program = self.token_program(&entry.mint)?;
+                let entry_output = IngredientOutput::MintToken {
+                    mint: entry.mint,
+                    amount: entry.amount,
+                };
+                accounts.extend(craft_output_accounts(
+                    &entry_output,
+                    &loot_roll_account.recipe,
+                    &loot_roll_account.owner,
+                    &token_program,
+                ));
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// The accounts the program expects after a token transfer ingredient,
+    /// mirroring `next_transfer_additional_accounts` on the program side.
+    fn transfer_additional_accounts(
+        &mut self,
+        mint: &Pubkey,
+        destination_token: &Pubkey,
+    ) -> Result<Vec<AccountMeta>, ClientError> {
+        let mut accounts = vec![];
+
+        // Memo program. Missing token accounts are created without any extension.
+        if let Some(token_account) = self.fetcher.get_account(destination_token)? {
+            let memo_required = token_account.owner == spl_token_2022::id()
+                && find_token_extension(&token_account.data, ExtensionType::MemoTransfer as u16)
+                    .map(|data| data.first() == Some(&1))
+                    .unwrap_or(false);
+            if memo_required {
+                accounts.push(AccountMeta::new_readonly(spl_memo::id(), false));
+            }
+        }
+
+        // Transfer hook accounts.
+        let mint_account = self.mint(mint)?;
+        if mint_account.owner != spl_token_2022::id() {
+            return Ok(accounts);
+        }
+        let transfer_hook_program_id =
+            find_token_extension(&mint_account.data, TRANSFER_HOOK_EXTENSION_TYPE)
+                .and_then(|data| <[u8; 32]>::try_from(data.get(32..64)?).ok())
+                .map(Pubkey::new_from_array)
+                .filter(|program_id| *program_id != Pubkey::default());
+        let transfer_hook_program_id = match transfer_hook_program_id {
+            Some(program_id) => program_id,
+            None => return Ok(accounts),
+        };
+        let (validation, _) = Pubkey::find_program_address(
+            &[TRANSFER_HOOK_VALIDATION_SEED.as_bytes(), mint.as_ref()],
+            &transfer_hook_program_id,
+        );
+        accounts.push(AccountMeta::new_readonly(transfer_hook_program_id, false));
+        accounts.push(AccountMeta::new_readonly(validation, false));
+
+        // Extra accounts listed in the validation account.
+        let validation_data = match self.fetcher.get_account(&validation)? {
+            Some(account) => account.data,
+            None => return Ok(accounts),
+        };
+        let extra_accounts_count = validation_data
+            .get(12..16)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .unwrap_or(0) as usize;
+        for index in 0..extra_accounts_count {
+            let start = 16 + index * EXTRA_ACCOUNT_META_LEN;
+            let meta = validation_data
+                .get(start..start + EXTRA_ACCOUNT_META_LEN)
+                .ok_or_else(|| {
+                    ClientError::InvalidAccountData(
+                        validation,
+                        format!("missing extra account meta #{}", index),
+                    )
+                })?;
+            // Only literal addresses can be resolved ahead of time.
+            if meta[0] != 0 {
+                return Err(ClientError::UnsupportedExtraAccountMeta(*mint));
+            }
+            accounts.push(AccountMeta {
+                pubkey: Pubkey::new_from_array(<[u8; 32]>::try_from(&meta[1..33]).unwrap()),
+                is_signer: meta[33] == 1,
+                is_writable: meta[34] == 1,
+            });
+        }
+
+        Ok(accounts)
+    }
+
+    fn mint(&mut self, mint: &Pubkey) -> Result<Account, ClientError> {
+        if let Some(account) = self.mints.get(mint) {
+            return Ok(account.clone());
+        }
+        let account = self
+            .fetcher
+            .get_account(mint)?
+            .ok_or(ClientError::AccountNotFound(*mint))?;
+        self.mints.insert(*mint, account.clone());
+        Ok(account)
+    }
+
+    fn token_program(&mut self, mint: &Pubkey) -> Result<Pubkey, ClientError> {
+        let owner = self.mint(mint)?.owner;
+        if owner == spl_token::id() || owner == spl_token_2022::id() {
+            Ok(owner)
+        } else {
+            Err(ClientError::InvalidTokenProgram(*mint))
+        }
+    }
+}
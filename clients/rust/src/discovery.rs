@@ -0,0 +1,306 @@
+use crate::{
+    error::ClientError,
+    fetcher::{MemcmpFilter, ProgramAccountsFetcher},
+};
+use solana_program::pubkey::Pubkey;
+use std::collections::{BTreeMap, BTreeSet};
+use token_recipes::{
+    state::{
+        ingredient_input::IngredientInput,
+        ingredient_output::IngredientOutput,
+        ingredient_record::IngredientRecord,
+        key::Key,
+        recipe::{deserialize_recipe, Recipe},
+    },
+    utils::deserialize_account,
+};
+
+/// Offsets of the `IngredientRecord` fields.
+const INGREDIENT_RECORD_INPUT_OFFSET: usize = 1;
+const INGREDIENT_RECORD_OUTPUT_OFFSET: usize = 2;
+const INGREDIENT_RECORD_MINT_OFFSET: usize = 3;
+const INGREDIENT_RECORD_RECIPE_OFFSET: usize = 35;
+
+/// Fetch the ingredient records of a mint, i.e. one record per recipe using the mint.
+pub fn find_ingredient_records_by_mint<F: ProgramAccountsFetcher>(
+    fetcher: &F,
+    mint: &Pubkey,
+) -> Result<Vec<(Pubkey, IngredientRecord)>, ClientError> {
+    fetch_ingredient_records(
+        fetcher,
+        MemcmpFilter::new(INGREDIENT_RECORD_MINT_OFFSET, mint.as_ref()),
+        None,
+    )
+}
+
+/// Fetch the ingredient records of a recipe, i.e. one record per mint used by the recipe.
+pub fn find_ingredient_records_by_recipe<F: ProgramAccountsFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+) -> Result<Vec<(Pubkey, IngredientRecord)>, ClientError> {
+    fetch_ingredient_records(
+        fetcher,
+        MemcmpFilter::new(INGREDIENT_RECORD_RECIPE_OFFSET, recipe.as_ref()),
+        None,
+    )
+}
+
+/// Find the recipes using the given mint as an input, including `HoldToken` inputs.
+pub fn find_recipes_consuming<F: ProgramAccountsFetcher>(
+    fetcher: &F,
+    mint: &Pubkey,
+) -> Result<Vec<Pubkey>, ClientError> {
+    let records = fetch_ingredient_records(
+        fetcher,
+        MemcmpFilter::new(INGREDIENT_RECORD_MINT_OFFSET, mint.as_ref()),
+        Some(MemcmpFilter::new(INGREDIENT_RECORD_INPUT_OFFSET, &[1])),
+    )?;
+    Ok(records
+        .into_iter()
+        .map(|(_, record)| record.recipe)
+        .collect())
+}
+
+/// Find the recipes using the given mint as an output, including loot table entries.
+pub fn find_recipes_producing<F: ProgramAccountsFetcher>(
+    fetcher: &F,
+    mint: &Pubkey,
+) -> Result<Vec<Pubkey>, ClientError> {
+    let records = fetch_ingredient_records(
+        fetcher,
+        MemcmpFilter::new(INGREDIENT_RECORD_MINT_OFFSET, mint.as_ref()),
+        Some(MemcmpFilter::new(INGREDIENT_RECORD_OUTPUT_OFFSET, &[1])),
+    )?;
+    Ok(records
+        .into_iter()
+        .map(|(_, record)| record.recipe)
+        .collect())
+}
+
+/// Fetch every recipe account of the program.
+pub fn fetch_all_recipes<F: ProgramAccountsFetcher>(
+    fetcher: &F,
+) -> Result<Vec<(Pubkey, Recipe)>, ClientError> {
+    let mut recipes = fetcher
+        .get_program_accounts(&[MemcmpFilter::new(0, &[Key::Recipe as u8])])?
+        .into_iter()
+        .map(|(address, account)| {
+            deserialize_recipe(&account.data)
+                .map(|recipe| (address, recipe))
+                .map_err(|error| ClientError::InvalidAccountData(address, error.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    recipes.sort_by_key(|(address, _)| *address);
+    Ok(recipes)
+}
+
+fn fetch_ingredient_records<F: ProgramAccountsFetcher>(
+    fetcher: &F,
+    filter: MemcmpFilter,
+    additional_filter: Option<MemcmpFilter>,
+) -> Result<Vec<(Pubkey, IngredientRecord)>, ClientError> {
+    let mut filters = vec![MemcmpFilter::new(0, &[Key::IngredientRecord as u8]), filter];
+    filters.extend(additional_filter);
+    let mut records = fetcher
+        .get_program_accounts(&filters)?
+        .into_iter()
+        .map(|(address, account)| {
+            deserialize_account(
+                &account.data,
+                IngredientRecord::LEN,
+                IngredientRecord::LEGACY_LEN,
+            )
+            .map(|record| (address, record))
+            .map_err(|error| ClientError::InvalidAccountData(address, error.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    records.sort_by_key(|(address, _)| *address);
+    Ok(records)
+}
+
+/// How a mint is used by a recipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngredientKind {
+    BurnTokenInput,
+    TransferTokenInput,
+    HoldTokenInput,
+    MintTokenOutput,
+    MintTokenWithMaxSupplyOutput,
+    TransferTokenOutput,
+    /// An entry of a loot table, minted `rolls` times with a chance of `weight / total_weight`.
+    LootTableOutput {
+        rolls: u8,
+        weight: u16,
+        total_weight: u64,
+    },
+}
+
+/// An edge of the recipe graph between a recipe and one of its token ingredients.
+/// Inputs go from the mint to the recipe and outputs from the recipe to the mint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngredientEdge {
+    pub recipe: Pubkey,
+    pub mint: Pubkey,
+    pub kind: IngredientKind,
+    /// The amount of tokens per craft, or per roll for loot table entries.
+    pub amount: u64,
+}
+
+impl IngredientEdge {
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self.kind,
+            IngredientKind::BurnTokenInput
+                | IngredientKind::TransferTokenInput
+                | IngredientKind::HoldTokenInput
+        )
+    }
+
+    pub fn is_output(&self) -> bool {
+        !self.is_input()
+    }
+
+    /// The average amount of tokens per craft.
+    pub fn expected_amount(&self) -> f64 {
+        match self.kind {
+            IngredientKind::LootTableOutput {
+                rolls,
+                weight,
+                total_weight,
+            } => self.amount as f64 * rolls as f64 * weight as f64 / total_weight as f64,
+            _ => self.amount as f64,
+        }
+    }
+}
+
+/// A directed graph of every recipe and the mints they consume and produce.
+/// SOL payments are not part of the graph.
+#[derive(Debug, Clone, Default)]
+pub struct RecipeGraph {
+    pub recipes: BTreeMap<Pubkey, Recipe>,
+    pub edges: Vec<IngredientEdge>,
+}
+
+impl RecipeGraph {
+    /// Fetch every recipe of the program and build their graph.
+    pub fn fetch<F: ProgramAccountsFetcher>(fetcher: &F) -> Result<Self, ClientError> {
+        Ok(Self::new(fetch_all_recipes(fetcher)?))
+    }
+
+    pub fn new(recipes: impl IntoIterator<Item = (Pubkey, Recipe)>) -> Self {
+        let recipes: BTreeMap<Pubkey, Recipe> = recipes.into_iter().collect();
+        let mut edges = vec![];
+        for (address, recipe) in &recipes {
+            edges.extend(
+                recipe
+                    .inputs
+                    .iter()
+                    .filter_map(|input| input_edge(address, input)),
+            );
+            for output in &recipe.outputs {
+                edges.extend(output_edges(address, output));
+            }
+        }
+        Self { recipes, edges }
+    }
+
+    pub fn mints(&self) -> BTreeSet<Pubkey> {
+        self.edges.iter().map(|edge| edge.mint).collect()
+    }
+
+    pub fn inputs_of<'a>(
+        &'a self,
+        recipe: &'a Pubkey,
+    ) -> impl Iterator<Item = &'a IngredientEdge> + 'a {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.recipe == *recipe && edge.is_input())
+    }
+
+    pub fn outputs_of<'a>(
+        &'a self,
+        recipe: &'a Pubkey,
+    ) -> impl Iterator<Item = &'a IngredientEdge> + 'a {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.recipe == *recipe && edge.is_output())
+    }
+
+    /// The recipes using the given mint as an input.
+    pub fn consumers_of(&self, mint: &Pubkey) -> BTreeSet<Pubkey> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.mint == *mint && edge.is_input())
+            .map(|edge| edge.recipe)
+            .collect()
+    }
+
+    /// The recipes using the given mint as an output.
+    pub fn producers_of(&self, mint: &Pubkey) -> BTreeSet<Pubkey> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.mint == *mint && edge.is_output())
+            .map(|edge| edge.recipe)
+            .collect()
+    }
+}
+
+fn input_edge(recipe: &Pubkey, input: &IngredientInput) -> Option<IngredientEdge> {
+    let (mint, amount, kind) = match *input {
+        IngredientInput::BurnToken { mint, amount } => {
+            (mint, amount, IngredientKind::BurnTokenInput)
+        }
+        IngredientInput::TransferToken { mint, amount, .. } => {
+            (mint, amount, IngredientKind::TransferTokenInput)
+        }
+        IngredientInput::HoldToken { mint, amount } => {
+            (mint, amount, IngredientKind::HoldTokenInput)
+        }
+        IngredientInput::TransferSol { .. } => return None,
+    };
+    Some(IngredientEdge {
+        recipe: *recipe,
+        mint,
+        kind,
+        amount,
+    })
+}
+
+fn output_edges(recipe: &Pubkey, output: &IngredientOutput) -> Vec<IngredientEdge> {
+    let edge = |mint: Pubkey, amount: u64, kind: IngredientKind| IngredientEdge {
+        recipe: *recipe,
+        mint,
+        kind,
+        amount,
+    };
+    match output {
+        IngredientOutput::MintToken { mint, amount } => {
+            vec![edge(*mint, *amount, IngredientKind::MintTokenOutput)]
+        }
+        IngredientOutput::MintTokenWithMaxSupply { mint, amount, .. } => vec![edge(
+            *mint,
+            *amount,
+            IngredientKind::MintTokenWithMaxSupplyOutput,
+        )],
+        IngredientOutput::TransferToken { mint, amount } => {
+            vec![edge(*mint, *amount, IngredientKind::TransferTokenOutput)]
+        }
+        IngredientOutput::MintTokenFromLootTable { rolls, entries } => {
+            let total_weight = entries.iter().map(|entry| entry.weight as u64).sum();
+            entries
+                .iter()
+                .map(|entry| {
+                    edge(
+                        entry.mint,
+                        entry.amount,
+                        IngredientKind::LootTableOutput {
+                            rolls: *rolls,
+                            weight: entry.weight,
+                            total_weight,
+                        },
+                    )
+                })
+                .collect()
+        }
+    }
+}
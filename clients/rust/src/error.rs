@@ -0,0 +1,30 @@
+use solana_program::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// The account does not exist.
+    #[error("Account [{0}] does not exist")]
+    AccountNotFound(Pubkey),
+    /// The account exists but its data could not be parsed.
+    #[error("Account [{0}] could not be deserialized: {1}")]
+    InvalidAccountData(Pubkey, String),
+    /// The account is not owned by a supported token program.
+    #[error("Mint [{0}] is not owned by SPL Token or SPL Token-2022")]
+    InvalidTokenProgram(Pubkey),
+    /// The transfer hook of the mint requires accounts derived at transfer time.
+    #[error("Transfer hook of mint [{0}] requires an extra account that cannot be resolved")]
+    UnsupportedExtraAccountMeta(Pubkey),
+    /// The recipe spec could not be parsed or is inconsistent.
+    #[error("Invalid recipe spec: {0}")]
+    InvalidSpec(String),
+    /// The recipe has not unlocked the feature level required by the spec.
+    #[error("Feature [{0}] is at level {1} but the spec requires level {2}")]
+    FeatureLevelTooLow(String, u8, u8),
+    /// The lamports paid per craft of the recipe do not fit in a u64.
+    #[error("Lamports per craft of recipe [{0}] overflow")]
+    LamportsOverflow(Pubkey),
+    /// The account fetcher failed.
+    #[error("Failed to fetch accounts: {0}")]
+    FetchError(String),
+}
@@ -0,0 +1,103 @@
+use crate::error::ClientError;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::collections::HashMap;
+
+/// Fetch accounts from the cluster, a cache or any other source.
+pub trait AccountFetcher {
+    /// Fetch the given account or return `None` if it does not exist.
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Account>, ClientError>;
+}
+
+/// Fetch the accounts of the Token Recipes program matching a set of filters.
+pub trait ProgramAccountsFetcher {
+    /// Fetch every account owned by the program whose data matches all the given filters.
+    fn get_program_accounts(
+        &self,
+        filters: &[MemcmpFilter],
+    ) -> Result<Vec<(Pubkey, Account)>, ClientError>;
+}
+
+/// Compare bytes at a given offset of the account data,
+/// i.e. a `memcmp` filter of the `getProgramAccounts` RPC method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemcmpFilter {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    pub fn new(offset: usize, bytes: &[u8]) -> Self {
+        Self {
+            offset,
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.get(self.offset..self.offset + self.bytes.len()) == Some(self.bytes.as_slice())
+    }
+}
+
+impl AccountFetcher for HashMap<Pubkey, Account> {
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Account>, ClientError> {
+        Ok(self.get(address).cloned())
+    }
+}
+
+impl ProgramAccountsFetcher for HashMap<Pubkey, Account> {
+    fn get_program_accounts(
+        &self,
+        filters: &[MemcmpFilter],
+    ) -> Result<Vec<(Pubkey, Account)>, ClientError> {
+        Ok(self
+            .iter()
+            .filter(|(_, account)| account.owner == token_recipes::id())
+            .filter(|(_, account)| filters.iter().all(|filter| filter.matches(&account.data)))
+            .map(|(address, account)| (*address, account.clone()))
+            .collect())
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl AccountFetcher for solana_client::rpc_client::RpcClient {
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Account>, ClientError> {
+        self.get_account_with_commitment(address, self.commitment())
+            .map(|response| response.value)
+            .map_err(|error| ClientError::FetchError(error.to_string()))
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl ProgramAccountsFetcher for solana_client::rpc_client::RpcClient {
+    fn get_program_accounts(
+        &self,
+        filters: &[MemcmpFilter],
+    ) -> Result<Vec<(Pubkey, Account)>, ClientError> {
+        use solana_client::{
+            rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+            rpc_filter::{Memcmp, RpcFilterType},
+        };
+        let config = RpcProgramAccountsConfig {
+            filters: Some(
+                filters
+                    .iter()
+                    .map(|filter| {
+                        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                            filter.offset,
+                            filter.bytes.clone(),
+                        ))
+                    })
+                    .collect(),
+            ),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                commitment: Some(self.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            with_context: None,
+        };
+        self.get_program_accounts_with_config(&token_recipes::id(), config)
+            .map_err(|error| ClientError::FetchError(error.to_string()))
+    }
+}
@@ -0,0 +1,9 @@
+pub mod accounts;
+pub mod analysis;
+pub mod craft;
+pub mod discovery;
+pub mod error;
+pub mod fetcher;
+pub mod spec;
+
+pub use token_recipes;
@@ -0,0 +1,881 @@
+use crate::{craft::fetch_recipe, error::ClientError, fetcher::AccountFetcher};
+use serde::{Deserialize, Serialize};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use std::collections::HashSet;
+use token_recipes::{
+    instruction::{
+        activate_recipe, add_ingredient, add_loot_table_output, pause_recipe, remove_ingredient,
+        remove_loot_table_output, set_fees, set_max_crafts_per_wallet, set_time_window,
+        update_ingredient,
+    },
+    state::{
+        features::FeatureLevels,
+        ingredient_input::IngredientInput,
+        ingredient_output::{IngredientOutput, LootTableEntry},
+        recipe::{IngredientType, Recipe, RecipeStatus},
+    },
+};
+
+/// A declarative description of a recipe that can be stored as TOML or JSON.
+///
+/// The order of the ingredients is not significant. Token ingredients are
+/// identified by their mint and there can only be one SOL payment input.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RecipeSpec {
+    pub status: StatusSpec,
+    #[serde(default)]
+    pub fees: u64,
+    #[serde(default)]
+    pub max_crafts_per_wallet: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+    /// The minimum feature levels the recipe must have unlocked.
+    #[serde(default)]
+    pub feature_levels: FeatureLevelsSpec,
+    #[serde(default)]
+    pub inputs: Vec<InputSpec>,
+    #[serde(default)]
+    pub outputs: Vec<OutputSpec>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusSpec {
+    Paused,
+    Active,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureLevelsSpec {
+    pub fees: u8,
+    pub additional_outputs: u8,
+    pub transfer_inputs: u8,
+    pub max_supply: u8,
+    pub sol_payment: u8,
+    pub wisdom: u8,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum InputSpec {
+    BurnToken {
+        #[serde(with = "pubkey_string")]
+        mint: Pubkey,
+        amount: u64,
+    },
+    TransferToken {
+        #[serde(with = "pubkey_string")]
+        mint: Pubkey,
+        amount: u64,
+        #[serde(with = "pubkey_string")]
+        destination: Pubkey,
+    },
+    TransferSol {
+        lamports: u64,
+        #[serde(with = "pubkey_string")]
+        destination: Pubkey,
+    },
+    HoldToken {
+        #[serde(with = "pubkey_string")]
+        mint: Pubkey,
+        amount: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum OutputSpec {
+    MintToken {
+        #[serde(with = "pubkey_string")]
+        mint: Pubkey,
+        amount: u64,
+    },
+    MintTokenWithMaxSupply {
+        #[serde(with = "pubkey_string")]
+        mint: Pubkey,
+        amount: u64,
+        max_supply: u64,
+    },
+    TransferToken {
+        #[serde(with = "pubkey_string")]
+        mint: Pubkey,
+        amount: u64,
+    },
+    MintTokenFromLootTable {
+        rolls: u8,
+        entries: Vec<LootTableEntrySpec>,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LootTableEntrySpec {
+    #[serde(with = "pubkey_string")]
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub weight: u16,
+}
+
+/// A single step of a plan along with a human-readable description.
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub description: String,
+    pub instruction: Instruction,
+}
+
+/// The ordered steps needed for an on-chain recipe to match its spec.
+#[derive(Debug, Clone, Default)]
+pub struct RecipePlan {
+    pub steps: Vec<PlanStep>,
+}
+
+impl RecipePlan {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.steps
+            .iter()
+            .map(|step| step.instruction.clone())
+            .collect()
+    }
+
+    fn push(&mut self, description: String, instruction: Instruction) {
+        self.steps.push(PlanStep {
+            description,
+            instruction,
+        });
+    }
+}
+
+impl RecipeSpec {
+    pub fn from_json(json: &str) -> Result<Self, ClientError> {
+        serde_json::from_str(json).map_err(|error| ClientError::InvalidSpec(error.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, ClientError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|error| ClientError::InvalidSpec(error.to_string()))
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, ClientError> {
+        toml::from_str(toml).map_err(|error| ClientError::InvalidSpec(error.to_string()))
+    }
+
+    pub fn to_toml(&self) -> Result<String, ClientError> {
+        toml::to_string(self).map_err(|error| ClientError::InvalidSpec(error.to_string()))
+    }
+
+    /// Ensure the spec can be converged to, regardless of the on-chain recipe.
+    pub fn validate(&self) -> Result<(), ClientError> {
+        let mut input_keys = HashSet::new();
+        for input in &self.inputs {
+            if !input_keys.insert(input_key(&input.into())) {
+                return Err(ClientError::InvalidSpec(format!(
+                    "duplicated input ingredient {:?}",
+                    input
+                )));
+            }
+        }
+        let mut output_mints = HashSet::new();
+        for output in &self.outputs {
+            if let Some(mint) = output_mint(&output.into()) {
+                if !output_mints.insert(mint) {
+                    return Err(ClientError::InvalidSpec(format!(
+                        "duplicated output ingredient for mint [{}]",
+                        mint
+                    )));
+                }
+            }
+        }
+        if let (Some(start_time), Some(end_time)) = (self.start_time, self.end_time) {
+            if start_time >= end_time {
+                return Err(ClientError::InvalidSpec(
+                    "start_time must be before end_time".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&Recipe> for RecipeSpec {
+    fn from(recipe: &Recipe) -> Self {
+        Self {
+            status: match recipe.status {
+                RecipeStatus::Paused => StatusSpec::Paused,
+                RecipeStatus::Active => StatusSpec::Active,
+            },
+            fees: recipe.fees,
+            max_crafts_per_wallet: recipe.max_crafts_per_wallet,
+            start_time: Some(recipe.start_time).filter(|time| *time != 0),
+            end_time: Some(recipe.end_time).filter(|time| *time != 0),
+            feature_levels: (&recipe.feature_levels).into(),
+            inputs: recipe.inputs.iter().map(Into::into).collect(),
+            outputs: recipe.outputs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&FeatureLevels> for FeatureLevelsSpec {
+    fn from(levels: &FeatureLevels) -> Self {
+        Self {
+            fees: levels.fees,
+            additional_outputs: levels.additional_outputs,
+            transfer_inputs: levels.transfer_inputs,
+            max_supply: levels.max_supply,
+            sol_payment: levels.sol_payment,
+            wisdom: levels.wisdom,
+        }
+    }
+}
+
+impl From<&IngredientInput> for InputSpec {
+    fn from(input: &IngredientInput) -> Self {
+        match *input {
+            IngredientInput::BurnToken { mint, amount } => Self::BurnToken { mint, amount },
+            IngredientInput::TransferToken {
+                mint,
+                amount,
+                destination,
+            } => Self::TransferToken {
+                mint,
+                amount,
+                destination,
+            },
+            IngredientInput::TransferSol {
+                lamports,
+                destination,
+            } => Self::TransferSol {
+                lamports,
+                destination,
+            },
+            IngredientInput::HoldToken { mint, amount } => Self::HoldToken { mint, amount },
+        }
+    }
+}
+
+impl From<&InputSpec> for IngredientInput {
+    fn from(input: &InputSpec) -> Self {
+        match *input {
+            InputSpec::BurnToken { mint, amount } => Self::BurnToken { mint, amount },
+            InputSpec::TransferToken {
+                mint,
+                amount,
+                destination,
+            } => Self::TransferToken {
+                mint,
+                amount,
+                destination,
+            },
+            InputSpec::TransferSol {
+                lamports,
+                destination,
+            } => Self::TransferSol {
+                lamports,
+                destination,
+            },
+            InputSpec::HoldToken { mint, amount } => Self::HoldToken { mint, amount },
+        }
+    }
+}
+
+impl From<&IngredientOutput> for OutputSpec {
+    fn from(output: &IngredientOutput) -> Self {
+        match output {
+            IngredientOutput::MintToken { mint, amount } => Self::MintToken {
+                mint: *mint,
+                amount: *amount,
+            },
+            IngredientOutput::MintTokenWithMaxSupply {
+                mint,
+                amount,
+                max_supply,
+            } => Self::MintTokenWithMaxSupply {
+                mint: *mint,
+                amount: *amount,
+                max_supply: *max_supply,
+            },
+            IngredientOutput::TransferToken { mint, amount } => Self::TransferToken {
+                mint: *mint,
+                amount: *amount,
+            },
+            IngredientOutput::MintTokenFromLootTable { rolls, entries } => {
+                Self::MintTokenFromLootTable {
+                    rolls: *rolls,
+                    entries: entries
+                        .iter()
+                        .map(|entry| LootTableEntrySpec {
+                            mint: entry.mint,
+                            amount: entry.amount,
+                            weight: entry.weight,
+                        })
+                        .collect(),
+                }
+            }
+        }
+    }
+}
+
+impl From<&OutputSpec> for IngredientOutput {
+    fn from(output: &OutputSpec) -> Self {
+        match output {
+            OutputSpec::MintToken { mint, amount } => Self::MintToken {
+                mint: *mint,
+                amount: *amount,
+            },
+            OutputSpec::MintTokenWithMaxSupply {
+                mint,
+                amount,
+                max_supply,
+            } => Self::MintTokenWithMaxSupply {
+                mint: *mint,
+                amount: *amount,
+                max_supply: *max_supply,
+            },
+            OutputSpec::TransferToken { mint, amount } => Self::TransferToken {
+                mint: *mint,
+                amount: *amount,
+            },
+            OutputSpec::MintTokenFromLootTable { rolls, entries } => Self::MintTokenFromLootTable {
+                rolls: *rolls,
+                entries: entries
+                    .iter()
+                    .map(|entry| LootTableEntry {
+                        mint: entry.mint,
+                        amount: entry.amount,
+                        weight: entry.weight,
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Fetch the given recipe and plan the instructions needed to match the spec.
+pub fn plan_recipe<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    spec: &RecipeSpec,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<RecipePlan, ClientError> {
+    let recipe_account = fetch_recipe(fetcher, recipe)?;
+    plan_recipe_with_recipe(fetcher, recipe, &recipe_account, spec, authority, payer)
+}
+
+/// Plan the instructions needed for an already fetched recipe to match the spec.
+///
+/// Steps are ordered as follows: pause the recipe, remove ingredients,
+/// update ingredients, add ingredients, update settings and activate the recipe.
+/// Feature levels cannot be changed by the recipe authority and are only checked.
+pub fn plan_recipe_with_recipe<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    recipe_account: &Recipe,
+    spec: &RecipeSpec,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<RecipePlan, ClientError> {
+    spec.validate()?;
+    assert_feature_levels(&recipe_account.feature_levels, &spec.feature_levels)?;
+
+    let mut plan = RecipePlan::default();
+    let mut removals = RecipePlan::default();
+    let mut updates = RecipePlan::default();
+    let mut additions = RecipePlan::default();
+
+    // Pause.
+    if spec.status == StatusSpec::Paused && recipe_account.status == RecipeStatus::Active {
+        plan.push("Pause recipe".to_string(), pause_recipe(recipe, authority));
+    }
+
+    // Inputs.
+    let desired_inputs: Vec<IngredientInput> = spec.inputs.iter().map(Into::into).collect();
+    for current in &recipe_account.inputs {
+        let desired = desired_inputs
+            .iter()
+            .find(|input| input_key(input) == input_key(current))
+            .filter(|desired| can_update_input(current, desired));
+        match desired {
+            Some(desired) => {
+                if let Some(update) = input_update(current, desired) {
+                    updates.push(
+                        format!("Update input {:?}", desired),
+                        update.instruction(recipe, authority, payer),
+                    );
+                }
+            }
+            None => removals.push(
+                format!("Remove input {:?}", current),
+                remove_input(fetcher, recipe, current, authority, payer)?,
+            ),
+        }
+    }
+    for desired in &desired_inputs {
+        let current = recipe_account
+            .inputs
+            .iter()
+            .find(|input| input_key(input) == input_key(desired));
+        if !matches!(current, Some(current) if can_update_input(current, desired)) {
+            additions.push(
+                format!("Add input {:?}", desired),
+                add_input(fetcher, recipe, desired, authority, payer)?,
+            );
+        }
+    }
+
+    // Outputs.
+    let desired_outputs: Vec<IngredientOutput> = spec.outputs.iter().map(Into::into).collect();
+    let mut matched_loot_tables = vec![false; desired_outputs.len()];
+    let mut loot_table_removals = vec![];
+    for (index, current) in recipe_account.outputs.iter().enumerate() {
+        if let IngredientOutput::MintTokenFromLootTable { entries, .. } = current {
+            let position = desired_outputs
+                .iter()
+                .enumerate()
+                .position(|(i, output)| !matched_loot_tables[i] && output == current);
+            match position {
+                Some(position) => matched_loot_tables[position] = true,
+                None => loot_table_removals.push((index, entries)),
+            }
+            continue;
+        }
+        let desired = desired_outputs
+            .iter()
+            .find(|output| output_mint(output) == output_mint(current))
+            .filter(|desired| can_update_output(current, desired));
+        match desired {
+            Some(desired) => {
+                if let Some(update) = output_update(current, desired) {
+                    updates.push(
+                        format!("Update output {:?}", desired),
+                        update.instruction(recipe, authority, payer),
+                    );
+                }
+            }
+            None => removals.push(
+                format!("Remove output {:?}", current),
+                remove_output(fetcher, recipe, current, authority, payer)?,
+            ),
+        }
+    }
+    // Loot tables are removed by index, starting from the last one so earlier indices
+    // remain valid, and before any other output is removed.
+    for (index, entries) in loot_table_removals.into_iter().rev() {
+        plan.push(
+            format!("Remove loot table output #{}", index),
+            remove_loot_table_output(
+                recipe,
+                authority,
+                payer,
+                &loot_table_token_program(fetcher, entries)?,
+                index as u16,
+                entries,
+            ),
+        );
+    }
+    for (index, desired) in desired_outputs.iter().enumerate() {
+        if let IngredientOutput::MintTokenFromLootTable { rolls, entries } = desired {
+            if !matched_loot_tables[index] {
+                additions.push(
+                    format!("Add loot table output {:?}", desired),
+                    add_loot_table_output(
+                        recipe,
+                        authority,
+                        payer,
+                        &loot_table_token_program(fetcher, entries)?,
+                        *rolls,
+                        entries.clone(),
+                    ),
+                );
+            }
+            continue;
+        }
+        let current = recipe_account
+            .outputs
+            .iter()
+            .find(|output| output_mint(output) == output_mint(desired));
+        if matches!(current, Some(current) if can_update_output(current, desired)) {
+            continue;
+        }
+        additions.push(
+            format!("Add output {:?}", desired),
+            add_output(fetcher, recipe, desired, authority, payer)?,
+        );
+    }
+
+    plan.steps.append(&mut removals.steps);
+    plan.steps.append(&mut updates.steps);
+    plan.steps.append(&mut additions.steps);
+
+    // Settings.
+    if spec.fees != recipe_account.fees {
+        plan.push(
+            format!("Set fees to {}", spec.fees),
+            set_fees(recipe, authority, spec.fees),
+        );
+    }
+    if spec.max_crafts_per_wallet != recipe_account.max_crafts_per_wallet {
+        plan.push(
+            format!(
+                "Set max crafts per wallet to {}",
+                spec.max_crafts_per_wallet
+            ),
+            set_max_crafts_per_wallet(recipe, authority, spec.max_crafts_per_wallet),
+        );
+    }
+    if spec.start_time.unwrap_or(0) != recipe_account.start_time
+        || spec.end_time.unwrap_or(0) != recipe_account.end_time
+    {
+        plan.push(
+            format!(
+                "Set time window to {:?}..{:?}",
+                spec.start_time, spec.end_time
+            ),
+            set_time_window(recipe, authority, spec.start_time, spec.end_time),
+        );
+    }
+
+    // Activate.
+    if spec.status == StatusSpec::Active && recipe_account.status == RecipeStatus::Paused {
+        plan.push(
+            "Activate recipe".to_string(),
+            activate_recipe(recipe, authority),
+        );
+    }
+
+    Ok(plan)
+}
+
+fn assert_feature_levels(
+    current: &FeatureLevels,
+    required: &FeatureLevelsSpec,
+) -> Result<(), ClientError> {
+    let levels = [
+        ("fees", current.fees, required.fees),
+        (
+            "additional_outputs",
+            current.additional_outputs,
+            required.additional_outputs,
+        ),
+        (
+            "transfer_inputs",
+            current.transfer_inputs,
+            required.transfer_inputs,
+        ),
+        ("max_supply", current.max_supply, required.max_supply),
+        ("sol_payment", current.sol_payment, required.sol_payment),
+        ("wisdom", current.wisdom, required.wisdom),
+    ];
+    for (feature, current, required) in levels {
+        if current < required {
+            return Err(ClientError::FeatureLevelTooLow(
+                feature.to_string(),
+                current,
+                required,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Token inputs are identified by their mint and the SOL payment input by `None`.
+fn input_key(input: &IngredientInput) -> Option<Pubkey> {
+    match input {
+        IngredientInput::BurnToken { mint, .. }
+        | IngredientInput::TransferToken { mint, .. }
+        | IngredientInput::HoldToken { mint, .. } => Some(*mint),
+        IngredientInput::TransferSol { .. } => None,
+    }
+}
+
+/// Loot table outputs have no mint and are compared as a whole.
+fn output_mint(output: &IngredientOutput) -> Option<Pubkey> {
+    match output {
+        IngredientOutput::MintToken { mint, .. }
+        | IngredientOutput::MintTokenWithMaxSupply { mint, .. }
+        | IngredientOutput::TransferToken { mint, .. } => Some(*mint),
+        IngredientOutput::MintTokenFromLootTable { .. } => None,
+    }
+}
+
+fn input_type(input: &IngredientInput) -> IngredientType {
+    match input {
+        IngredientInput::BurnToken { .. } => IngredientType::BurnTokenInput,
+        IngredientInput::TransferToken { .. } => IngredientType::TransferTokenInput,
+        IngredientInput::TransferSol { .. } => IngredientType::TransferSolInput,
+        IngredientInput::HoldToken { .. } => IngredientType::HoldTokenInput,
+    }
+}
+
+fn output_type(output: &IngredientOutput) -> IngredientType {
+    match output {
+        IngredientOutput::MintToken { .. } => IngredientType::MintTokenOutput,
+        IngredientOutput::MintTokenWithMaxSupply { .. } => {
+            IngredientType::MintTokenWithMaxSupplyOutput
+        }
+        IngredientOutput::TransferToken { .. } => IngredientType::TransferTokenOutput,
+        // Loot tables have dedicated instructions.
+        IngredientOutput::MintTokenFromLootTable { .. } => unreachable!(),
+    }
+}
+
+/// The arguments of an `UpdateIngredient` instruction.
+struct IngredientUpdate {
+    mint: Option<Pubkey>,
+    ingredient_type: IngredientType,
+    new_ingredient_type: Option<IngredientType>,
+    amount: Option<u64>,
+    destination: Option<Pubkey>,
+    max_supply: Option<u64>,
+}
+
+impl IngredientUpdate {
+    fn instruction(self, recipe: &Pubkey, authority: &Pubkey, payer: &Pubkey) -> Instruction {
+        update_ingredient(
+            recipe,
+            self.mint.as_ref(),
+            authority,
+            payer,
+            self.ingredient_type,
+            self.new_ingredient_type,
+            self.amount,
+            self.destination,
+            self.max_supply,
+        )
+    }
+}
+
+/// Mirrors the ingredient swaps allowed by the `UpdateIngredient` instruction.
+fn can_update_input(current: &IngredientInput, desired: &IngredientInput) -> bool {
+    matches!(
+        (current, desired),
+        (
+            IngredientInput::BurnToken { .. } | IngredientInput::TransferToken { .. },
+            IngredientInput::BurnToken { .. } | IngredientInput::TransferToken { .. }
+        ) | (
+            IngredientInput::TransferSol { .. },
+            IngredientInput::TransferSol { .. }
+        ) | (
+            IngredientInput::HoldToken { .. },
+            IngredientInput::HoldToken { .. }
+        )
+    )
+}
+
+fn can_update_output(current: &IngredientOutput, desired: &IngredientOutput) -> bool {
+    matches!(
+        (current, desired),
+        (
+            IngredientOutput::MintToken { .. } | IngredientOutput::MintTokenWithMaxSupply { .. },
+            IngredientOutput::MintToken { .. } | IngredientOutput::MintTokenWithMaxSupply { .. }
+        ) | (
+            IngredientOutput::TransferToken { .. },
+            IngredientOutput::TransferToken { .. }
+        )
+    )
+}
+
+/// The update turning `current` into `desired`, if they differ.
+/// Both ingredients must be compatible as per `can_update_input`.
+fn input_update(current: &IngredientInput, desired: &IngredientInput) -> Option<IngredientUpdate> {
+    if current == desired {
+        return None;
+    }
+    let (current_amount, current_destination) = input_amount_and_destination(current);
+    let (amount, destination) = input_amount_and_destination(desired);
+    let (ingredient_type, new_ingredient_type) = (input_type(current), input_type(desired));
+    Some(IngredientUpdate {
+        mint: input_key(current),
+        new_ingredient_type: Some(new_ingredient_type.clone())
+            .filter(|new_ingredient_type| *new_ingredient_type != ingredient_type),
+        ingredient_type,
+        amount: Some(amount).filter(|amount| *amount != current_amount),
+        destination: destination.filter(|destination| Some(*destination) != current_destination),
+        max_supply: None,
+    })
+}
+
+/// The update turning `current` into `desired`, if they differ.
+/// Both ingredients must be compatible as per `can_update_output`.
+fn output_update(
+    current: &IngredientOutput,
+    desired: &IngredientOutput,
+) -> Option<IngredientUpdate> {
+    if current == desired {
+        return None;
+    }
+    let (current_amount, current_max_supply) = output_amount_and_max_supply(current);
+    let (amount, max_supply) = output_amount_and_max_supply(desired);
+    let (ingredient_type, new_ingredient_type) = (output_type(current), output_type(desired));
+    Some(IngredientUpdate {
+        mint: output_mint(current),
+        new_ingredient_type: Some(new_ingredient_type.clone())
+            .filter(|new_ingredient_type| *new_ingredient_type != ingredient_type),
+        ingredient_type,
+        amount: Some(amount).filter(|amount| *amount != current_amount),
+        destination: None,
+        max_supply: max_supply.filter(|max_supply| Some(*max_supply) != current_max_supply),
+    })
+}
+
+fn input_amount_and_destination(input: &IngredientInput) -> (u64, Option<Pubkey>) {
+    match *input {
+        IngredientInput::BurnToken { amount, .. } | IngredientInput::HoldToken { amount, .. } => {
+            (amount, None)
+        }
+        IngredientInput::TransferToken {
+            amount,
+            destination,
+            ..
+        } => (amount, Some(destination)),
+        IngredientInput::TransferSol {
+            lamports,
+            destination,
+        } => (lamports, Some(destination)),
+    }
+}
+
+fn output_amount_and_max_supply(output: &IngredientOutput) -> (u64, Option<u64>) {
+    match *output {
+        IngredientOutput::MintToken { amount, .. }
+        | IngredientOutput::TransferToken { amount, .. } => (amount, None),
+        IngredientOutput::MintTokenWithMaxSupply {
+            amount, max_supply, ..
+        } => (amount, Some(max_supply)),
+        IngredientOutput::MintTokenFromLootTable { .. } => (0, None),
+    }
+}
+
+fn add_input<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    input: &IngredientInput,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ClientError> {
+    let mint = input_key(input);
+    let (amount, destination) = input_amount_and_destination(input);
+    Ok(add_ingredient(
+        recipe,
+        mint.as_ref(),
+        authority,
+        payer,
+        &optional_token_program(fetcher, mint.as_ref())?,
+        input_type(input),
+        amount,
+        destination,
+        None,
+    ))
+}
+
+fn remove_input<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    input: &IngredientInput,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ClientError> {
+    let mint = input_key(input);
+    Ok(remove_ingredient(
+        recipe,
+        mint.as_ref(),
+        authority,
+        payer,
+        &optional_token_program(fetcher, mint.as_ref())?,
+        input_type(input),
+    ))
+}
+
+fn add_output<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    output: &IngredientOutput,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ClientError> {
+    let mint = output_mint(output);
+    let (amount, max_supply) = output_amount_and_max_supply(output);
+    Ok(add_ingredient(
+        recipe,
+        mint.as_ref(),
+        authority,
+        payer,
+        &optional_token_program(fetcher, mint.as_ref())?,
+        output_type(output),
+        amount,
+        None,
+        max_supply,
+    ))
+}
+
+fn remove_output<F: AccountFetcher>(
+    fetcher: &F,
+    recipe: &Pubkey,
+    output: &IngredientOutput,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ClientError> {
+    let mint = output_mint(output);
+    Ok(remove_ingredient(
+        recipe,
+        mint.as_ref(),
+        authority,
+        payer,
+        &optional_token_program(fetcher, mint.as_ref())?,
+        output_type(output),
+    ))
+}
+
+/// The token program owning the mint, or SPL Token for ingredients without a mint.
+fn optional_token_program<F: AccountFetcher>(
+    fetcher: &F,
+    mint: Option<&Pubkey>,
+) -> Result<Pubkey, ClientError> {
+    match mint {
+        Some(mint) => token_program(fetcher, mint),
+        None => Ok(spl_token::id()),
+    }
+}
+
+/// Loot table instructions use a single token program for every entry.
+fn loot_table_token_program<F: AccountFetcher>(
+    fetcher: &F,
+    entries: &[LootTableEntry],
+) -> Result<Pubkey, ClientError> {
+    optional_token_program(fetcher, entries.first().map(|entry| &entry.mint))
+}
+
+fn token_program<F: AccountFetcher>(fetcher: &F, mint: &Pubkey) -> Result<Pubkey, ClientError> {
+    let owner = fetcher
+        .get_account(mint)?
+        .ok_or(ClientError::AccountNotFound(*mint))?
+        .owner;
+    if owner == spl_token::id() || owner == spl_token_2022::id() {
+        Ok(owner)
+    } else {
+        Err(ClientError::InvalidTokenProgram(*mint))
+    }
+}
+
+/// Serialize public keys as base58 strings.
+mod pubkey_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use solana_program::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        Pubkey::from_str(&string).map_err(D::Error::custom)
+    }
+}
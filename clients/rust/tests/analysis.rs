@@ -0,0 +1,147 @@
+use solana_program::pubkey::Pubkey;
+use token_recipes_client::{
+    analysis::{check_recipe_activation, find_arbitrage, find_loops},
+    discovery::RecipeGraph,
+    error::ClientError,
+    token_recipes::state::{
+        features::{
+            fees::{FeesFeature, DEFAULT_BASE_FEES, DEFAULT_FEE_PERCENTAGES},
+            FeatureLevels,
+        },
+        ingredient_input::IngredientInput,
+        ingredient_output::IngredientOutput,
+        key::Key,
+        recipe::{Recipe, RecipeStatus},
+    },
+};
+
+fn recipe(input: (Pubkey, u64), output: (Pubkey, u64)) -> Recipe {
+    Recipe {
+        key: Key::Recipe,
+        base: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        pending_authority: Pubkey::default(),
+        status: RecipeStatus::Active,
+        total_crafts: 0,
+        total_crafts_with_quantity: 0,
+        fees: 0,
+        max_crafts_per_wallet: 0,
+        start_time: 0,
+        end_time: 0,
+        allowlist_root: [0; 32],
+        accumulated_admin_fees: 0,
+        accumulated_shards: 0,
+        accumulated_experience: 0,
+        feature_levels: FeatureLevels::default(),
+        version: Recipe::VERSION,
+        bump: 0,
+        inputs: vec![IngredientInput::BurnToken {
+            mint: input.0,
+            amount: input.1,
+        }],
+        outputs: vec![IngredientOutput::MintToken {
+            mint: output.0,
+            amount: output.1,
+        }],
+    }
+}
+
+fn fees_feature() -> FeesFeature {
+    FeesFeature {
+        key: Key::FeesFeature,
+        admin_destination: Pubkey::new_unique(),
+        shard_mint: Pubkey::new_unique(),
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        mint_burn_3: Pubkey::new_unique(),
+        mint_burn_4: Pubkey::new_unique(),
+        mint_burn_5: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        mint_skill_2: Pubkey::new_unique(),
+        mint_skill_3: Pubkey::new_unique(),
+        bump: 0,
+        version: FeesFeature::VERSION,
+        base_fees: DEFAULT_BASE_FEES,
+        fee_percentages: DEFAULT_FEE_PERCENTAGES,
+    }
+}
+
+#[test]
+fn it_detects_loops_creating_tokens() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (a_to_b, b_to_a) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut b_to_a_account = recipe((b, 1), (a, 1));
+    b_to_a_account.inputs.push(IngredientInput::TransferSol {
+        lamports: 1_000,
+        destination: Pubkey::new_unique(),
+    });
+    let graph = RecipeGraph::new([
+        (a_to_b, recipe((a, 1), (b, 2))),
+        (b_to_a, b_to_a_account.clone()),
+    ]);
+
+    // 1 A → 2 B → 2 A.
+    let loops = find_arbitrage(&graph, &fees_feature()).unwrap();
+    assert_eq!(loops.len(), 1);
+    let recipe_loop = &loops[0];
+    assert_eq!(recipe_loop.multiplier, 2.0);
+    assert_eq!(recipe_loop.net_tokens, 1.0);
+    assert_eq!(recipe_loop.steps.len(), 2);
+    let b_to_a_crafts = recipe_loop
+        .steps
+        .iter()
+        .find(|step| step.recipe == b_to_a)
+        .unwrap()
+        .crafts;
+    assert_eq!(b_to_a_crafts, 2.0);
+    assert_eq!(
+        recipe_loop.lamports_cost,
+        DEFAULT_BASE_FEES as f64 * 3.0 + 2_000.0
+    );
+
+    // The loop is only detected once the recipe is activated.
+    b_to_a_account.status = RecipeStatus::Paused;
+    let graph = RecipeGraph::new([
+        (a_to_b, recipe((a, 1), (b, 2))),
+        (b_to_a, b_to_a_account.clone()),
+    ]);
+    assert!(find_loops(&graph, &fees_feature()).unwrap().is_empty());
+    assert_eq!(
+        check_recipe_activation(&graph, &b_to_a, &b_to_a_account, &fees_feature())
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn it_ignores_loops_losing_tokens() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let graph = RecipeGraph::new([
+        (Pubkey::new_unique(), recipe((a, 2), (b, 1))),
+        (Pubkey::new_unique(), recipe((b, 1), (a, 1))),
+    ]);
+
+    let loops = find_loops(&graph, &fees_feature()).unwrap();
+    assert_eq!(loops.len(), 1);
+    assert_eq!(loops[0].multiplier, 0.5);
+    assert!(find_arbitrage(&graph, &fees_feature()).unwrap().is_empty());
+}
+
+#[test]
+fn it_fails_when_the_lamports_per_craft_overflow() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let b_to_a = Pubkey::new_unique();
+    let mut b_to_a_account = recipe((b, 1), (a, 1));
+    b_to_a_account.inputs.push(IngredientInput::TransferSol {
+        lamports: u64::MAX,
+        destination: Pubkey::new_unique(),
+    });
+    let graph = RecipeGraph::new([
+        (Pubkey::new_unique(), recipe((a, 1), (b, 1))),
+        (b_to_a, b_to_a_account),
+    ]);
+
+    let result = find_loops(&graph, &fees_feature());
+    assert!(matches!(result, Err(ClientError::LamportsOverflow(recipe)) if recipe == b_to_a));
+}
@@ -0,0 +1,287 @@
+use borsh::BorshSerialize;
+use solana_program::{instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::account::Account;
+use std::collections::HashMap;
+use token_recipes_client::{
+    craft::{resolve_craft_with_recipe, resolve_reveal_loot},
+    token_recipes::{
+        pda::{
+            find_associated_token_address, find_crafter_record_pda, find_delegated_ingredient_pda,
+            find_fees_feature_pda, find_loot_roll_pda,
+        },
+        state::{
+            features::FeatureLevels,
+            ingredient_input::IngredientInput,
+            ingredient_output::{IngredientOutput, LootTableEntry},
+            key::Key,
+            loot_roll::{LootRoll, LootTableRolls},
+            recipe::{Recipe, RecipeStatus},
+        },
+        utils::{TRANSFER_HOOK_EXTENSION_TYPE, TRANSFER_HOOK_VALIDATION_SEED},
+    },
+};
+
+fn recipe(inputs: Vec<IngredientInput>, outputs: Vec<IngredientOutput>) -> Recipe {
+    Recipe {
+        key: Key::Recipe,
+        base: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        pending_authority: Pubkey::default(),
+        status: RecipeStatus::Active,
+        total_crafts: 0,
+        total_crafts_with_quantity: 0,
+        fees: 0,
+        max_crafts_per_wallet: 0,
+        start_time: 0,
+        end_time: 0,
+        allowlist_root: [0; 32],
+        accumulated_admin_fees: 0,
+        accumulated_shards: 0,
+        accumulated_experience: 0,
+        feature_levels: FeatureLevels::default(),
+        version: Recipe::VERSION,
+        bump: 0,
+        inputs,
+        outputs,
+    }
+}
+
+fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn it_resolves_accounts_in_ingredient_order() {
+    let (recipe_address, owner, payer) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (burn_mint, mint_mint, loot_mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let sol_destination = Pubkey::new_unique();
+    let mut recipe_account = recipe(
+        vec![
+            IngredientInput::BurnToken {
+                mint: burn_mint,
+                amount: 1,
+            },
+            IngredientInput::TransferSol {
+                lamports: 1,
+                destination: sol_destination,
+            },
+        ],
+        vec![
+            IngredientOutput::MintToken {
+                mint: mint_mint,
+                amount: 1,
+            },
+            IngredientOutput::MintTokenFromLootTable {
+                rolls: 1,
+                entries: vec![LootTableEntry {
+                    mint: loot_mint,
+                    amount: 1,
+                    weight: 1,
+                }],
+            },
+        ],
+    );
+    recipe_account.max_crafts_per_wallet = 3;
+
+    let mut fetcher = HashMap::new();
+    fetcher.insert(burn_mint, account(spl_token::id(), vec![]));
+    fetcher.insert(mint_mint, account(spl_token::id(), vec![]));
+
+    let instruction = resolve_craft_with_recipe(
+        &fetcher,
+        &recipe_address,
+        &recipe_account,
+        &owner,
+        &payer,
+        1,
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(
+        instruction.accounts[6],
+        AccountMeta::new(find_crafter_record_pda(&recipe_address, &owner).0, false)
+    );
+    assert_eq!(
+        instruction.accounts[7],
+        AccountMeta::new_readonly(find_fees_feature_pda().0, false)
+    );
+    assert_eq!(
+        instruction.accounts[8..],
+        [
+            AccountMeta::new(burn_mint, false),
+            AccountMeta::new(
+                find_associated_token_address(&owner, &burn_mint, &spl_token::id()),
+                false
+            ),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(sol_destination, false),
+            AccountMeta::new(mint_mint, false),
+            AccountMeta::new(
+                find_associated_token_address(&owner, &mint_mint, &spl_token::id()),
+                false
+            ),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(find_delegated_ingredient_pda(&mint_mint).0, false),
+            AccountMeta::new(find_loot_roll_pda(&recipe_address, &owner).0, false),
+        ]
+    );
+}
+
+#[test]
+fn it_resolves_the_entries_of_a_loot_roll() {
+    let (recipe_address, owner, payer, craft_payer) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (first_mint, second_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let entry = |mint| LootTableEntry {
+        mint,
+        amount: 1,
+        weight: 1,
+    };
+    let loot_roll_account = LootRoll {
+        key: Key::LootRoll,
+        recipe: recipe_address,
+        owner,
+        payer: craft_payer,
+        total_crafts: 0,
+        slot: 1,
+        version: LootRoll::VERSION,
+        loot_tables: vec![
+            LootTableRolls {
+                rolls: 1,
+                entries: vec![entry(first_mint)],
+            },
+            LootTableRolls {
+                rolls: 2,
+                entries: vec![entry(second_mint)],
+            },
+        ],
+    };
+
+    let (loot_roll, _) = find_loot_roll_pda(&recipe_address, &owner);
+    let mut fetcher = HashMap::new();
+    fetcher.insert(
+        loot_roll,
+        account(
+            token_recipes_client::token_recipes::id(),
+            loot_roll_account.try_to_vec().unwrap(),
+        ),
+    );
+    fetcher.insert(first_mint, account(spl_token::id(), vec![]));
+    fetcher.insert(second_mint, account(spl_token_2022::id(), vec![]));
+
+    let instruction = resolve_reveal_loot(&fetcher, &recipe_address, &owner, &payer).unwrap();
+
+    let token_2022 = spl_token_2022::id();
+    assert_eq!(instruction.accounts[0], AccountMeta::new(loot_roll, false));
+    assert_eq!(
+        instruction.accounts[3],
+        AccountMeta::new(craft_payer, false)
+    );
+    assert_eq!(
+        instruction.accounts[7..],
+        [
+            AccountMeta::new(first_mint, false),
+            AccountMeta::new(
+                find_associated_token_address(&owner, &first_mint, &spl_token::id()),
+                false
+            ),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(find_delegated_ingredient_pda(&first_mint).0, false),
+            AccountMeta::new(second_mint, false),
+            AccountMeta::new(
+                find_associated_token_address(&owner, &second_mint, &token_2022),
+                false
+            ),
+            AccountMeta::new_readonly(token_2022, false),
+            AccountMeta::new_readonly(find_delegated_ingredient_pda(&second_mint).0, false),
+        ]
+    );
+}
+
+#[test]
+fn it_resolves_transfer_hook_accounts() {
+    let (recipe_address, owner, payer) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (mint, destination, hook_program, extra_account) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let recipe_account = recipe(
+        vec![IngredientInput::TransferToken {
+            mint,
+            amount: 1,
+            destination,
+        }],
+        vec![],
+    );
+
+    // A Token-2022 mint with a transfer hook extension.
+    let mut mint_data = vec![0; spl_token_2022::state::Account::LEN + 1];
+    mint_data.extend(TRANSFER_HOOK_EXTENSION_TYPE.to_le_bytes());
+    mint_data.extend(64u16.to_le_bytes());
+    mint_data.extend([0; 32]);
+    mint_data.extend(hook_program.to_bytes());
+
+    // A validation account listing a single writable extra account.
+    let (validation, _) = Pubkey::find_program_address(
+        &[TRANSFER_HOOK_VALIDATION_SEED.as_bytes(), mint.as_ref()],
+        &hook_program,
+    );
+    let mut validation_data = vec![0; 12];
+    validation_data.extend(1u32.to_le_bytes());
+    validation_data.push(0);
+    validation_data.extend(extra_account.to_bytes());
+    validation_data.extend([0, 1]);
+
+    let mut fetcher = HashMap::new();
+    fetcher.insert(mint, account(spl_token_2022::id(), mint_data));
+    fetcher.insert(validation, account(hook_program, validation_data));
+
+    let instruction = resolve_craft_with_recipe(
+        &fetcher,
+        &recipe_address,
+        &recipe_account,
+        &owner,
+        &payer,
+        1,
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(
+        instruction.accounts[6],
+        AccountMeta::new_readonly(token_recipes_client::token_recipes::id(), false)
+    );
+    assert_eq!(
+        instruction.accounts[13..],
+        [
+            AccountMeta::new_readonly(hook_program, false),
+            AccountMeta::new_readonly(validation, false),
+            AccountMeta::new(extra_account, false),
+        ]
+    );
+}
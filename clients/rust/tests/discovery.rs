@@ -0,0 +1,145 @@
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::collections::{BTreeSet, HashMap};
+use token_recipes_client::{
+    discovery::{find_recipes_consuming, find_recipes_producing, IngredientKind, RecipeGraph},
+    token_recipes::{
+        pda::find_ingredient_record_pda,
+        state::{
+            features::FeatureLevels,
+            ingredient_input::IngredientInput,
+            ingredient_output::{IngredientOutput, LootTableEntry},
+            ingredient_record::IngredientRecord,
+            key::Key,
+            recipe::{Recipe, RecipeStatus},
+        },
+    },
+};
+
+fn recipe(inputs: Vec<IngredientInput>, outputs: Vec<IngredientOutput>) -> Recipe {
+    Recipe {
+        key: Key::Recipe,
+        base: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        pending_authority: Pubkey::default(),
+        status: RecipeStatus::Active,
+        total_crafts: 0,
+        total_crafts_with_quantity: 0,
+        fees: 0,
+        max_crafts_per_wallet: 0,
+        start_time: 0,
+        end_time: 0,
+        allowlist_root: [0; 32],
+        accumulated_admin_fees: 0,
+        accumulated_shards: 0,
+        accumulated_experience: 0,
+        feature_levels: FeatureLevels::default(),
+        version: Recipe::VERSION,
+        bump: 0,
+        inputs,
+        outputs,
+    }
+}
+
+fn account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1,
+        data,
+        owner: token_recipes_client::token_recipes::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Insert a recipe along with the ingredient records the program would create.
+fn insert_recipe(accounts: &mut HashMap<Pubkey, Account>, address: Pubkey, recipe: &Recipe) {
+    let graph = RecipeGraph::new([(address, recipe.clone())]);
+    for mint in graph.mints() {
+        let (record_address, bump) = find_ingredient_record_pda(&mint, &address);
+        let record = IngredientRecord {
+            key: Key::IngredientRecord,
+            input: graph.inputs_of(&address).any(|edge| edge.mint == mint),
+            output: graph.outputs_of(&address).any(|edge| edge.mint == mint),
+            mint,
+            recipe: address,
+            bump,
+            version: IngredientRecord::VERSION,
+        };
+        accounts.insert(record_address, account(record.try_to_vec().unwrap()));
+    }
+    accounts.insert(address, account(recipe.try_to_vec().unwrap()));
+}
+
+#[test]
+fn it_discovers_recipes_by_mint() {
+    let (ore, ingot, gem) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (smelt, mine) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = HashMap::new();
+    insert_recipe(
+        &mut accounts,
+        smelt,
+        &recipe(
+            vec![IngredientInput::BurnToken {
+                mint: ore,
+                amount: 3,
+            }],
+            vec![IngredientOutput::MintToken {
+                mint: ingot,
+                amount: 1,
+            }],
+        ),
+    );
+    insert_recipe(
+        &mut accounts,
+        mine,
+        &recipe(
+            vec![IngredientInput::HoldToken {
+                mint: ingot,
+                amount: 1,
+            }],
+            vec![IngredientOutput::MintTokenFromLootTable {
+                rolls: 2,
+                entries: vec![
+                    LootTableEntry {
+                        mint: ore,
+                        amount: 4,
+                        weight: 3,
+                    },
+                    LootTableEntry {
+                        mint: gem,
+                        amount: 1,
+                        weight: 1,
+                    },
+                ],
+            }],
+        ),
+    );
+
+    assert_eq!(find_recipes_consuming(&accounts, &ore).unwrap(), [smelt]);
+    assert_eq!(find_recipes_producing(&accounts, &ore).unwrap(), [mine]);
+    assert_eq!(find_recipes_consuming(&accounts, &ingot).unwrap(), [mine]);
+    assert!(find_recipes_consuming(&accounts, &gem).unwrap().is_empty());
+
+    let graph = RecipeGraph::fetch(&accounts).unwrap();
+    assert_eq!(graph.recipes.len(), 2);
+    assert_eq!(graph.mints(), BTreeSet::from([ore, ingot, gem]));
+    assert_eq!(graph.producers_of(&gem), BTreeSet::from([mine]));
+    let ore_output = graph
+        .outputs_of(&mine)
+        .find(|edge| edge.mint == ore)
+        .unwrap();
+    assert_eq!(
+        ore_output.kind,
+        IngredientKind::LootTableOutput {
+            rolls: 2,
+            weight: 3,
+            total_weight: 4,
+        }
+    );
+    assert_eq!(ore_output.expected_amount(), 6.0);
+}
@@ -0,0 +1,238 @@
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::collections::HashMap;
+use token_recipes_client::{
+    error::ClientError,
+    spec::{plan_recipe_with_recipe, RecipeSpec},
+    token_recipes::{
+        instruction::TokenRecipesInstruction,
+        state::{
+            features::FeatureLevels,
+            ingredient_input::IngredientInput,
+            ingredient_output::{IngredientOutput, LootTableEntry},
+            key::Key,
+            recipe::{IngredientType, Recipe, RecipeStatus},
+        },
+    },
+};
+
+fn recipe(inputs: Vec<IngredientInput>, outputs: Vec<IngredientOutput>) -> Recipe {
+    Recipe {
+        key: Key::Recipe,
+        base: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        pending_authority: Pubkey::default(),
+        status: RecipeStatus::Active,
+        total_crafts: 0,
+        total_crafts_with_quantity: 0,
+        fees: 0,
+        max_crafts_per_wallet: 0,
+        start_time: 0,
+        end_time: 0,
+        allowlist_root: [0; 32],
+        accumulated_admin_fees: 0,
+        accumulated_shards: 0,
+        accumulated_experience: 0,
+        feature_levels: FeatureLevels::default(),
+        version: Recipe::VERSION,
+        bump: 0,
+        inputs,
+        outputs,
+    }
+}
+
+fn mint_account() -> Account {
+    Account {
+        lamports: 1,
+        data: vec![],
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn it_exports_recipes_to_toml_and_json() {
+    let mut recipe_account = recipe(
+        vec![
+            IngredientInput::TransferToken {
+                mint: Pubkey::new_unique(),
+                amount: 2,
+                destination: Pubkey::new_unique(),
+            },
+            IngredientInput::TransferSol {
+                lamports: 1_000,
+                destination: Pubkey::new_unique(),
+            },
+        ],
+        vec![IngredientOutput::MintTokenFromLootTable {
+            rolls: 2,
+            entries: vec![LootTableEntry {
+                mint: Pubkey::new_unique(),
+                amount: 1,
+                weight: 10,
+            }],
+        }],
+    );
+    recipe_account.end_time = 1_700_000_000;
+    recipe_account.feature_levels.sol_payment = 1;
+    let spec = RecipeSpec::from(&recipe_account);
+
+    assert_eq!(spec.start_time, None);
+    assert_eq!(spec.end_time, Some(1_700_000_000));
+    assert_eq!(
+        RecipeSpec::from_toml(&spec.to_toml().unwrap()).unwrap(),
+        spec
+    );
+    assert_eq!(
+        RecipeSpec::from_json(&spec.to_json().unwrap()).unwrap(),
+        spec
+    );
+}
+
+#[test]
+fn it_plans_the_instructions_needed_to_match_a_spec() {
+    let (recipe_address, authority, payer) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (burn_mint, hold_mint, output_mint, new_mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut recipe_account = recipe(
+        vec![
+            IngredientInput::BurnToken {
+                mint: burn_mint,
+                amount: 1,
+            },
+            IngredientInput::HoldToken {
+                mint: hold_mint,
+                amount: 1,
+            },
+        ],
+        vec![IngredientOutput::MintToken {
+            mint: output_mint,
+            amount: 1,
+        }],
+    );
+    recipe_account.status = RecipeStatus::Paused;
+    recipe_account.feature_levels.fees = 1;
+
+    let spec = RecipeSpec::from_toml(&format!(
+        r#"
+        status = "active"
+        fees = 500
+
+        [feature_levels]
+        fees = 1
+
+        [[inputs]]
+        type = "burn_token"
+        mint = "{burn_mint}"
+        amount = 3
+
+        [[inputs]]
+        type = "burn_token"
+        mint = "{hold_mint}"
+        amount = 1
+
+        [[outputs]]
+        type = "mint_token"
+        mint = "{output_mint}"
+        amount = 1
+
+        [[outputs]]
+        type = "transfer_token"
+        mint = "{new_mint}"
+        amount = 5
+        "#
+    ))
+    .unwrap();
+
+    let mut fetcher = HashMap::new();
+    for mint in [burn_mint, hold_mint, output_mint, new_mint] {
+        fetcher.insert(mint, mint_account());
+    }
+
+    let plan = plan_recipe_with_recipe(
+        &fetcher,
+        &recipe_address,
+        &recipe_account,
+        &spec,
+        &authority,
+        &payer,
+    )
+    .unwrap();
+    let instructions: Vec<TokenRecipesInstruction> = plan
+        .instructions()
+        .iter()
+        .map(|instruction| TokenRecipesInstruction::try_from_slice(&instruction.data).unwrap())
+        .collect();
+
+    // The hold input cannot be turned into a burn input in place.
+    assert!(matches!(
+        instructions.as_slice(),
+        [
+            TokenRecipesInstruction::RemoveIngredient {
+                ingredient_type: IngredientType::HoldTokenInput
+            },
+            TokenRecipesInstruction::UpdateIngredient {
+                ingredient_type: IngredientType::BurnTokenInput,
+                new_ingredient_type: None,
+                amount: Some(3),
+                destination: None,
+                max_supply: None,
+            },
+            TokenRecipesInstruction::AddIngredient {
+                ingredient_type: IngredientType::BurnTokenInput,
+                amount: 1,
+                ..
+            },
+            TokenRecipesInstruction::AddIngredient {
+                ingredient_type: IngredientType::TransferTokenOutput,
+                amount: 5,
+                ..
+            },
+            TokenRecipesInstruction::SetFees { fees: 500 },
+            TokenRecipesInstruction::ActivateRecipe,
+        ]
+    ));
+    assert_eq!(plan.instructions()[0].accounts[1].pubkey, hold_mint);
+
+    // Applying the spec a second time does nothing.
+    let mut converged = recipe_account.clone();
+    converged.status = RecipeStatus::Active;
+    converged.fees = 500;
+    converged.inputs = spec.inputs.iter().map(Into::into).collect();
+    converged.outputs = spec.outputs.iter().map(Into::into).collect();
+    let plan = plan_recipe_with_recipe(
+        &fetcher,
+        &recipe_address,
+        &converged,
+        &spec,
+        &authority,
+        &payer,
+    )
+    .unwrap();
+    assert!(plan.is_empty());
+
+    // Feature levels are required, not unlocked.
+    recipe_account.feature_levels.fees = 0;
+    let result = plan_recipe_with_recipe(
+        &fetcher,
+        &recipe_address,
+        &recipe_account,
+        &spec,
+        &authority,
+        &payer,
+    );
+    assert!(matches!(
+        result,
+        Err(ClientError::FeatureLevelTooLow(_, 0, 1))
+    ));
+}
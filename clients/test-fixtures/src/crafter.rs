@@ -0,0 +1,85 @@
+use crate::{
+    recipe::RecipeFixture,
+    utils::{mint_to, process},
+};
+use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, system_instruction};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::signature::{Keypair, Signer};
+use token_recipes::state::{
+    features::fees::{get_fees_and_shards_per_craft, FeesFeature},
+    ingredient_input::IngredientInput,
+};
+
+/// Builds a new keypair funded with lamports and tokens by the context payer.
+///
+/// The context payer must be the mint authority of every mint, which is the case
+/// for the input mints created by a `RecipeBuilder`.
+#[derive(Clone, Debug)]
+pub struct CrafterBuilder {
+    lamports: u64,
+    token_balances: Vec<(Pubkey, u64)>,
+}
+
+impl Default for CrafterBuilder {
+    fn default() -> Self {
+        Self {
+            lamports: LAMPORTS_PER_SOL,
+            token_balances: vec![],
+        }
+    }
+}
+
+impl CrafterBuilder {
+    /// A crafter with 1 SOL and no tokens.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A crafter holding enough lamports and tokens to craft a recipe the given number of times.
+    /// An extra SOL is provided to pay for the token accounts created when crafting.
+    /// Crafting fees are computed using the given fees feature.
+    pub fn for_recipe(recipe: &RecipeFixture, fees_feature: &FeesFeature, quantity: u64) -> Self {
+        let (fees, _, _) = get_fees_and_shards_per_craft(&recipe.recipe, fees_feature).unwrap();
+        let mut builder = Self::new();
+        for input in &recipe.recipe.inputs {
+            builder = match input {
+                IngredientInput::BurnToken { mint, amount }
+                | IngredientInput::TransferToken { mint, amount, .. } => {
+                    builder.token_balance(mint, amount * quantity)
+                }
+                IngredientInput::HoldToken { mint, amount } => builder.token_balance(mint, *amount),
+                IngredientInput::TransferSol { lamports, .. } => {
+                    let lamports = builder.lamports + lamports * quantity;
+                    builder.lamports(lamports)
+                }
+            };
+        }
+        let lamports = builder.lamports + fees;
+        builder.lamports(lamports)
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    /// Hold the given amount of tokens in the associated token account of the crafter.
+    pub fn token_balance(mut self, mint: &Pubkey, amount: u64) -> Self {
+        self.token_balances.push((*mint, amount));
+        self
+    }
+
+    pub async fn build(
+        self,
+        context: &mut ProgramTestContext,
+    ) -> Result<Keypair, BanksClientError> {
+        let crafter = Keypair::new();
+        let ix =
+            system_instruction::transfer(&context.payer.pubkey(), &crafter.pubkey(), self.lamports);
+        process(context, &[ix], &[]).await?;
+        for (mint, amount) in self.token_balances {
+            mint_to(context, &mint, &crafter.pubkey(), amount).await?;
+        }
+        Ok(crafter)
+    }
+}
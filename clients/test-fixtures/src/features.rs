@@ -0,0 +1,213 @@
+use borsh::BorshSerialize;
+use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey, rent::Rent};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use token_recipes::{
+    pda::{find_feature_pda, find_fees_feature_pda, find_wisdom_feature_pda},
+    state::{
+        features::{
+            additional_outputs::AdditionalOutputsFeature,
+            fees::{FeesFeature, DEFAULT_BASE_FEES, DEFAULT_FEE_PERCENTAGES},
+            max_supply::MaxSupplyFeature,
+            sol_payment::SolPaymentFeature,
+            transfer_inputs::TransferInputsFeature,
+            wisdom::WisdomFeature,
+            Feature,
+        },
+        key::Key,
+    },
+};
+
+/// The feature accounts of the program.
+///
+/// By default, every mint used to level up a feature is a random address. Replace them
+/// and add the matching mint accounts to test unlocking features. The shard mint and
+/// the experience mint are added as mint accounts whose authorities are the feature PDAs.
+#[derive(Clone, Debug)]
+pub struct Features {
+    pub fees: FeesFeature,
+    pub additional_outputs: AdditionalOutputsFeature,
+    pub transfer_inputs: TransferInputsFeature,
+    pub max_supply: MaxSupplyFeature,
+    pub sol_payment: SolPaymentFeature,
+    pub wisdom: WisdomFeature,
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            fees: FeesFeature {
+                key: Key::FeesFeature,
+                admin_destination: Pubkey::new_unique(),
+                shard_mint: Pubkey::new_unique(),
+                mint_burn_1: Pubkey::new_unique(),
+                mint_burn_2: Pubkey::new_unique(),
+                mint_burn_3: Pubkey::new_unique(),
+                mint_burn_4: Pubkey::new_unique(),
+                mint_burn_5: Pubkey::new_unique(),
+                mint_skill_1: Pubkey::new_unique(),
+                mint_skill_2: Pubkey::new_unique(),
+                mint_skill_3: Pubkey::new_unique(),
+                bump: 0,
+                version: 0,
+                base_fees: DEFAULT_BASE_FEES,
+                fee_percentages: DEFAULT_FEE_PERCENTAGES,
+            },
+            additional_outputs: AdditionalOutputsFeature {
+                key: Key::AdditionalOutputsFeature,
+                mint_burn_1: Pubkey::new_unique(),
+                mint_burn_2: Pubkey::new_unique(),
+                mint_burn_3: Pubkey::new_unique(),
+                mint_skill_1: Pubkey::new_unique(),
+                mint_skill_2: Pubkey::new_unique(),
+                bump: 0,
+                version: 0,
+            },
+            transfer_inputs: TransferInputsFeature {
+                key: Key::TransferInputsFeature,
+                mint_burn_1: Pubkey::new_unique(),
+                mint_burn_2: Pubkey::new_unique(),
+                mint_burn_3: Pubkey::new_unique(),
+                mint_skill_1: Pubkey::new_unique(),
+                mint_skill_2: Pubkey::new_unique(),
+                bump: 0,
+                version: 0,
+            },
+            max_supply: MaxSupplyFeature {
+                key: Key::MaxSupplyFeature,
+                mint_burn_1: Pubkey::new_unique(),
+                mint_skill_1: Pubkey::new_unique(),
+                bump: 0,
+                version: 0,
+            },
+            sol_payment: SolPaymentFeature {
+                key: Key::SolPaymentFeature,
+                mint_burn_1: Pubkey::new_unique(),
+                mint_burn_2: Pubkey::new_unique(),
+                mint_burn_3: Pubkey::new_unique(),
+                mint_burn_4: Pubkey::new_unique(),
+                mint_burn_5: Pubkey::new_unique(),
+                mint_burn_6: Pubkey::new_unique(),
+                mint_burn_7: Pubkey::new_unique(),
+                mint_burn_8: Pubkey::new_unique(),
+                mint_burn_9: Pubkey::new_unique(),
+                mint_skill_1: Pubkey::new_unique(),
+                mint_skill_2: Pubkey::new_unique(),
+                mint_skill_3: Pubkey::new_unique(),
+                mint_skill_4: Pubkey::new_unique(),
+                mint_skill_5: Pubkey::new_unique(),
+                bump: 0,
+                version: 0,
+            },
+            wisdom: WisdomFeature {
+                key: Key::WisdomFeature,
+                experience_mint: Pubkey::new_unique(),
+                mint_burn_1: Pubkey::new_unique(),
+                mint_burn_2: Pubkey::new_unique(),
+                bump: 0,
+                version: 0,
+            },
+        }
+    }
+}
+
+impl Features {
+    /// Every feature, in the order of their account keys.
+    pub fn to_vec(&self) -> Vec<Feature> {
+        vec![
+            Feature::Fees(self.fees.clone()),
+            Feature::AdditionalOutputs(self.additional_outputs.clone()),
+            Feature::TransferInputs(self.transfer_inputs.clone()),
+            Feature::MaxSupply(self.max_supply.clone()),
+            Feature::SolPayment(self.sol_payment.clone()),
+            Feature::Wisdom(self.wisdom.clone()),
+        ]
+    }
+
+    /// Add the feature accounts, the shard mint and the experience mint to a `ProgramTest` before it starts.
+    pub fn add_to(&self, program_test: &mut ProgramTest) {
+        for (address, account) in self.accounts() {
+            program_test.add_account(address, account);
+        }
+    }
+
+    /// Overwrite the feature accounts, the shard mint and the experience mint of a running test.
+    pub fn set(&self, context: &mut ProgramTestContext) {
+        for (address, account) in self.accounts() {
+            context.set_account(&address, &AccountSharedData::from(account));
+        }
+    }
+
+    fn accounts(&self) -> Vec<(Pubkey, Account)> {
+        let mut accounts: Vec<(Pubkey, Account)> =
+            self.to_vec().into_iter().map(feature_account).collect();
+        accounts.push((
+            self.fees.shard_mint,
+            mint_account(&find_fees_feature_pda().0),
+        ));
+        accounts.push((
+            self.wisdom.experience_mint,
+            mint_account(&find_wisdom_feature_pda().0),
+        ));
+        accounts
+    }
+}
+
+/// Returns the address and the account of a feature with its canonical bump and current version,
+/// as created by the AdminSetFeature instruction.
+pub fn feature_account(mut feature: Feature) -> (Pubkey, Account) {
+    let (address, bump) = find_feature_pda(&feature);
+    let data = match &mut feature {
+        Feature::Fees(f) => {
+            (f.bump, f.version) = (bump, FeesFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::AdditionalOutputs(f) => {
+            (f.bump, f.version) = (bump, AdditionalOutputsFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::TransferInputs(f) => {
+            (f.bump, f.version) = (bump, TransferInputsFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::MaxSupply(f) => {
+            (f.bump, f.version) = (bump, MaxSupplyFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::SolPayment(f) => {
+            (f.bump, f.version) = (bump, SolPaymentFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::Wisdom(f) => {
+            (f.bump, f.version) = (bump, WisdomFeature::VERSION);
+            f.try_to_vec()
+        }
+    }
+    .unwrap();
+    let account = Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: token_recipes::id(),
+        ..Account::default()
+    };
+    (address, account)
+}
+
+/// Returns an initialized SPL Token mint account with no decimals and no supply.
+pub fn mint_account(mint_authority: &Pubkey) -> Account {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(*mint_authority),
+        supply: 0,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::id(),
+        ..Account::default()
+    }
+}
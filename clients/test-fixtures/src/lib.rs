@@ -0,0 +1,28 @@
+pub mod crafter;
+pub mod features;
+pub mod recipe;
+pub mod utils;
+
+use solana_program_test::ProgramTest;
+
+pub use crafter::CrafterBuilder;
+pub use features::Features;
+pub use recipe::{RecipeBuilder, RecipeFixture};
+pub use token_recipes;
+
+/// Add the Token Recipes program to a `ProgramTest`.
+///
+/// The program is loaded from the `token_recipes.so` file of the `BPF_OUT_DIR` directory
+/// so it can be invoked by the other programs of the test.
+pub fn add_program(program_test: &mut ProgramTest) {
+    program_test.add_program("token_recipes", token_recipes::id(), None);
+}
+
+/// A `ProgramTest` running the Token Recipes program with every feature account set up.
+pub fn program_test() -> (ProgramTest, Features) {
+    let mut program_test = ProgramTest::default();
+    add_program(&mut program_test);
+    let features = Features::default();
+    features.add_to(&mut program_test);
+    (program_test, features)
+}
@@ -0,0 +1,254 @@
+use crate::utils::{create_mint, mint_to, process};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    account::AccountSharedData,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    instruction::{
+        activate_recipe, add_ingredient, craft, create_recipe, deposit_vault_tokens, set_fees,
+        set_max_crafts_per_wallet,
+    },
+    pda::find_recipe_pda,
+    state::{
+        features::FeatureLevels,
+        recipe::{IngredientType, Recipe},
+    },
+};
+
+/// Builds a recipe whose authority is the context payer.
+///
+/// A new mint is created for every token ingredient with the context payer as mint authority,
+/// and every transfer input sends its tokens or lamports to the same random destination.
+#[derive(Clone, Debug, Default)]
+pub struct RecipeBuilder {
+    feature_levels: FeatureLevels,
+    fees: Option<u64>,
+    max_crafts_per_wallet: u64,
+    ingredients: Vec<IngredientFixture>,
+    active: bool,
+}
+
+#[derive(Clone, Debug)]
+struct IngredientFixture {
+    ingredient_type: IngredientType,
+    amount: u64,
+    max_supply: Option<u64>,
+    vault_amount: u64,
+}
+
+impl RecipeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the feature levels of the recipe without unlocking them.
+    pub fn feature_levels(mut self, feature_levels: FeatureLevels) -> Self {
+        self.feature_levels = feature_levels;
+        self
+    }
+
+    /// Set custom fees. Requires the "Fees" feature at level 10 or above.
+    pub fn fees(mut self, fees: u64) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    pub fn max_crafts_per_wallet(mut self, max_crafts_per_wallet: u64) -> Self {
+        self.max_crafts_per_wallet = max_crafts_per_wallet;
+        self
+    }
+
+    /// Activate the recipe once all ingredients are added.
+    pub fn active(mut self) -> Self {
+        self.active = true;
+        self
+    }
+
+    pub fn burn_token_input(self, amount: u64) -> Self {
+        self.ingredient(IngredientType::BurnTokenInput, amount, None, 0)
+    }
+
+    pub fn transfer_token_input(self, amount: u64) -> Self {
+        self.ingredient(IngredientType::TransferTokenInput, amount, None, 0)
+    }
+
+    pub fn transfer_sol_input(self, lamports: u64) -> Self {
+        self.ingredient(IngredientType::TransferSolInput, lamports, None, 0)
+    }
+
+    pub fn hold_token_input(self, amount: u64) -> Self {
+        self.ingredient(IngredientType::HoldTokenInput, amount, None, 0)
+    }
+
+    pub fn mint_token_output(self, amount: u64) -> Self {
+        self.ingredient(IngredientType::MintTokenOutput, amount, None, 0)
+    }
+
+    pub fn mint_token_with_max_supply_output(self, amount: u64, max_supply: u64) -> Self {
+        self.ingredient(
+            IngredientType::MintTokenWithMaxSupplyOutput,
+            amount,
+            Some(max_supply),
+            0,
+        )
+    }
+
+    /// Add a transfer output whose vault is funded with the given amount of tokens.
+    pub fn transfer_token_output(self, amount: u64, vault_amount: u64) -> Self {
+        self.ingredient(
+            IngredientType::TransferTokenOutput,
+            amount,
+            None,
+            vault_amount,
+        )
+    }
+
+    fn ingredient(
+        mut self,
+        ingredient_type: IngredientType,
+        amount: u64,
+        max_supply: Option<u64>,
+        vault_amount: u64,
+    ) -> Self {
+        self.ingredients.push(IngredientFixture {
+            ingredient_type,
+            amount,
+            max_supply,
+            vault_amount,
+        });
+        self
+    }
+
+    pub async fn build(
+        self,
+        context: &mut ProgramTestContext,
+    ) -> Result<RecipeFixture, BanksClientError> {
+        let payer = context.payer.pubkey();
+        let base = Keypair::new();
+        let address = find_recipe_pda(&base.pubkey()).0;
+        let ix = create_recipe(&base.pubkey(), &payer, &payer);
+        process(context, &[ix], &[&base]).await?;
+
+        // Feature levels are written directly since the recipe has no ingredients yet.
+        if self.feature_levels != FeatureLevels::default() {
+            let mut account = context
+                .banks_client
+                .get_account(address)
+                .await?
+                .ok_or(BanksClientError::ClientError("recipe not found"))?;
+            let mut recipe = Recipe::deserialize(&mut account.data.as_ref())?;
+            recipe.feature_levels = self.feature_levels;
+            account.data = recipe.try_to_vec()?;
+            context.set_account(&address, &AccountSharedData::from(account));
+        }
+
+        if let Some(fees) = self.fees {
+            process(context, &[set_fees(&address, &payer, fees)], &[]).await?;
+        }
+        if self.max_crafts_per_wallet > 0 {
+            let ix = set_max_crafts_per_wallet(&address, &payer, self.max_crafts_per_wallet);
+            process(context, &[ix], &[]).await?;
+        }
+
+        let destination = Pubkey::new_unique();
+        for ingredient in self.ingredients {
+            let (mint, destination) = match ingredient.ingredient_type {
+                IngredientType::TransferSolInput => (None, Some(destination)),
+                IngredientType::TransferTokenInput => {
+                    (Some(create_mint(context, &payer).await?), Some(destination))
+                }
+                _ => (Some(create_mint(context, &payer).await?), None),
+            };
+            let ix = add_ingredient(
+                &address,
+                mint.as_ref(),
+                &payer,
+                &payer,
+                &spl_token::id(),
+                ingredient.ingredient_type,
+                ingredient.amount,
+                destination,
+                ingredient.max_supply,
+            );
+            process(context, &[ix], &[]).await?;
+
+            if let (Some(mint), true) = (mint, ingredient.vault_amount > 0) {
+                mint_to(context, &mint, &payer, ingredient.vault_amount).await?;
+                let ix = deposit_vault_tokens(
+                    &address,
+                    &payer,
+                    &payer,
+                    &mint,
+                    &spl_token::id(),
+                    ingredient.vault_amount,
+                );
+                process(context, &[ix], &[]).await?;
+            }
+        }
+
+        if self.active {
+            process(context, &[activate_recipe(&address, &payer)], &[]).await?;
+        }
+
+        Ok(RecipeFixture {
+            address,
+            destination,
+            recipe: get_recipe(context, &address).await?,
+        })
+    }
+}
+
+/// A recipe created by a `RecipeBuilder`.
+#[derive(Clone, Debug)]
+pub struct RecipeFixture {
+    pub address: Pubkey,
+    /// The destination of every transfer input.
+    pub destination: Pubkey,
+    /// The content of the recipe account when it was last loaded.
+    pub recipe: Recipe,
+}
+
+impl RecipeFixture {
+    /// Load the latest content of the recipe account.
+    pub async fn reload(
+        &mut self,
+        context: &mut ProgramTestContext,
+    ) -> Result<(), BanksClientError> {
+        self.recipe = get_recipe(context, &self.address).await?;
+        Ok(())
+    }
+
+    /// Craft the recipe using the associated token accounts of the crafter, who also pays for it.
+    pub async fn craft(
+        &self,
+        context: &mut ProgramTestContext,
+        crafter: &Keypair,
+        quantity: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = craft(
+            &self.address,
+            &self.recipe,
+            &crafter.pubkey(),
+            &crafter.pubkey(),
+            quantity,
+            vec![],
+        );
+        process(context, &[ix], &[crafter]).await
+    }
+}
+
+/// Fetch and deserialize a recipe account.
+pub async fn get_recipe(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+) -> Result<Recipe, BanksClientError> {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await?
+        .ok_or(BanksClientError::ClientError("recipe not found"))?;
+    Ok(Recipe::deserialize(&mut account.data.as_ref())?)
+}
@@ -0,0 +1,102 @@
+use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use token_recipes::pda::find_associated_token_address;
+
+/// Send a transaction paid by the context payer and signed by the given signers.
+pub async fn process(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Create a new SPL Token mint with no decimals.
+pub async fn create_mint(
+    context: &mut ProgramTestContext,
+    mint_authority: &Pubkey,
+) -> Result<Pubkey, BanksClientError> {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await?;
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            mint_authority,
+            None,
+            0,
+        )
+        .unwrap(),
+    ];
+    process(context, &ixs, &[&mint]).await?;
+    Ok(mint.pubkey())
+}
+
+/// Create the associated token account of the owner, if needed, and mint
+/// tokens to it. The context payer must be the mint authority of the mint.
+pub async fn mint_to(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Result<Pubkey, BanksClientError> {
+    let payer = context.payer.pubkey();
+    let token = find_associated_token_address(owner, mint, &spl_token::id());
+    let mut ixs = vec![];
+    if context.banks_client.get_account(token).await?.is_none() {
+        ixs.push(
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer,
+                owner,
+                mint,
+                &spl_token::id(),
+            ),
+        );
+    }
+    if amount > 0 {
+        ixs.push(
+            spl_token::instruction::mint_to(&spl_token::id(), mint, &token, &payer, &[], amount)
+                .unwrap(),
+        );
+    }
+    if !ixs.is_empty() {
+        process(context, &ixs, &[]).await?;
+    }
+    Ok(token)
+}
+
+/// Returns the token balance of a token account, or 0 if it does not exist.
+pub async fn get_token_balance(
+    context: &mut ProgramTestContext,
+    token: &Pubkey,
+) -> Result<u64, BanksClientError> {
+    Ok(match context.banks_client.get_account(*token).await? {
+        Some(account) => {
+            spl_token::state::Account::unpack(&account.data)
+                .map_err(|_| BanksClientError::ClientError("not a token account"))?
+                .amount
+        }
+        None => 0,
+    })
+}
@@ -0,0 +1,111 @@
+use solana_program::{program_option::COption, program_pack::Pack};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+use token_recipes::{
+    pda::{find_associated_token_address, find_fees_feature_pda},
+    state::{
+        features::{fees::DEFAULT_BASE_FEES, FeatureLevels},
+        ingredient_input::IngredientInput,
+        ingredient_output::IngredientOutput,
+        recipe::RecipeStatus,
+    },
+};
+use token_recipes_test_fixtures::{
+    program_test, utils::get_token_balance, CrafterBuilder, RecipeBuilder,
+};
+
+#[tokio::test]
+async fn craft_a_recipe_built_from_fixtures() {
+    // Given a recipe using every type of ingredient.
+    let (program_test, features) = program_test();
+    let mut context = program_test.start_with_context().await;
+    let recipe = RecipeBuilder::new()
+        .feature_levels(FeatureLevels {
+            fees: 5,
+            additional_outputs: 3,
+            transfer_inputs: 1,
+            max_supply: 1,
+            sol_payment: 1,
+            ..FeatureLevels::default()
+        })
+        .burn_token_input(2)
+        .transfer_token_input(1)
+        .transfer_sol_input(1_000_000)
+        .hold_token_input(1)
+        .mint_token_output(1)
+        .mint_token_with_max_supply_output(1, 10)
+        .transfer_token_output(1, 5)
+        .active()
+        .build(&mut context)
+        .await
+        .unwrap();
+    assert_eq!(recipe.recipe.status, RecipeStatus::Active);
+    assert_eq!(recipe.recipe.feature_levels.fees, 5);
+    assert_eq!(recipe.recipe.inputs.len(), 4);
+    assert_eq!(recipe.recipe.outputs.len(), 3);
+
+    // And a crafter holding enough tokens to craft it twice.
+    let crafter = CrafterBuilder::for_recipe(&recipe, &features.fees, 2)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    // When the crafter crafts the recipe twice.
+    recipe.craft(&mut context, &crafter, 2).await.unwrap();
+
+    // Then every input was consumed.
+    for input in &recipe.recipe.inputs {
+        match input {
+            IngredientInput::BurnToken { mint, .. }
+            | IngredientInput::TransferToken { mint, .. } => {
+                let token =
+                    find_associated_token_address(&crafter.pubkey(), mint, &spl_token::id());
+                assert_eq!(get_token_balance(&mut context, &token).await.unwrap(), 0);
+            }
+            IngredientInput::HoldToken { mint, .. } => {
+                let token =
+                    find_associated_token_address(&crafter.pubkey(), mint, &spl_token::id());
+                assert_eq!(get_token_balance(&mut context, &token).await.unwrap(), 1);
+            }
+            IngredientInput::TransferSol { .. } => {
+                let destination = context
+                    .banks_client
+                    .get_balance(recipe.destination)
+                    .await
+                    .unwrap();
+                assert_eq!(destination, 2_000_000);
+            }
+        }
+    }
+
+    // And every output was received.
+    for output in &recipe.recipe.outputs {
+        let mint = match output {
+            IngredientOutput::MintToken { mint, .. }
+            | IngredientOutput::MintTokenWithMaxSupply { mint, .. }
+            | IngredientOutput::TransferToken { mint, .. } => mint,
+            IngredientOutput::MintTokenFromLootTable { .. } => unreachable!(),
+        };
+        let token = find_associated_token_address(&crafter.pubkey(), mint, &spl_token::id());
+        assert_eq!(get_token_balance(&mut context, &token).await.unwrap(), 2);
+    }
+
+    // And the fees were accumulated using the fees feature level.
+    let mut recipe = recipe;
+    recipe.reload(&mut context).await.unwrap();
+    assert_eq!(recipe.recipe.accumulated_admin_fees, DEFAULT_BASE_FEES / 2);
+    assert_eq!(recipe.recipe.accumulated_shards, DEFAULT_BASE_FEES / 2);
+
+    // And the shard mint of the features is owned by the fees feature PDA.
+    let shard_mint = context
+        .banks_client
+        .get_account(features.fees.shard_mint)
+        .await
+        .unwrap()
+        .unwrap();
+    let shard_mint = spl_token::state::Mint::unpack(&shard_mint.data).unwrap();
+    assert_eq!(
+        shard_mint.mint_authority,
+        COption::Some(find_fees_feature_pda().0)
+    );
+}
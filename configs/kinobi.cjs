@@ -30,6 +30,13 @@ kinobi.update(
         k.publicKeySeed("mint", "The mint address of the ingredient"),
       ],
     },
+    crafterRecord: {
+      seeds: [
+        k.stringConstantSeed("crafter_record"),
+        k.publicKeySeed("recipe", "The address of the recipe"),
+        k.publicKeySeed("owner", "The address of the crafter"),
+      ],
+    },
     feesFeature: {
       seeds: [k.stringConstantSeed("features"), k.stringConstantSeed("fees")],
     },
@@ -119,6 +126,7 @@ kinobi.update(
     recipe: key("Recipe"),
     ingredient: key("Ingredient"),
     delegatedIngredient: key("DelegatedIngredient"),
+    crafterRecord: key("CrafterRecord"),
   })
 );
 
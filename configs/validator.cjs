@@ -14,6 +14,13 @@ module.exports = {
         label: "Token Recipes",
         programId: "C7zZZJpLzAehgidrbwdpBwN6RZCJo98qb55Zjep1a28T",
         deployPath: getProgram("localnet", "token_recipes.so"),
+        // Deployed using `--upgradeable-program` so the program has a ProgramData
+        // account whose upgrade authority can initialize the program config.
+        upgradeAuthority: path.join(
+          __dirname,
+          "program-scripts",
+          "upgrade-authority.json"
+        ),
       },
       // Below are external programs that should be included in the local validator.
       // You may configure which ones to fetch from the cluster when building
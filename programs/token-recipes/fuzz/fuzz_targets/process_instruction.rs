@@ -0,0 +1,264 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use borsh::BorshSerialize;
+use libfuzzer_sys::fuzz_target;
+use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey, system_program};
+use std::collections::HashSet;
+use token_recipes::{
+    pda::{
+        find_additional_outputs_feature_pda, find_crafter_record_pda,
+        find_delegated_ingredient_pda, find_fees_feature_pda, find_ingredient_record_pda,
+        find_max_supply_feature_pda, find_program_config_pda, find_recipe_pda,
+        find_sol_payment_feature_pda, find_transfer_inputs_feature_pda, find_wisdom_feature_pda,
+    },
+    state::{
+        features::{fees::FeesFeature, FeatureLevels},
+        key::Key,
+        recipe::{Recipe, RecipeStatus},
+    },
+};
+
+#[path = "../../tests/utils/runtime.rs"]
+mod runtime;
+
+use runtime::{process_instruction, TestAccount};
+
+/// The maximum number of accounts passed to a single instruction.
+const MAX_ACCOUNTS: usize = 32;
+
+#[derive(Arbitrary, Debug)]
+struct FuzzInput {
+    accounts: Vec<FuzzAccount>,
+    instruction_data: Vec<u8>,
+}
+
+#[derive(Arbitrary, Debug)]
+struct FuzzAccount {
+    key: FuzzKey,
+    owner: FuzzProgram,
+    lamports: u64,
+    data: FuzzData,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+/// Addresses are picked from a small set so that accounts can match each other
+/// as well as the PDAs of the program and the programs it invokes.
+#[derive(Arbitrary, Debug, Clone, Copy)]
+enum FuzzKey {
+    Wallet(u8),
+    Recipe(u8),
+    IngredientRecord { mint: u8, recipe: u8 },
+    DelegatedIngredient(u8),
+    CrafterRecord { recipe: u8, owner: u8 },
+    Feature(u8),
+    ProgramConfig,
+    Program(FuzzProgram),
+}
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+enum FuzzProgram {
+    System,
+    Token,
+    Token2022,
+    AssociatedToken,
+    TokenRecipes,
+}
+
+#[derive(Arbitrary, Debug)]
+enum FuzzData {
+    Raw(Vec<u8>),
+    Mint {
+        mint_authority: FuzzKey,
+        supply: u64,
+    },
+    Token {
+        mint: FuzzKey,
+        owner: FuzzKey,
+        amount: u64,
+    },
+    Recipe {
+        base: u8,
+        authority: FuzzKey,
+        active: bool,
+    },
+    FeesFeature {
+        admin_destination: FuzzKey,
+        base_fees: u64,
+        fee_percentages: [u8; 12],
+    },
+}
+
+fn wallet(seed: u8) -> Pubkey {
+    Pubkey::new_from_array([seed; 32])
+}
+
+impl FuzzKey {
+    fn pubkey(self) -> Pubkey {
+        match self {
+            Self::Wallet(seed) => wallet(seed),
+            Self::Recipe(base) => find_recipe_pda(&wallet(base)).0,
+            Self::IngredientRecord { mint, recipe } => {
+                find_ingredient_record_pda(&wallet(mint), &find_recipe_pda(&wallet(recipe)).0).0
+            }
+            Self::DelegatedIngredient(mint) => find_delegated_ingredient_pda(&wallet(mint)).0,
+            Self::CrafterRecord { recipe, owner } => {
+                find_crafter_record_pda(&find_recipe_pda(&wallet(recipe)).0, &wallet(owner)).0
+            }
+            Self::Feature(index) => match index % 6 {
+                0 => find_fees_feature_pda().0,
+                1 => find_additional_outputs_feature_pda().0,
+                2 => find_transfer_inputs_feature_pda().0,
+                3 => find_max_supply_feature_pda().0,
+                4 => find_sol_payment_feature_pda().0,
+                _ => find_wisdom_feature_pda().0,
+            },
+            Self::ProgramConfig => find_program_config_pda().0,
+            Self::Program(program) => program.pubkey(),
+        }
+    }
+}
+
+impl FuzzProgram {
+    fn pubkey(self) -> Pubkey {
+        match self {
+            Self::System => system_program::id(),
+            Self::Token => spl_token::id(),
+            Self::Token2022 => spl_token_2022::id(),
+            Self::AssociatedToken => spl_associated_token_account::id(),
+            Self::TokenRecipes => token_recipes::id(),
+        }
+    }
+}
+
+impl FuzzData {
+    fn to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Raw(data) => data.clone(),
+            Self::Mint {
+                mint_authority,
+                supply,
+            } => {
+                let mut data = vec![0; spl_token::state::Mint::LEN];
+                spl_token::state::Mint {
+                    mint_authority: COption::Some(mint_authority.pubkey()),
+                    supply: *supply,
+                    decimals: 0,
+                    is_initialized: true,
+                    freeze_authority: COption::None,
+                }
+                .pack_into_slice(&mut data);
+                data
+            }
+            Self::Token {
+                mint,
+                owner,
+                amount,
+            } => {
+                let mut data = vec![0; spl_token::state::Account::LEN];
+                spl_token::state::Account {
+                    mint: mint.pubkey(),
+                    owner: owner.pubkey(),
+                    amount: *amount,
+                    state: spl_token::state::AccountState::Initialized,
+                    ..spl_token::state::Account::default()
+                }
+                .pack_into_slice(&mut data);
+                data
+            }
+            Self::Recipe {
+                base,
+                authority,
+                active,
+            } => {
+                let (_, bump) = find_recipe_pda(&wallet(*base));
+                Recipe {
+                    key: Key::Recipe,
+                    base: wallet(*base),
+                    authority: authority.pubkey(),
+                    status: match active {
+                        true => RecipeStatus::Active,
+                        false => RecipeStatus::Paused,
+                    },
+                    total_crafts: 0,
+                    total_crafts_with_quantity: 0,
+                    fees: 0,
+                    accumulated_admin_fees: 0,
+                    accumulated_shards: 0,
+                    accumulated_experience: 0,
+                    feature_levels: FeatureLevels::default(),
+                    version: Recipe::VERSION,
+                    bump,
+                    pending_authority: Pubkey::default(),
+                    max_crafts_per_wallet: 0,
+                    start_time: 0,
+                    end_time: 0,
+                    allowlist_root: [0; 32],
+                    inputs: vec![],
+                    outputs: vec![],
+                }
+                .try_to_vec()
+                .unwrap()
+            }
+            Self::FeesFeature {
+                admin_destination,
+                base_fees,
+                fee_percentages,
+            } => FeesFeature {
+                key: Key::FeesFeature,
+                admin_destination: admin_destination.pubkey(),
+                shard_mint: wallet(0),
+                mint_burn_1: wallet(1),
+                mint_burn_2: wallet(2),
+                mint_burn_3: wallet(3),
+                mint_burn_4: wallet(4),
+                mint_burn_5: wallet(5),
+                mint_skill_1: wallet(6),
+                mint_skill_2: wallet(7),
+                mint_skill_3: wallet(8),
+                bump: find_fees_feature_pda().1,
+                version: FeesFeature::VERSION,
+                base_fees: *base_fees,
+                fee_percentages: *fee_percentages,
+            }
+            .try_to_vec()
+            .unwrap(),
+        }
+    }
+}
+
+/// The total amount of lamports held by the given accounts.
+/// Duplicated accounts share the state of their first occurrence.
+fn total_lamports(accounts: &[TestAccount]) -> u128 {
+    let mut keys = HashSet::new();
+    accounts
+        .iter()
+        .filter(|account| keys.insert(account.key))
+        .map(|account| account.lamports as u128)
+        .sum()
+}
+
+fuzz_target!(|input: FuzzInput| {
+    let mut accounts: Vec<TestAccount> = input
+        .accounts
+        .iter()
+        .take(MAX_ACCOUNTS)
+        .map(|account| TestAccount {
+            key: account.key.pubkey(),
+            owner: account.owner.pubkey(),
+            lamports: account.lamports,
+            data: account.data.to_vec(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+            executable: matches!(account.key, FuzzKey::Program(_)),
+        })
+        .collect();
+    let lamports_before = total_lamports(&accounts);
+
+    // The processor may reject any input but it must never panic.
+    if process_instruction(&mut accounts, &input.instruction_data).is_ok() {
+        // Lamports are only moved between accounts.
+        assert_eq!(total_lamports(&accounts), lamports_before);
+    }
+});
@@ -1,9 +1,21 @@
-use crate::{error::TokenRecipesError, state::key::Key, utils::create_associated_token_account};
+use crate::{
+    error::TokenRecipesError, pda::find_program_data_address, state::key::Key,
+    utils::create_associated_token_account,
+};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey,
+    account_info::AccountInfo,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+};
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    state::{Account, Mint},
 };
-use spl_token::state::{Account, Mint};
 
 /// Assert that the given account is owned by the given program.
 pub fn assert_program_owner(
@@ -45,6 +57,32 @@ pub fn assert_pda(
     Ok(bump)
 }
 
+/// Assert the derivation of the seeds and the stored bump against the given account.
+/// This avoids searching for the canonical bump like `assert_pda` does.
+pub fn assert_pda_with_bump(
+    account_name: &str,
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    let bump = [bump];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump);
+    match Pubkey::create_program_address(&seeds_with_bump, program_id) {
+        Ok(key) if *account.key == key => Ok(()),
+        _ => {
+            msg!(
+                "Account \"{}\" [{}] is an invalid PDA for bump [{}]",
+                account_name,
+                account.key,
+                bump[0],
+            );
+            Err(TokenRecipesError::InvalidPda.into())
+        }
+    }
+}
+
 /// Assert that the given account is empty.
 pub fn assert_empty(account_name: &str, account: &AccountInfo) -> ProgramResult {
     if !account.data_is_empty() {
@@ -125,13 +163,21 @@ pub fn assert_data_size(account_name: &str, account: &AccountInfo, size: usize)
 /// Assert that the given account has the expected account key.
 pub fn assert_account_key(account_name: &str, account: &AccountInfo, key: Key) -> ProgramResult {
     if account.data_len() <= 1 || account.try_borrow_data()?[0] != key as u8 {
-        msg!(
-            "Account \"{}\" [{}] expected account key [{}], got [{}]",
-            account_name,
-            account.key,
-            key as u8,
-            account.try_borrow_data()?[0]
-        );
+        match account.try_borrow_data()?.first() {
+            Some(account_key) => msg!(
+                "Account \"{}\" [{}] expected account key [{}], got [{}]",
+                account_name,
+                account.key,
+                key as u8,
+                account_key
+            ),
+            None => msg!(
+                "Account \"{}\" [{}] expected account key [{}], got an empty account",
+                account_name,
+                account.key,
+                key as u8
+            ),
+        }
         Err(TokenRecipesError::InvalidAccountKey.into())
     } else {
         Ok(())
@@ -190,24 +236,66 @@ pub fn assert_enough_tokens(
     }
 }
 
-/// Assert that a given account is a mint account.
+/// Assert that the given account is either the SPL Token program or the SPL Token-2022 program.
+pub fn assert_token_program(account_name: &str, account: &AccountInfo) -> ProgramResult {
+    if *account.key != spl_token::id() && *account.key != spl_token_2022::id() {
+        msg!(
+            "Account \"{}\" [{}] must be the SPL Token program [{}] or the SPL Token-2022 program [{}]",
+            account_name,
+            account.key,
+            spl_token::id(),
+            spl_token_2022::id(),
+        );
+        Err(TokenRecipesError::InvalidTokenProgram.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Assert that the given account is owned by either the SPL Token program
+/// or the SPL Token-2022 program.
+pub fn assert_token_program_owner(account_name: &str, account: &AccountInfo) -> ProgramResult {
+    if *account.owner != spl_token::id() && *account.owner != spl_token_2022::id() {
+        msg!(
+            "Account \"{}\" [{}] expected program owner [{}] or [{}], got [{}]",
+            account_name,
+            account.key,
+            spl_token::id(),
+            spl_token_2022::id(),
+            account.owner
+        );
+        Err(TokenRecipesError::InvalidProgramOwner.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Assert that a given account is a mint account owned by
+/// either the SPL Token program or the SPL Token-2022 program.
 pub fn assert_mint_account(
     account_name: &str,
     account: &AccountInfo,
 ) -> Result<Mint, ProgramError> {
-    assert_program_owner(account_name, account, &spl_token::id())?;
-    assert_data_size(account_name, account, 82)?;
-    Mint::unpack(&account.data.borrow())
+    assert_token_program_owner(account_name, account)?;
+    if *account.owner == spl_token::id() {
+        assert_data_size(account_name, account, Mint::LEN)?;
+    }
+    let data = account.data.borrow();
+    Ok(StateWithExtensions::<Mint>::unpack(&data)?.base)
 }
 
-/// Assert that a given account is a token account.
+/// Assert that a given account is a token account owned by
+/// either the SPL Token program or the SPL Token-2022 program.
 pub fn assert_token_account(
     account_name: &str,
     account: &AccountInfo,
 ) -> Result<Account, ProgramError> {
-    assert_program_owner(account_name, account, &spl_token::id())?;
-    assert_data_size(account_name, account, 165)?;
-    Account::unpack(&account.data.borrow())
+    assert_token_program_owner(account_name, account)?;
+    if *account.owner == spl_token::id() {
+        assert_data_size(account_name, account, Account::LEN)?;
+    }
+    let data = account.data.borrow();
+    Ok(StateWithExtensions::<Account>::unpack(&data)?.base)
 }
 
 /// Assert that a given account is a token account
@@ -227,16 +315,56 @@ pub fn assert_token_account_or_create_ata<'a>(
             account_name,
             account,
             &spl_associated_token_account::id(),
-            &[
-                owner.key.as_ref(),
-                spl_token::id().as_ref(),
-                mint.key.as_ref(),
-            ],
+            &[owner.key.as_ref(), mint.owner.as_ref(), mint.key.as_ref()],
         )?;
         create_associated_token_account(account, mint, owner, payer)
     } else {
         let parsed_account = assert_token_account(account_name, account)?;
+        assert_program_owner(account_name, account, mint.owner)?;
         assert_same_pubkeys(mint_name, mint, &parsed_account.mint)?;
         assert_same_pubkeys(owner_name, owner, &parsed_account.owner)
     }
 }
+
+/// Assert that the given account is the upgrade authority of the program
+/// stored in its ProgramData account, and that it signed the transaction.
+pub fn assert_upgrade_authority(
+    program_data: &AccountInfo,
+    upgrade_authority: &AccountInfo,
+) -> ProgramResult {
+    assert_signer("upgrade_authority", upgrade_authority)?;
+
+    if *program_data.key != find_program_data_address()
+        || *program_data.owner != bpf_loader_upgradeable::id()
+    {
+        msg!(
+            "Account \"program_data\" [{}] must be the ProgramData account of the program",
+            program_data.key
+        );
+        return Err(TokenRecipesError::InvalidProgramData.into());
+    }
+    let upgrade_authority_address = match limited_deserialize(
+        &program_data.try_borrow_data()?,
+        UpgradeableLoaderState::size_of_programdata_metadata() as u64,
+    ) {
+        Ok(UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address,
+            ..
+        }) => upgrade_authority_address,
+        _ => {
+            msg!(
+                "Account \"program_data\" [{}] is not a ProgramData account",
+                program_data.key
+            );
+            return Err(TokenRecipesError::InvalidProgramData.into());
+        }
+    };
+    if upgrade_authority_address != Some(*upgrade_authority.key) {
+        msg!(
+            "Account \"upgrade_authority\" [{}] must be the upgrade authority of the program",
+            upgrade_authority.key
+        );
+        return Err(TokenRecipesError::InvalidUpgradeAuthority.into());
+    }
+    Ok(())
+}
@@ -92,6 +92,66 @@ pub enum TokenRecipesError {
     /// 27 - Invalid mint to level up feature
     #[error("Invalid mint to level up feature")]
     InvalidMintToLevelUpFeature,
+    /// 28 - Invalid token program
+    #[error("Invalid token program")]
+    InvalidTokenProgram,
+    /// 29 - Not enough tokens in vault
+    #[error("Not enough tokens in vault")]
+    NotEnoughTokensInVault,
+    /// 30 - Invalid loot table
+    #[error("Invalid loot table")]
+    InvalidLootTable,
+    /// 31 - No pending authority
+    #[error("No pending authority")]
+    NoPendingAuthority,
+    /// 32 - Delegated ingredient is shared
+    #[error("Delegated ingredient is shared")]
+    DelegatedIngredientIsShared,
+    /// 33 - Invalid ingredient update
+    #[error("Invalid ingredient update")]
+    InvalidIngredientUpdate,
+    /// 34 - Craft limit reached
+    #[error("Craft limit reached")]
+    CraftLimitReached,
+    /// 35 - Recipe has not started
+    #[error("Recipe has not started")]
+    RecipeHasNotStarted,
+    /// 36 - Recipe has ended
+    #[error("Recipe has ended")]
+    RecipeHasEnded,
+    /// 37 - Invalid time window
+    #[error("Invalid time window")]
+    InvalidTimeWindow,
+    /// 38 - Not in allowlist
+    #[error("Not in allowlist")]
+    NotInAllowlist,
+    /// 39 - Recipe must be paused
+    #[error("Recipe must be paused")]
+    RecipeMustBePaused,
+    /// 40 - Not enough lamports
+    #[error("Not enough lamports")]
+    NotEnoughLamports,
+    /// 41 - Invalid program data
+    #[error("Invalid program data")]
+    InvalidProgramData,
+    /// 42 - Invalid upgrade authority
+    #[error("Invalid upgrade authority")]
+    InvalidUpgradeAuthority,
+    /// 43 - Invalid fee percentage
+    #[error("Invalid fee percentage")]
+    InvalidFeePercentage,
+    /// 44 - Recipe must be migrated
+    #[error("Recipe must be migrated")]
+    RecipeMustBeMigrated,
+    /// 45 - Loot roll is pending
+    #[error("Loot roll is pending")]
+    LootRollPending,
+    /// 46 - Loot roll cannot be revealed yet
+    #[error("Loot roll cannot be revealed yet")]
+    LootRollNotRevealable,
+    /// 47 - Craft quote is too large
+    #[error("Craft quote is too large")]
+    CraftQuoteTooLarge,
 }
 
 impl PrintProgramError for TokenRecipesError {
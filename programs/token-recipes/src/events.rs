@@ -0,0 +1,94 @@
+use crate::{
+    error::TokenRecipesError,
+    state::{craft_quote::TokenAmount, features::FeatureLevels, key::Key, recipe::IngredientType},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, msg, pubkey::Pubkey};
+
+/// The version of the events emitted by the program.
+/// Every event is logged as a single data field starting with this version
+/// followed by the Borsh-serialized event. The version is bumped whenever
+/// the layout of an existing event changes.
+pub const EVENT_VERSION: u8 = 1;
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum TokenRecipesEvent {
+    /// A recipe was crafted.
+    Craft {
+        recipe: Pubkey,
+        owner: Pubkey,
+        quantity: u64,
+        /// The tokens burned or transferred by the input ingredients.
+        token_inputs: Vec<TokenAmount>,
+        /// The lamports transferred by the TransferSol input ingredient.
+        lamport_inputs: u64,
+        /// The tokens minted or transferred to the owner. Loot tables
+        /// are rolled later on and emit a RevealLoot event instead.
+        token_outputs: Vec<TokenAmount>,
+        fees: u64,
+        admin_fees: u64,
+        shards: u64,
+        experience: u64,
+    },
+    /// An ingredient was added to a recipe.
+    AddIngredient {
+        recipe: Pubkey,
+        mint: Pubkey,
+        ingredient_type: IngredientType,
+        amount: u64,
+    },
+    /// An ingredient was removed from a recipe.
+    RemoveIngredient {
+        recipe: Pubkey,
+        mint: Pubkey,
+        ingredient_type: IngredientType,
+    },
+    /// A recipe was activated.
+    ActivateRecipe { recipe: Pubkey },
+    /// A recipe was paused.
+    PauseRecipe { recipe: Pubkey },
+    /// A feature of a recipe was leveled up by using the given mint.
+    UnlockFeature {
+        recipe: Pubkey,
+        feature: Key,
+        mint: Pubkey,
+        feature_levels: FeatureLevels,
+    },
+    /// The fees and shards of a recipe were collected.
+    CollectFees {
+        recipe: Pubkey,
+        authority_fees: u64,
+        admin_fees: u64,
+        shards: u64,
+    },
+    /// The experience of a recipe was collected.
+    CollectExperience { recipe: Pubkey, experience: u64 },
+    /// A recipe was deleted after collecting its fees, shards and experience.
+    DeleteRecipe {
+        recipe: Pubkey,
+        authority_fees: u64,
+        admin_fees: u64,
+        shards: u64,
+        experience: u64,
+    },
+    /// The loot tables committed by a craft were rolled and their picked entries minted.
+    /// The owner receives no tokens when the loot roll expired before it was revealed.
+    RevealLoot {
+        recipe: Pubkey,
+        owner: Pubkey,
+        token_outputs: Vec<TokenAmount>,
+    },
+}
+
+impl TokenRecipesEvent {
+    /// Log the event using `sol_log_data`.
+    pub fn emit(&self) -> ProgramResult {
+        let mut data = vec![EVENT_VERSION];
+        self.serialize(&mut data).map_err(|error| {
+            msg!("Error serializing event: {}", error);
+            TokenRecipesError::SerializationError
+        })?;
+        sol_log_data(&[&data]);
+        Ok(())
+    }
+}
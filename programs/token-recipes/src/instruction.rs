@@ -1,9 +1,25 @@
-use crate::state::{features::Feature, recipe::IngredientType};
+use crate::{
+    pda::{
+        find_associated_token_address, find_crafter_record_pda, find_delegated_ingredient_pda,
+        find_feature_pda, find_fees_feature_pda, find_ingredient_record_pda, find_loot_roll_pda,
+        find_program_config_pda, find_program_data_address, find_recipe_pda,
+        find_wisdom_feature_pda,
+    },
+    state::{
+        features::Feature,
+        ingredient_input::IngredientInput,
+        ingredient_output::{IngredientOutput, LootTableEntry},
+        loot_roll::LootRoll,
+        recipe::{IngredientType, Recipe},
+    },
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankInstruction;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
+    system_program,
+    sysvar::slot_hashes,
 };
 
 #[derive(Debug, Clone, ShankInstruction, BorshSerialize, BorshDeserialize)]
@@ -19,7 +35,9 @@ pub enum TokenRecipesInstruction {
 
     /// Add an ingredient to a recipe.
     /// This could be an input or output ingredient.
-    /// CAREFUL: If the ingredient is an output, the mint authority will be transferred to the program.
+    /// CAREFUL: If the ingredient is a mint output, the mint authority will be transferred to the program.
+    /// If the ingredient is a transfer output, tokens will be transferred from the vault of the recipe
+    /// which must be funded using the DepositVaultTokens instruction.
     /// Removing the ingredient will transfer the mint authority back to the recipe authority if and only
     /// if no other recipe uses this mint as an output ingredient.
     #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
@@ -29,7 +47,7 @@ pub enum TokenRecipesInstruction {
     #[account(4, signer, name="authority", desc = "The authority of the recipe account and the mint authority of the ingredient if it's an output ingredient")]
     #[account(5, writable, signer, name="payer", desc = "The account paying for the storage fees")]
     #[account(6, name="system_program", desc = "The system program")]
-    #[account(7, name="token_program", desc = "The token program")]
+    #[account(7, name="token_program", desc = "The token program owning the mint, i.e. SPL Token or SPL Token-2022")]
     #[default_optional_accounts]
     AddIngredient {
         /// Which input or output ingredient to add.
@@ -53,7 +71,7 @@ pub enum TokenRecipesInstruction {
     #[account(4, signer, name="authority", desc = "The authority of the recipe account and the mint authority of the ingredient if it's an output ingredient")]
     #[account(5, writable, signer, name="payer", desc = "The account paying for the storage fees")]
     #[account(6, name="system_program", desc = "The system program")]
-    #[account(7, name="token_program", desc = "The token program")]
+    #[account(7, name="token_program", desc = "The token program owning the mint, i.e. SPL Token or SPL Token-2022")]
     #[default_optional_accounts]
     RemoveIngredient {
         /// Which input or output ingredient to remove.
@@ -75,23 +93,50 @@ pub enum TokenRecipesInstruction {
     /// Remaining accounts must be used to provide the required accounts of each
     /// ingredients in the order they are stored in the recipe starting with the
     /// input ingredients.
+    /// The base fees are read from the fees feature PDA. Until the admin creates it
+    /// using AdminSetFeature, the default base fees and fee percentages are used.
     /// 
     /// If the ingredient is an input, the remaining accounts must be:
     ///   - [writable] The mint account of the ingredient. 
     ///   - [writable] The token account of the ingredient.
+    ///   - The token program owning the mint, i.e. SPL Token or SPL Token-2022.
     ///   - [optional] The destination account, when a destination is set on the ingredient.
     ///   - [optional, writable] The destination token account, when a destination is set on the ingredient.
+    ///   - [optional] The SPL Memo program, when the destination token account requires incoming transfer memos.
+    ///   - [optional] The transfer hook program, its validation account and its extra accounts, when the mint has a transfer hook.
+    ///
+    /// If the ingredient is a hold input, nothing is consumed and the remaining accounts must be:
+    ///   - The mint account of the ingredient.
+    ///   - The token account of the ingredient.
+    ///   - The token program owning the mint, i.e. SPL Token or SPL Token-2022.
     /// 
-    /// If the ingredient is an output, the remaining accounts must be:
+    /// If the ingredient is a mint output, the remaining accounts must be:
     ///   - [writable] The mint account of the ingredient.
     ///   - [writable] The token account of the ingredient.
+    ///   - The token program owning the mint, i.e. SPL Token or SPL Token-2022.
     ///   - The delegated ingredient PDA of the ingredient.
+    ///
+    /// If the ingredient is a transfer output, the remaining accounts must be:
+    ///   - The mint account of the ingredient.
+    ///   - [writable] The token account of the ingredient.
+    ///   - The token program owning the mint, i.e. SPL Token or SPL Token-2022.
+    ///   - [writable] The vault of the recipe, i.e. the associated token account of the recipe for the mint.
+    ///   - [optional] The SPL Memo program, when the token account requires incoming transfer memos.
+    ///   - [optional] The transfer hook program, its validation account and its extra accounts, when the mint has a transfer hook.
+    ///
+    /// If the ingredient is a loot table output, the loot table is committed to the loot roll
+    /// PDA of the owner and rolled in a later slot by the RevealLoot instruction. The owner must
+    /// reveal the loot of a previous craft before crafting the recipe again. The remaining accounts must be:
+    ///   - [writable] The loot roll PDA of the owner for the recipe.
     #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
     #[account(1, signer, name="owner", desc = "The owner of the token accounts")]
     #[account(2, writable, signer, name="payer", desc = "The account paying for the storage fees if we have to create associated token accounts")]
     #[account(3, name="system_program", desc = "The system program")]
-    #[account(4, name="token_program", desc = "The token program")]
+    #[account(4, name="token_program", desc = "The SPL Token or SPL Token-2022 program, ingredients provide the token program of their own mint")]
     #[account(5, name="ata_program", desc = "The associated token program")]
+    #[account(6, optional, writable, name="crafter_record", desc = "The crafter record PDA of the owner, required if the recipe limits the crafts per wallet")]
+    #[account(7, name="fees_feature_pda", desc = "The fees feature PDA storing the base fees and fee percentages")]
+    #[default_optional_accounts]
     Craft {
         /// The amount of recipes to craft.
         quantity: u64,
@@ -114,12 +159,14 @@ pub enum TokenRecipesInstruction {
     DeleteRecipe,
 
     /// [ADMIN ONLY] Set a feature on the program.
-    #[account(0, signer, name="program_id", desc = "The program as a signer")]
-    #[account(1, writable, name="feature_pda", desc = "The feature PDA")]
-    #[account(2, signer, writable, name="payer", desc = "The account that pays for the storage fees")]
-    #[account(3, name="system_program", desc = "The system program")]
+    #[account(0, name="program_config", desc = "The program config PDA")]
+    #[account(1, signer, name="admin", desc = "The admin authority of the program")]
+    #[account(2, writable, name="feature_pda", desc = "The feature PDA")]
+    #[account(3, signer, writable, name="payer", desc = "The account that pays for the storage fees")]
+    #[account(4, name="system_program", desc = "The system program")]
     AdminSetFeature {
-        /// The feature to set.
+        /// The feature to set. Its bump and version are replaced by the canonical bump of
+        /// the feature PDA and the current version of its layout.
         feature: Feature,
     },
 
@@ -130,7 +177,7 @@ pub enum TokenRecipesInstruction {
     #[account(3, signer, name="owner", desc = "The owner of the token account, usually the same as the authority")]
     #[account(4, writable, name="mint", desc = "The mint account that unlocks the feature")]
     #[account(5, writable, name="token", desc = "The token account linking the mint and owner accounts")]
-    #[account(6, name="token_program", desc = "The token program")]
+    #[account(6, name="token_program", desc = "The token program owning the mint, i.e. SPL Token or SPL Token-2022")]
     UnlockFeature,
 
     /// Set the fees of a recipe once a certain level is reached on the fees feature.
@@ -164,14 +211,250 @@ pub enum TokenRecipesInstruction {
     #[account(7, name="token_program", desc = "The token program")]
     #[account(8, name="ata_program", desc = "The associated token program")]
     CollectExperience,
+
+    /// Deposit tokens in the vault of a recipe so they can be transferred by its transfer outputs.
+    /// The vault is the associated token account of the recipe and is created if needed.
+    /// Remaining accounts may include the SPL Memo program and transfer hook accounts
+    /// required by Token-2022 extensions.
+    #[account(0, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account and the owner of the token account")]
+    #[account(2, name="mint", desc = "The mint account of the tokens to deposit")]
+    #[account(3, writable, name="token", desc = "The token account to deposit from")]
+    #[account(4, writable, name="vault", desc = "The vault of the recipe, i.e. its associated token account")]
+    #[account(5, writable, signer, name="payer", desc = "The account paying for the storage fees, in case the vault needs to be created")]
+    #[account(6, name="system_program", desc = "The system program")]
+    #[account(7, name="token_program", desc = "The token program owning the mint, i.e. SPL Token or SPL Token-2022")]
+    #[account(8, name="ata_program", desc = "The associated token program")]
+    DepositVaultTokens {
+        /// The amount of tokens to deposit.
+        amount: u64,
+    },
+
+    /// Withdraw tokens from the vault of a recipe.
+    /// Remaining accounts may include the SPL Memo program and transfer hook accounts
+    /// required by Token-2022 extensions.
+    #[account(0, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account")]
+    #[account(2, name="mint", desc = "The mint account of the tokens to withdraw")]
+    #[account(3, writable, name="token", desc = "The token account of the authority receiving the tokens")]
+    #[account(4, writable, name="vault", desc = "The vault of the recipe, i.e. its associated token account")]
+    #[account(5, writable, signer, name="payer", desc = "The account paying for the storage fees, in case an associated token account needs to be created")]
+    #[account(6, name="system_program", desc = "The system program")]
+    #[account(7, name="token_program", desc = "The token program owning the mint, i.e. SPL Token or SPL Token-2022")]
+    #[account(8, name="ata_program", desc = "The associated token program")]
+    WithdrawVaultTokens {
+        /// The amount of tokens to withdraw.
+        amount: u64,
+    },
+
+    /// Add a loot table output to a recipe.
+    /// Every craft commits the loot table, which is then rolled by the RevealLoot
+    /// instruction to mint the amount of the picked entries.
+    /// CAREFUL: The mint authority of every entry will be transferred to the program.
+    ///
+    /// For each entry of the loot table, the remaining accounts must be:
+    ///   - [writable] The mint account of the entry.
+    ///   - [writable] The ingredient record PDA of the entry.
+    ///   - [writable] The delegated ingredient PDA of the entry.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account and the mint authority of all entries")]
+    #[account(2, writable, signer, name="payer", desc = "The account paying for the storage fees")]
+    #[account(3, name="system_program", desc = "The system program")]
+    #[account(4, name="token_program", desc = "The token program")]
+    AddLootTableOutput {
+        /// How many times the loot table is rolled per craft.
+        rolls: u8,
+        /// The weighted entries of the loot table.
+        entries: Vec<LootTableEntry>,
+    },
+
+    /// Remove a loot table output from a recipe.
+    /// The mint authority of every entry will be transferred back to the original authority
+    /// if no other recipe uses it as an output.
+    /// Pending loot rolls of the recipe can no longer mint the entries whose authority is transferred back.
+    ///
+    /// For each entry of the loot table, the remaining accounts must be:
+    ///   - [writable] The mint account of the entry.
+    ///   - [writable] The ingredient record PDA of the entry.
+    ///   - [writable] The delegated ingredient PDA of the entry.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account")]
+    #[account(2, writable, signer, name="payer", desc = "The account paying for the storage fees")]
+    #[account(3, name="system_program", desc = "The system program")]
+    #[account(4, name="token_program", desc = "The token program")]
+    RemoveLootTableOutput {
+        /// The index of the loot table in the outputs of the recipe.
+        index: u16,
+    },
+
+    /// Propose a new authority for a recipe.
+    /// The new authority must accept it using the AcceptRecipeAuthority instruction.
+    /// Providing the current authority as the new authority cancels any pending transfer.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The current authority of the recipe account")]
+    #[account(2, name="new_authority", desc = "The proposed authority of the recipe account")]
+    TransferRecipeAuthority,
+
+    /// Accept the authority of a recipe as its pending authority.
+    /// The delegated ingredients of all mint outputs are handed over to the new authority, except
+    /// the ones also used by other recipes which stay with the current authority until every
+    /// recipe removes them, at which point the current authority gets the mint authority back.
+    ///
+    /// The remaining accounts must be, in the order of the outputs of the recipe:
+    ///   - [writable] The delegated ingredient PDA of each distinct mint output, including each loot table entry.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="new_authority", desc = "The pending authority of the recipe account")]
+    AcceptRecipeAuthority,
+
+    /// Update an ingredient of a recipe in place.
+    /// Ingredients can only be swapped to another ingredient type that uses the same
+    /// accounts, i.e. BurnToken and TransferToken inputs or MintToken and MintTokenWithMaxSupply outputs.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, optional, name="mint", desc = "The mint account of the ingredient")]
+    #[account(2, signer, name="authority", desc = "The authority of the recipe account")]
+    #[account(3, writable, signer, name="payer", desc = "The account paying for the storage fees")]
+    #[account(4, name="system_program", desc = "The system program")]
+    #[default_optional_accounts]
+    UpdateIngredient {
+        /// Which input or output ingredient to update.
+        ingredient_type: IngredientType,
+        /// The new type of the ingredient, if it should change.
+        new_ingredient_type: Option<IngredientType>,
+        /// The new amount of tokens required if it's an input ingredient or minted otherwise.
+        amount: Option<u64>,
+        /// The new destination of the ingredient, if it has one.
+        destination: Option<Pubkey>,
+        /// The new maximum supply of the ingredient, if it has one.
+        max_supply: Option<u64>,
+    },
+
+    /// Set the maximum number of times each wallet can craft a recipe,
+    /// quantities included. Zero means there is no limit.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account")]
+    SetMaxCraftsPerWallet {
+        /// The maximum number of crafts per wallet or zero for no limit.
+        max_crafts_per_wallet: u64,
+    },
+
+    /// Set the time window during which an active recipe can be crafted.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account")]
+    SetTimeWindow {
+        /// The Unix timestamp from which the recipe can be crafted, if any.
+        start_time: Option<i64>,
+        /// The Unix timestamp from which the recipe can no longer be crafted, if any.
+        end_time: Option<i64>,
+    },
+
+    /// Set the merkle root of the wallets allowed to craft a recipe.
+    /// Each leaf is the keccak hash of an allowed owner and pairs are hashed in sorted order.
+    /// The recipe must be paused.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="authority", desc = "The authority of the recipe account")]
+    SetAllowlistRoot {
+        /// The merkle root of the allowlist or None to allow every wallet.
+        root: Option<[u8; 32]>,
+    },
+
+    /// Check that a recipe can be crafted without modifying any account and
+    /// return a Borsh-serialized `CraftQuote` using the return data of the transaction.
+    /// This instruction is meant to be simulated before crafting.
+    /// The allowlist of the recipe is not checked. Since the quote must fit in the return data,
+    /// recipes with more than 24 burn or transfer token inputs cannot be quoted.
+    /// Like the Craft instruction, it uses the default fees until the fees feature PDA exists.
+    ///
+    /// For every ingredient, in the same order as the Craft instruction, the remaining accounts must be:
+    ///   - For token inputs, including hold inputs, the mint account and the token account of the ingredient.
+    ///   - For mint outputs with a maximum supply, the mint account of the ingredient.
+    ///   - For transfer outputs, the mint account of the ingredient and the vault of the recipe.
+    ///   - Nothing for other ingredients.
+    #[account(0, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, name="owner", desc = "The owner of the token accounts")]
+    #[account(2, name="payer", desc = "The account paying for the lamport costs and fees")]
+    #[account(3, optional, name="crafter_record", desc = "The crafter record PDA of the owner, required if the recipe limits the crafts per wallet")]
+    #[account(4, name="fees_feature_pda", desc = "The fees feature PDA storing the base fees and fee percentages")]
+    #[default_optional_accounts]
+    QuoteCraft {
+        /// The amount of recipes to craft.
+        quantity: u64,
+    },
+
+    /// Rewrite an account of the program with the current version of its layout,
+    /// resizing it if needed. Anyone can migrate an account and the payer funds
+    /// the rent of any additional space. Migrating an up-to-date account has no effect.
+    #[account(0, writable, name="account", desc = "The program account to migrate")]
+    #[account(1, signer, writable, name="payer", desc = "The account paying for the storage fees")]
+    #[account(2, name="system_program", desc = "The system program")]
+    MigrateAccount,
+
+    /// Create the program config PDA storing the admin authority of the program.
+    /// It can only be created once, by the upgrade authority of the program.
+    #[account(0, writable, name="program_config", desc = "The program config PDA")]
+    #[account(1, name="admin", desc = "The admin authority of the program")]
+    #[account(2, signer, name="upgrade_authority", desc = "The upgrade authority of the program")]
+    #[account(3, name="program_data", desc = "The ProgramData account of the program")]
+    #[account(4, signer, writable, name="payer", desc = "The account paying for the storage fees")]
+    #[account(5, name="system_program", desc = "The system program")]
+    InitializeProgramConfig,
+
+    /// Propose a new admin authority for the program.
+    /// The new admin must accept it using the AcceptAdminAuthority instruction.
+    /// Providing the current admin as the new admin cancels any pending transfer.
+    #[account(0, writable, name="program_config", desc = "The program config PDA")]
+    #[account(1, signer, name="admin", desc = "The current admin authority of the program")]
+    #[account(2, name="new_admin", desc = "The proposed admin authority of the program")]
+    TransferAdminAuthority,
+
+    /// Accept the admin authority of the program as its pending admin.
+    #[account(0, writable, name="program_config", desc = "The program config PDA")]
+    #[account(1, signer, name="new_admin", desc = "The pending admin authority of the program")]
+    AcceptAdminAuthority,
+
+    /// Roll the loot tables committed by a craft and mint the picked entries to the owner.
+    /// The rolls are seeded by the hash of the slot of the craft, which is unknown when crafting,
+    /// so the loot can only be revealed from the next slot onwards. Anyone can reveal the loot.
+    /// Once the hash of the slot of the craft is no longer stored in the SlotHashes sysvar,
+    /// i.e. after 512 slots, revealing the loot forfeits it and mints nothing.
+    ///
+    /// For each entry of each committed loot table, the remaining accounts must be
+    /// the same accounts as a mint output of the Craft instruction.
+    #[account(0, writable, name="loot_roll", desc = "The loot roll PDA to reveal")]
+    #[account(1, name="owner", desc = "The owner of the token accounts")]
+    #[account(2, writable, signer, name="payer", desc = "The account paying for the storage fees if we have to create associated token accounts")]
+    #[account(3, writable, name="craft_payer", desc = "The account that paid for the loot roll, it will receive its storage fees")]
+    #[account(4, name="system_program", desc = "The system program")]
+    #[account(5, name="ata_program", desc = "The associated token program")]
+    #[account(6, name="slot_hashes", desc = "The SlotHashes sysvar")]
+    RevealLoot,
+
+    /// Craft a recipe that has an allowlist by providing the merkle proof of the owner.
+    /// It requires the same accounts as the Craft instruction, which is
+    /// kept unchanged for recipes without an allowlist.
+    #[account(0, writable, name="recipe", desc = "The address of the recipe account")]
+    #[account(1, signer, name="owner", desc = "The owner of the token accounts")]
+    #[account(2, writable, signer, name="payer", desc = "The account paying for the storage fees if we have to create associated token accounts")]
+    #[account(3, name="system_program", desc = "The system program")]
+    #[account(4, name="token_program", desc = "The SPL Token or SPL Token-2022 program, ingredients provide the token program of their own mint")]
+    #[account(5, name="ata_program", desc = "The associated token program")]
+    #[account(6, optional, writable, name="crafter_record", desc = "The crafter record PDA of the owner, required if the recipe limits the crafts per wallet")]
+    #[account(7, name="fees_feature_pda", desc = "The fees feature PDA storing the base fees and fee percentages")]
+    #[default_optional_accounts]
+    CraftWithProof {
+        /// The amount of recipes to craft.
+        quantity: u64,
+        /// The merkle proof of the owner in the allowlist of the recipe.
+        proof: Vec<[u8; 32]>,
+    },
 }
 
-pub fn create_recipe(recipe: &Pubkey, authority: &Pubkey, payer: &Pubkey) -> Instruction {
+pub fn create_recipe(base: &Pubkey, authority: &Pubkey, payer: &Pubkey) -> Instruction {
     let accounts = vec![
-        AccountMeta::new(*recipe, true),
+        AccountMeta::new_readonly(*base, true),
+        AccountMeta::new(find_recipe_pda(base).0, false),
         AccountMeta::new_readonly(*authority, false),
         AccountMeta::new(*payer, true),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
     ];
     Instruction {
         program_id: crate::id(),
@@ -180,34 +463,620 @@ pub fn create_recipe(recipe: &Pubkey, authority: &Pubkey, payer: &Pubkey) -> Ins
     }
 }
 
+/// Accounts shared by the AddIngredient, RemoveIngredient and UpdateIngredient instructions.
+/// The ingredient record and the delegated ingredient default to the program ID when not needed.
+fn ingredient_accounts(
+    recipe: &Pubkey,
+    mint: Option<&Pubkey>,
+    ingredient_type: &IngredientType,
+) -> Vec<AccountMeta> {
+    let mint = match (mint, ingredient_type) {
+        (_, IngredientType::TransferSolInput) | (None, _) => None,
+        (Some(mint), _) => Some(mint),
+    };
+    let ingredient_record = mint.map(|mint| find_ingredient_record_pda(mint, recipe).0);
+    let delegated_ingredient = match ingredient_type {
+        IngredientType::MintTokenOutput | IngredientType::MintTokenWithMaxSupplyOutput => {
+            mint.map(|mint| find_delegated_ingredient_pda(mint).0)
+        }
+        _ => None,
+    };
+    vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new(*mint.unwrap_or(&crate::id()), false),
+        AccountMeta::new(ingredient_record.unwrap_or(crate::id()), false),
+        AccountMeta::new(delegated_ingredient.unwrap_or(crate::id()), false),
+    ]
+}
+
 pub fn add_ingredient(
     recipe: &Pubkey,
-    mint: &Pubkey,
-    ingredient_record: &Pubkey,
-    delegated_ingredient: Option<&Pubkey>,
+    mint: Option<&Pubkey>,
     authority: &Pubkey,
     payer: &Pubkey,
-    amount: u64,
+    token_program: &Pubkey,
     ingredient_type: IngredientType,
+    amount: u64,
     destination: Option<Pubkey>,
     max_supply: Option<u64>,
+) -> Instruction {
+    let mut accounts = ingredient_accounts(recipe, mint, &ingredient_type);
+    accounts.extend([
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
+    ]);
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::AddIngredient {
+            ingredient_type,
+            amount,
+            destination,
+            max_supply,
+        }
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn remove_ingredient(
+    recipe: &Pubkey,
+    mint: Option<&Pubkey>,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    ingredient_type: IngredientType,
+) -> Instruction {
+    let mut accounts = ingredient_accounts(recipe, mint, &ingredient_type);
+    accounts.extend([
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
+    ]);
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::RemoveIngredient { ingredient_type }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn activate_recipe(recipe: &Pubkey, authority: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::ActivateRecipe
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn pause_recipe(recipe: &Pubkey, authority: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::PauseRecipe.try_to_vec().unwrap(),
+    }
+}
+
+/// Craft a recipe using associated token accounts for every ingredient
+/// and the SPL Token program for every mint.
+///
+/// Use `craft_with_remaining_accounts` along with `craft_input_accounts` and
+/// `craft_output_accounts` for Token-2022 mints, custom token accounts or to
+/// append the accounts required by Token-2022 extensions after an ingredient.
+/// A non-empty proof builds a CraftWithProof instruction instead of a Craft instruction.
+pub fn craft(
+    recipe: &Pubkey,
+    recipe_account: &Recipe,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let remaining_accounts = recipe_account
+        .inputs
+        .iter()
+        .flat_map(|input| craft_input_accounts(input, owner, &spl_token::id()))
+        .chain(
+            recipe_account
+                .outputs
+                .iter()
+                .flat_map(|output| craft_output_accounts(output, recipe, owner, &spl_token::id())),
+        )
+        .collect();
+    let crafter_record = match recipe_account.max_crafts_per_wallet {
+        0 => None,
+        _ => Some(find_crafter_record_pda(recipe, owner).0),
+    };
+    craft_with_remaining_accounts(
+        recipe,
+        owner,
+        payer,
+        crafter_record.as_ref(),
+        quantity,
+        proof,
+        remaining_accounts,
+    )
+}
+
+pub fn craft_with_remaining_accounts(
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    crafter_record: Option<&Pubkey>,
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        match crafter_record {
+            Some(crafter_record) => AccountMeta::new(*crafter_record, false),
+            None => AccountMeta::new_readonly(crate::id(), false),
+        },
+        AccountMeta::new_readonly(find_fees_feature_pda().0, false),
+    ];
+    accounts.extend(remaining_accounts);
+    let data = if proof.is_empty() {
+        TokenRecipesInstruction::Craft { quantity }
+    } else {
+        TokenRecipesInstruction::CraftWithProof { quantity, proof }
+    };
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: data.try_to_vec().unwrap(),
+    }
+}
+
+/// The remaining accounts of the Craft instruction for an input ingredient,
+/// using the associated token accounts of the owner and destination.
+pub fn craft_input_accounts(
+    input: &IngredientInput,
+    owner: &Pubkey,
+    token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    match input {
+        IngredientInput::BurnToken { mint, .. } => vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(
+                find_associated_token_address(owner, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        IngredientInput::TransferToken {
+            mint, destination, ..
+        } => vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(
+                find_associated_token_address(owner, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(*destination, false),
+            AccountMeta::new(
+                find_associated_token_address(destination, mint, token_program),
+                false,
+            ),
+        ],
+        IngredientInput::TransferSol { destination, .. } => {
+            vec![AccountMeta::new(*destination, false)]
+        }
+        IngredientInput::HoldToken { mint, .. } => vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(
+                find_associated_token_address(owner, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    }
+}
+
+/// The remaining accounts of the Craft instruction for an output ingredient,
+/// using the associated token accounts of the owner and the recipe.
+pub fn craft_output_accounts(
+    output: &IngredientOutput,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    match output {
+        IngredientOutput::MintToken { mint, .. }
+        | IngredientOutput::MintTokenWithMaxSupply { mint, .. } => {
+            mint_output_accounts(mint, owner, token_program)
+        }
+        IngredientOutput::TransferToken { mint, .. } => vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(
+                find_associated_token_address(owner, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(
+                find_associated_token_address(recipe, mint, token_program),
+                false,
+            ),
+        ],
+        IngredientOutput::MintTokenFromLootTable { .. } => {
+            vec![AccountMeta::new(find_loot_roll_pda(recipe, owner).0, false)]
+        }
+    }
+}
+
+/// The accounts minting a mint output or a loot table entry to
+/// the associated token account of the owner.
+fn mint_output_accounts(mint: &Pubkey, owner: &Pubkey, token_program: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(
+            find_associated_token_address(owner, mint, token_program),
+            false,
+        ),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(find_delegated_ingredient_pda(mint).0, false),
+    ]
+}
+
+pub fn delete_recipe(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    admin_fees_destination: &Pubkey,
+    shards_mint: &Pubkey,
+    experience_mint: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*admin_fees_destination, false),
+        AccountMeta::new_readonly(find_fees_feature_pda().0, false),
+        AccountMeta::new(*shards_mint, false),
+        AccountMeta::new(
+            find_associated_token_address(authority, shards_mint, &spl_token::id()),
+            false,
+        ),
+        AccountMeta::new_readonly(find_wisdom_feature_pda().0, false),
+        AccountMeta::new(*experience_mint, false),
+        AccountMeta::new(
+            find_associated_token_address(authority, experience_mint, &spl_token::id()),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::DeleteRecipe.try_to_vec().unwrap(),
+    }
+}
+
+pub fn admin_set_feature(admin: &Pubkey, payer: &Pubkey, feature: Feature) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(find_program_config_pda().0, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(find_feature_pda(&feature).0, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::AdminSetFeature { feature }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn unlock_feature(
+    recipe: &Pubkey,
+    feature_pda: &Pubkey,
+    authority: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*feature_pda, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(
+            find_associated_token_address(owner, mint, token_program),
+            false,
+        ),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::UnlockFeature.try_to_vec().unwrap(),
+    }
+}
+
+pub fn set_fees(recipe: &Pubkey, authority: &Pubkey, fees: u64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::SetFees { fees }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn collect_fees(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    admin_fees_destination: &Pubkey,
+    shards_mint: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(*admin_fees_destination, false),
+        AccountMeta::new_readonly(find_fees_feature_pda().0, false),
+        AccountMeta::new(*shards_mint, false),
+        AccountMeta::new(
+            find_associated_token_address(authority, shards_mint, &spl_token::id()),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::CollectFees.try_to_vec().unwrap(),
+    }
+}
+
+pub fn collect_experience(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    experience_mint: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(find_wisdom_feature_pda().0, false),
+        AccountMeta::new(*experience_mint, false),
+        AccountMeta::new(
+            find_associated_token_address(authority, experience_mint, &spl_token::id()),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::CollectExperience
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Accounts shared by the DepositVaultTokens and WithdrawVaultTokens instructions.
+fn vault_accounts(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
         AccountMeta::new_readonly(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
         AccountMeta::new_readonly(*mint, false),
-        AccountMeta::new(*ingredient_record, false),
-        AccountMeta::new(*delegated_ingredient.unwrap_or(&crate::id()), false),
+        AccountMeta::new(
+            find_associated_token_address(authority, mint, token_program),
+            false,
+        ),
+        AccountMeta::new(
+            find_associated_token_address(recipe, mint, token_program),
+            false,
+        ),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+    ]
+}
+
+pub fn deposit_vault_tokens(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vault_accounts(recipe, authority, payer, mint, token_program),
+        data: TokenRecipesInstruction::DepositVaultTokens { amount }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn withdraw_vault_tokens(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vault_accounts(recipe, authority, payer, mint, token_program),
+        data: TokenRecipesInstruction::WithdrawVaultTokens { amount }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Accounts shared by the AddLootTableOutput and RemoveLootTableOutput instructions.
+fn loot_table_accounts(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    entries: &[LootTableEntry],
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*recipe, false),
         AccountMeta::new_readonly(*authority, true),
         AccountMeta::new(*payer, true),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    entries.iter().for_each(|entry| {
+        accounts.extend([
+            AccountMeta::new(entry.mint, false),
+            AccountMeta::new(find_ingredient_record_pda(&entry.mint, recipe).0, false),
+            AccountMeta::new(find_delegated_ingredient_pda(&entry.mint).0, false),
+        ])
+    });
+    accounts
+}
+
+pub fn add_loot_table_output(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    rolls: u8,
+    entries: Vec<LootTableEntry>,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: loot_table_accounts(recipe, authority, payer, token_program, &entries),
+        data: TokenRecipesInstruction::AddLootTableOutput { rolls, entries }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn remove_loot_table_output(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    index: u16,
+    entries: &[LootTableEntry],
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: loot_table_accounts(recipe, authority, payer, token_program, entries),
+        data: TokenRecipesInstruction::RemoveLootTableOutput { index }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn transfer_recipe_authority(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*new_authority, false),
     ];
     Instruction {
         program_id: crate::id(),
         accounts,
-        data: TokenRecipesInstruction::AddIngredient {
-            amount,
+        data: TokenRecipesInstruction::TransferRecipeAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn accept_recipe_authority(
+    recipe: &Pubkey,
+    recipe_account: &Recipe,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*new_authority, true),
+    ];
+    accounts.extend(
+        recipe_account
+            .delegated_mints()
+            .iter()
+            .map(|mint| AccountMeta::new(find_delegated_ingredient_pda(mint).0, false)),
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::AcceptRecipeAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn update_ingredient(
+    recipe: &Pubkey,
+    mint: Option<&Pubkey>,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    ingredient_type: IngredientType,
+    new_ingredient_type: Option<IngredientType>,
+    amount: Option<u64>,
+    destination: Option<Pubkey>,
+    max_supply: Option<u64>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*mint.unwrap_or(&crate::id()), false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::UpdateIngredient {
             ingredient_type,
+            new_ingredient_type,
+            amount,
             destination,
             max_supply,
         }
@@ -215,3 +1084,282 @@ pub fn add_ingredient(
         .unwrap(),
     }
 }
+
+pub fn set_max_crafts_per_wallet(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    max_crafts_per_wallet: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::SetMaxCraftsPerWallet {
+            max_crafts_per_wallet,
+        }
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn set_time_window(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::SetTimeWindow {
+            start_time,
+            end_time,
+        }
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn set_allowlist_root(
+    recipe: &Pubkey,
+    authority: &Pubkey,
+    root: Option<[u8; 32]>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipe, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::SetAllowlistRoot { root }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn migrate_account(account: &Pubkey, payer: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::MigrateAccount
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn initialize_program_config(
+    admin: &Pubkey,
+    upgrade_authority: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_program_config_pda().0, false),
+        AccountMeta::new_readonly(*admin, false),
+        AccountMeta::new_readonly(*upgrade_authority, true),
+        AccountMeta::new_readonly(find_program_data_address(), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::InitializeProgramConfig
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn transfer_admin_authority(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_program_config_pda().0, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new_readonly(*new_admin, false),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::TransferAdminAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn accept_admin_authority(new_admin: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_program_config_pda().0, false),
+        AccountMeta::new_readonly(*new_admin, true),
+    ];
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::AcceptAdminAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Quote a recipe using associated token accounts for every ingredient
+/// and the SPL Token program for every mint.
+pub fn quote_craft(
+    recipe: &Pubkey,
+    recipe_account: &Recipe,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quantity: u64,
+) -> Instruction {
+    let remaining_accounts = recipe_account
+        .inputs
+        .iter()
+        .flat_map(|input| quote_craft_input_accounts(input, owner, &spl_token::id()))
+        .chain(
+            recipe_account
+                .outputs
+                .iter()
+                .flat_map(|output| quote_craft_output_accounts(output, recipe, &spl_token::id())),
+        )
+        .collect();
+    let crafter_record = match recipe_account.max_crafts_per_wallet {
+        0 => None,
+        _ => Some(find_crafter_record_pda(recipe, owner).0),
+    };
+    quote_craft_with_remaining_accounts(
+        recipe,
+        owner,
+        payer,
+        crafter_record.as_ref(),
+        quantity,
+        remaining_accounts,
+    )
+}
+
+pub fn quote_craft_with_remaining_accounts(
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    crafter_record: Option<&Pubkey>,
+    quantity: u64,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*recipe, false),
+        AccountMeta::new_readonly(*owner, false),
+        AccountMeta::new_readonly(*payer, false),
+        AccountMeta::new_readonly(*crafter_record.unwrap_or(&crate::id()), false),
+        AccountMeta::new_readonly(find_fees_feature_pda().0, false),
+    ];
+    accounts.extend(remaining_accounts);
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::QuoteCraft { quantity }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// The remaining accounts of the QuoteCraft instruction for an input ingredient,
+/// using the associated token account of the owner.
+pub fn quote_craft_input_accounts(
+    input: &IngredientInput,
+    owner: &Pubkey,
+    token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    match input {
+        IngredientInput::BurnToken { mint, .. }
+        | IngredientInput::TransferToken { mint, .. }
+        | IngredientInput::HoldToken { mint, .. } => vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(
+                find_associated_token_address(owner, mint, token_program),
+                false,
+            ),
+        ],
+        IngredientInput::TransferSol { .. } => vec![],
+    }
+}
+
+/// The remaining accounts of the QuoteCraft instruction for an output ingredient,
+/// using the associated token account of the recipe as a vault.
+pub fn quote_craft_output_accounts(
+    output: &IngredientOutput,
+    recipe: &Pubkey,
+    token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    match output {
+        IngredientOutput::MintTokenWithMaxSupply { mint, .. } => {
+            vec![AccountMeta::new_readonly(*mint, false)]
+        }
+        IngredientOutput::TransferToken { mint, .. } => vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(
+                find_associated_token_address(recipe, mint, token_program),
+                false,
+            ),
+        ],
+        IngredientOutput::MintToken { .. } | IngredientOutput::MintTokenFromLootTable { .. } => {
+            vec![]
+        }
+    }
+}
+
+/// Reveal a loot roll using associated token accounts for every
+/// entry and the SPL Token program for every mint.
+pub fn reveal_loot(
+    loot_roll: &Pubkey,
+    loot_roll_account: &LootRoll,
+    payer: &Pubkey,
+) -> Instruction {
+    let remaining_accounts = loot_roll_account
+        .loot_tables
+        .iter()
+        .flat_map(|loot_table| {
+            loot_table.entries.iter().flat_map(|entry| {
+                mint_output_accounts(&entry.mint, &loot_roll_account.owner, &spl_token::id())
+            })
+        })
+        .collect();
+    reveal_loot_with_remaining_accounts(
+        loot_roll,
+        &loot_roll_account.owner,
+        payer,
+        &loot_roll_account.payer,
+        remaining_accounts,
+    )
+}
+
+pub fn reveal_loot_with_remaining_accounts(
+    loot_roll: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    craft_payer: &Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*loot_roll, false),
+        AccountMeta::new_readonly(*owner, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*craft_payer, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(slot_hashes::id(), false),
+    ];
+    accounts.extend(remaining_accounts);
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: TokenRecipesInstruction::RevealLoot.try_to_vec().unwrap(),
+    }
+}
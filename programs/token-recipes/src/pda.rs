@@ -0,0 +1,93 @@
+use crate::state::{
+    crafter_record::CrafterRecord,
+    delegated_ingredient::DelegatedIngredient,
+    features::{
+        additional_outputs::AdditionalOutputsFeature, fees::FeesFeature,
+        max_supply::MaxSupplyFeature, sol_payment::SolPaymentFeature,
+        transfer_inputs::TransferInputsFeature, wisdom::WisdomFeature, Feature,
+    },
+    ingredient_record::IngredientRecord,
+    loot_roll::LootRoll,
+    program_config::ProgramConfig,
+    recipe::Recipe,
+};
+use solana_program::{bpf_loader_upgradeable, pubkey::Pubkey};
+
+pub fn find_recipe_pda(base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&Recipe::seeds(base), &crate::id())
+}
+
+pub fn find_ingredient_record_pda(mint: &Pubkey, recipe: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&IngredientRecord::seeds(mint, recipe), &crate::id())
+}
+
+pub fn find_delegated_ingredient_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&DelegatedIngredient::seeds(mint), &crate::id())
+}
+
+pub fn find_crafter_record_pda(recipe: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&CrafterRecord::seeds(recipe, owner), &crate::id())
+}
+
+pub fn find_loot_roll_pda(recipe: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&LootRoll::seeds(recipe, owner), &crate::id())
+}
+
+pub fn find_fees_feature_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&FeesFeature::seeds(), &crate::id())
+}
+
+pub fn find_additional_outputs_feature_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&AdditionalOutputsFeature::seeds(), &crate::id())
+}
+
+pub fn find_transfer_inputs_feature_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&TransferInputsFeature::seeds(), &crate::id())
+}
+
+pub fn find_max_supply_feature_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&MaxSupplyFeature::seeds(), &crate::id())
+}
+
+pub fn find_sol_payment_feature_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&SolPaymentFeature::seeds(), &crate::id())
+}
+
+pub fn find_wisdom_feature_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&WisdomFeature::seeds(), &crate::id())
+}
+
+/// Find the PDA storing the given feature.
+pub fn find_feature_pda(feature: &Feature) -> (Pubkey, u8) {
+    match feature {
+        Feature::Fees(_) => find_fees_feature_pda(),
+        Feature::AdditionalOutputs(_) => find_additional_outputs_feature_pda(),
+        Feature::TransferInputs(_) => find_transfer_inputs_feature_pda(),
+        Feature::MaxSupply(_) => find_max_supply_feature_pda(),
+        Feature::SolPayment(_) => find_sol_payment_feature_pda(),
+        Feature::Wisdom(_) => find_wisdom_feature_pda(),
+    }
+}
+
+pub fn find_program_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&ProgramConfig::seeds(), &crate::id())
+}
+
+/// Find the ProgramData account of the program, which stores its upgrade authority.
+pub fn find_program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[crate::id().as_ref()], &bpf_loader_upgradeable::id()).0
+}
+
+/// Find the associated token account of the given owner and mint
+/// for the given token program, i.e. SPL Token or SPL Token-2022.
+pub fn find_associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        owner,
+        mint,
+        token_program,
+    )
+}
@@ -0,0 +1,24 @@
+use crate::state::program_config::ProgramConfig;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+pub(crate) fn accept_admin_authority(accounts: &[AccountInfo]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let program_config = next_account_info(account_info_iter)?;
+    let new_admin = next_account_info(account_info_iter)?;
+
+    // Check: program_config.
+    let mut program_config_account = ProgramConfig::get_writable(program_config)?;
+
+    // Check: new_admin.
+    program_config_account.assert_signer_pending_admin(new_admin)?;
+
+    // Update the admin.
+    program_config_account.admin = *new_admin.key;
+    program_config_account.pending_admin = Pubkey::default();
+    program_config_account.save(program_config)
+}
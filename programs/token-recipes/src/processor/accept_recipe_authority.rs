@@ -0,0 +1,36 @@
+use crate::state::{delegated_ingredient::DelegatedIngredient, recipe::Recipe};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+pub(crate) fn accept_recipe_authority(accounts: &[AccountInfo]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let new_authority = next_account_info(account_info_iter)?;
+
+    // Check: recipe.
+    let mut recipe_account = Recipe::get_writable(recipe)?;
+
+    // Check: new_authority.
+    recipe_account.assert_signer_pending_authority(new_authority)?;
+
+    // Migrate the delegated ingredients of every output mint.
+    for mint in recipe_account.delegated_mints() {
+        let delegated_ingredient = next_account_info(account_info_iter)?;
+        DelegatedIngredient::migrate_authority(
+            delegated_ingredient,
+            &mint,
+            &recipe_account.authority,
+            new_authority.key,
+            recipe_account.delegated_mint_uses(&mint),
+        )?;
+    }
+
+    // Update the authority.
+    recipe_account.authority = *new_authority.key;
+    recipe_account.pending_authority = Pubkey::default();
+    recipe_account.save_header(recipe)
+}
@@ -1,4 +1,7 @@
-use crate::state::recipe::{Recipe, RecipeStatus};
+use crate::{
+    events::TokenRecipesEvent,
+    state::{recipe::RecipeStatus, recipe_header::RecipeHeader},
+};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -11,10 +14,15 @@ pub(crate) fn activate_recipe(accounts: &[AccountInfo]) -> ProgramResult {
     let authority = next_account_info(account_info_iter)?;
 
     // Check: recipe.
-    let mut recipe_account = Recipe::get_writable(recipe)?;
-    recipe_account.assert_signer_authority(authority)?;
+    let mut recipe_header = RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
 
     // Activate the recipe.
-    recipe_account.status = RecipeStatus::Active;
-    recipe_account.save(recipe)
+    recipe_header.set_status(RecipeStatus::Active);
+
+    // Emit event.
+    TokenRecipesEvent::ActivateRecipe {
+        recipe: *recipe.key,
+    }
+    .emit()
 }
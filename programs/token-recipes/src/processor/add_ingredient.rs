@@ -1,6 +1,7 @@
 use crate::{
-    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
     error::TokenRecipesError,
+    events::TokenRecipesEvent,
     state::{
         features::{
             additional_outputs::assert_valid_additional_outputs,
@@ -39,12 +40,13 @@ pub(crate) fn add_ingredient(
     let token_program = next_account_info(account_info_iter)?;
 
     // Check accounts.
+    Recipe::migrate(recipe, payer, system_program)?;
     let mut recipe_account = Recipe::get_writable(recipe)?;
     recipe_account.assert_signer_authority(authority)?;
     assert_writable("payer", payer)?;
     assert_signer("payer", payer)?;
     assert_same_pubkeys("system_program", system_program, &system_program::id())?;
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
 
     // Check: amount
     if amount == 0 {
@@ -52,7 +54,7 @@ pub(crate) fn add_ingredient(
     }
 
     // Get ingredient from type.
-    let ingredient: Ingredient = match ingredient_type {
+    let ingredient: Ingredient = match ingredient_type.clone() {
         IngredientType::BurnTokenInput => Ingredient::Input(IngredientInput::BurnToken {
             mint: *mint.key,
             amount,
@@ -66,6 +68,10 @@ pub(crate) fn add_ingredient(
             lamports: amount,
             destination: destination.ok_or(TokenRecipesError::MissingDestinationArgument)?,
         }),
+        IngredientType::HoldTokenInput => Ingredient::Input(IngredientInput::HoldToken {
+            mint: *mint.key,
+            amount,
+        }),
         IngredientType::MintTokenOutput => Ingredient::Output(IngredientOutput::MintToken {
             mint: *mint.key,
             amount,
@@ -77,6 +83,12 @@ pub(crate) fn add_ingredient(
                 max_supply: max_supply.ok_or(TokenRecipesError::MissingMaxSupplyArgument)?,
             })
         }
+        IngredientType::TransferTokenOutput => {
+            Ingredient::Output(IngredientOutput::TransferToken {
+                mint: *mint.key,
+                amount,
+            })
+        }
     };
 
     // Add the ingredient.
@@ -107,5 +119,12 @@ pub(crate) fn add_ingredient(
     assert_valid_additional_outputs(&recipe_account)?;
     assert_valid_max_supply_outputs(&recipe_account)?;
 
-    Ok(())
+    // Emit event.
+    TokenRecipesEvent::AddIngredient {
+        recipe: *recipe.key,
+        mint: *mint.key,
+        ingredient_type,
+        amount,
+    }
+    .emit()
 }
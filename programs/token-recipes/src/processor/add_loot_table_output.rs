@@ -0,0 +1,49 @@
+use crate::{
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
+    state::{
+        features::additional_outputs::assert_valid_additional_outputs,
+        ingredient_output::{IngredientOutput, LootTableEntry},
+        recipe::Recipe,
+    },
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    system_program,
+};
+
+pub(crate) fn add_loot_table_output<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    rolls: u8,
+    entries: Vec<LootTableEntry>,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    // Check accounts.
+    Recipe::migrate(recipe, payer, system_program)?;
+    let mut recipe_account = Recipe::get_writable(recipe)?;
+    recipe_account.assert_signer_authority(authority)?;
+    assert_writable("payer", payer)?;
+    assert_signer("payer", payer)?;
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+    assert_token_program("token_program", token_program)?;
+
+    // Add the ingredient.
+    IngredientOutput::MintTokenFromLootTable { rolls, entries }.add_loot_table(
+        account_info_iter,
+        &mut recipe_account,
+        recipe,
+        authority,
+        payer,
+        system_program,
+    )?;
+
+    // Check feature invariants.
+    assert_valid_additional_outputs(&recipe_account)
+}
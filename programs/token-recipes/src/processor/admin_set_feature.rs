@@ -1,5 +1,5 @@
 use crate::{
-    assertions::{assert_pda, assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_pda, assert_signer, assert_writable},
     error::TokenRecipesError,
     state::{
         features::{
@@ -8,8 +8,9 @@ use crate::{
             transfer_inputs::TransferInputsFeature, wisdom::WisdomFeature, Feature,
         },
         key::Key,
+        program_config::ProgramConfig,
     },
-    utils::create_account,
+    utils::{create_account, realloc_legacy_account},
 };
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -19,13 +20,13 @@ use solana_program::{
 pub(crate) fn admin_set_feature(accounts: &[AccountInfo], feature: Feature) -> ProgramResult {
     // Accounts.
     let account_info_iter = &mut accounts.iter();
-    let program = next_account_info(account_info_iter)?;
+    let program_config = next_account_info(account_info_iter)?;
+    let admin = next_account_info(account_info_iter)?;
     let feature_pda = next_account_info(account_info_iter)?;
     let payer = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
 
-    assert_signer("program", program)?;
-    assert_same_pubkeys("program", program, &crate::id())?;
+    ProgramConfig::get(program_config)?.assert_signer_admin(admin)?;
     assert_writable("feature_pda", feature_pda)?;
     assert_writable("payer", payer)?;
     assert_signer("payer", payer)?;
@@ -72,9 +73,12 @@ pub(crate) fn admin_set_feature(accounts: &[AccountInfo], feature: Feature) -> P
     if key != expected_key {
         return Err(TokenRecipesError::InvalidAccountKey.into());
     }
+    if let Feature::Fees(f) = &feature {
+        f.assert_valid_fee_percentages()?;
+    }
 
-    let bump = assert_pda("feature_pda", feature_pda, &crate::id(), &seeds.clone())?;
-    let bump = [bump];
+    let feature_bump = assert_pda("feature_pda", feature_pda, &crate::id(), &seeds.clone())?;
+    let bump = [feature_bump];
     let mut seeds_with_bump = seeds;
     seeds_with_bump.push(&bump);
 
@@ -87,14 +91,48 @@ pub(crate) fn admin_set_feature(accounts: &[AccountInfo], feature: Feature) -> P
             &crate::id(),
             Some(&[&seeds_with_bump]),
         )?;
+    } else {
+        realloc_legacy_account(feature_pda, payer, system_program, size)?;
     }
 
+    // Store the canonical bump and the current version regardless of the ones provided.
+    let bump = feature_bump;
     match feature {
-        Feature::Fees(f) => f.save(feature_pda),
-        Feature::AdditionalOutputs(f) => f.save(feature_pda),
-        Feature::TransferInputs(f) => f.save(feature_pda),
-        Feature::MaxSupply(f) => f.save(feature_pda),
-        Feature::SolPayment(f) => f.save(feature_pda),
-        Feature::Wisdom(f) => f.save(feature_pda),
+        Feature::Fees(f) => FeesFeature {
+            bump,
+            version: FeesFeature::VERSION,
+            ..f
+        }
+        .save(feature_pda),
+        Feature::AdditionalOutputs(f) => AdditionalOutputsFeature {
+            bump,
+            version: AdditionalOutputsFeature::VERSION,
+            ..f
+        }
+        .save(feature_pda),
+        Feature::TransferInputs(f) => TransferInputsFeature {
+            bump,
+            version: TransferInputsFeature::VERSION,
+            ..f
+        }
+        .save(feature_pda),
+        Feature::MaxSupply(f) => MaxSupplyFeature {
+            bump,
+            version: MaxSupplyFeature::VERSION,
+            ..f
+        }
+        .save(feature_pda),
+        Feature::SolPayment(f) => SolPaymentFeature {
+            bump,
+            version: SolPaymentFeature::VERSION,
+            ..f
+        }
+        .save(feature_pda),
+        Feature::Wisdom(f) => WisdomFeature {
+            bump,
+            version: WisdomFeature::VERSION,
+            ..f
+        }
+        .save(feature_pda),
     }
 }
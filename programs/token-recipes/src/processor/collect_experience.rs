@@ -1,8 +1,10 @@
 use crate::{
-    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
+    events::TokenRecipesEvent,
     state::{
         features::wisdom::{collect_experience as collect_experience_logic, WisdomFeature},
         recipe::Recipe,
+        recipe_header::RecipeHeader,
     },
 };
 use solana_program::{
@@ -25,8 +27,10 @@ pub(crate) fn collect_experience<'a>(accounts: &'a [AccountInfo<'a>]) -> Program
     let ata_program = next_account_info(account_info_iter)?;
 
     // Check: recipe and authority.
-    let mut recipe_account = Recipe::get_writable(recipe)?;
-    recipe_account.assert_signer_authority(authority)?;
+    Recipe::migrate(recipe, payer, system_program)?;
+    let mut recipe_header = *RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
+    let experience = u64::from(recipe_header.accumulated_experience);
 
     // Check: payer.
     assert_signer("payer", payer)?;
@@ -34,7 +38,7 @@ pub(crate) fn collect_experience<'a>(accounts: &'a [AccountInfo<'a>]) -> Program
 
     // Check: programs.
     assert_same_pubkeys("system_program", system_program, &system_program::id())?;
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
     assert_same_pubkeys(
         "ata_program",
         ata_program,
@@ -44,16 +48,25 @@ pub(crate) fn collect_experience<'a>(accounts: &'a [AccountInfo<'a>]) -> Program
     // Collect the experience.
     let wisdom_feature_account = WisdomFeature::get(wisdom_feature_pda)?;
     collect_experience_logic(
-        recipe_account.accumulated_experience,
+        experience,
         &wisdom_feature_account.experience_mint,
         authority,
         experience_mint,
         experience_token,
         wisdom_feature_pda,
+        wisdom_feature_account.bump,
         payer,
     )?;
 
+    // Emit event.
+    TokenRecipesEvent::CollectExperience {
+        recipe: *recipe.key,
+        experience,
+    }
+    .emit()?;
+
     // Update the recipe.
-    recipe_account.accumulated_experience = 0;
-    recipe_account.save(recipe)
+    recipe_header.accumulated_experience = 0.into();
+    *RecipeHeader::load_mut(recipe)? = recipe_header;
+    Ok(())
 }
@@ -1,8 +1,10 @@
 use crate::{
-    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
+    events::TokenRecipesEvent,
     state::{
         features::fees::{collect_fees as collect_fees_logic, collect_shards, FeesFeature},
         recipe::Recipe,
+        recipe_header::RecipeHeader,
     },
 };
 use solana_program::{
@@ -26,8 +28,11 @@ pub(crate) fn collect_fees<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResult
     let ata_program = next_account_info(account_info_iter)?;
 
     // Check: recipe and authority.
-    let mut recipe_account = Recipe::get_writable(recipe)?;
-    recipe_account.assert_signer_authority(authority)?;
+    Recipe::migrate(recipe, payer, system_program)?;
+    let mut recipe_header = *RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
+    let admin_fees = u64::from(recipe_header.accumulated_admin_fees);
+    let shards = u64::from(recipe_header.accumulated_shards);
     assert_writable("authority", authority)?;
 
     // Check: payer.
@@ -36,7 +41,7 @@ pub(crate) fn collect_fees<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResult
 
     // Check: programs.
     assert_same_pubkeys("system_program", system_program, &system_program::id())?;
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
     assert_same_pubkeys(
         "ata_program",
         ata_program,
@@ -48,26 +53,37 @@ pub(crate) fn collect_fees<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResult
 
     // Collect the shards.
     collect_shards(
-        recipe_account.accumulated_shards,
+        shards,
         &fees_feature_account.shard_mint,
         authority,
         shards_mint,
         shards_token,
         fees_feature_pda,
+        fees_feature_account.bump,
         payer,
     )?;
 
     // Collect the lamports.
-    collect_fees_logic(
-        recipe_account.accumulated_admin_fees,
+    let authority_fees = collect_fees_logic(
+        admin_fees,
         &fees_feature_account.admin_destination,
         recipe,
         authority,
         admin_fees_destination,
     )?;
 
+    // Emit event.
+    TokenRecipesEvent::CollectFees {
+        recipe: *recipe.key,
+        authority_fees,
+        admin_fees,
+        shards,
+    }
+    .emit()?;
+
     // Update the recipe.
-    recipe_account.accumulated_admin_fees = 0;
-    recipe_account.accumulated_shards = 0;
-    recipe_account.save(recipe)
+    recipe_header.accumulated_admin_fees = 0.into();
+    recipe_header.accumulated_shards = 0.into();
+    *RecipeHeader::load_mut(recipe)? = recipe_header;
+    Ok(())
 }
@@ -1,8 +1,14 @@
 use crate::{
-    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
     error::TokenRecipesError,
+    events::TokenRecipesEvent,
     state::{
-        features::{fees::get_fees_and_shards_per_craft, wisdom::get_experience_per_craft},
+        craft_quote::TokenAmount,
+        crafter_record::CrafterRecord,
+        features::{
+            fees::{get_fees_and_shards_per_craft, FeesFeature},
+            wisdom::get_experience_per_craft,
+        },
         recipe::Recipe,
     },
     utils::transfer_lamports,
@@ -14,7 +20,11 @@ use solana_program::{
     system_program,
 };
 
-pub(crate) fn craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> ProgramResult {
+pub(crate) fn craft<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
     // Accounts.
     let account_info_iter = &mut accounts.iter();
     let recipe = next_account_info(account_info_iter)?;
@@ -23,13 +33,17 @@ pub(crate) fn craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> Progr
     let system_program = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
     let ata_program = next_account_info(account_info_iter)?;
+    let crafter_record = next_account_info(account_info_iter)?;
+    let fees_feature_pda = next_account_info(account_info_iter)?;
 
     // Check: recipe.
+    Recipe::migrate(recipe, payer, system_program)?;
     let mut recipe_account = Recipe::get_writable(recipe)?;
     recipe_account.assert_active()?;
 
     // Check: owner.
     assert_signer("owner", owner)?;
+    recipe_account.assert_allowlisted(owner, &proof)?;
 
     // Check: payer.
     assert_signer("payer", payer)?;
@@ -37,13 +51,24 @@ pub(crate) fn craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> Progr
 
     // Check: programs.
     assert_same_pubkeys("system_program", system_program, &system_program::id())?;
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
     assert_same_pubkeys(
         "ata_program",
         ata_program,
         &spl_associated_token_account::id(),
     )?;
 
+    // Check: crafter_record.
+    if recipe_account.max_crafts_per_wallet > 0 {
+        let mut crafter_record_account =
+            CrafterRecord::get_or_create(crafter_record, recipe, owner, payer, system_program)?;
+        crafter_record_account.increment(quantity, recipe_account.max_crafts_per_wallet)?;
+        crafter_record_account.save(crafter_record)?;
+    }
+
+    // Check: fees_feature_pda.
+    let fees_feature = FeesFeature::get_or_default(fees_feature_pda)?;
+
     // Craft ingredient inputs.
     recipe_account
         .inputs
@@ -52,14 +77,26 @@ pub(crate) fn craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> Progr
         .collect::<ProgramResult>()?;
 
     // Craft ingredient outputs.
-    recipe_account
+    let token_outputs = recipe_account
         .outputs
         .iter()
-        .map(|output| output.craft(account_info_iter, owner, payer, quantity))
-        .collect::<ProgramResult>()?;
+        .map(|output| {
+            output.craft(
+                account_info_iter,
+                recipe,
+                &recipe_account,
+                owner,
+                payer,
+                system_program,
+                quantity,
+            )
+        })
+        .collect::<Result<Vec<Vec<TokenAmount>>, _>>()?
+        .concat();
 
     // Take fees.
-    let (total_fees, admin_fees, shards) = get_fees_and_shards_per_craft(&recipe_account)?;
+    let (total_fees, admin_fees, shards) =
+        get_fees_and_shards_per_craft(&recipe_account, &fees_feature)?;
     if total_fees > 0 {
         transfer_lamports(payer, recipe, total_fees, None)?;
     }
@@ -76,9 +113,10 @@ pub(crate) fn craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> Progr
             .ok_or::<ProgramError>(TokenRecipesError::NumericalOverflow.into())?;
 
     // Update experience.
+    let experience = get_experience_per_craft(&recipe_account, &fees_feature);
     recipe_account.accumulated_experience = recipe_account
         .accumulated_experience
-        .checked_add(get_experience_per_craft(&recipe_account))
+        .checked_add(experience)
         .ok_or::<ProgramError>(TokenRecipesError::NumericalOverflow.into())?;
 
     // Update statistics.
@@ -91,8 +129,29 @@ pub(crate) fn craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> Progr
         .checked_add(quantity)
         .ok_or::<ProgramError>(TokenRecipesError::NumericalOverflow.into())?;
 
-    // Save recipe.
-    recipe_account.save(recipe)?;
+    // Save recipe header, the ingredients are left untouched.
+    recipe_account.save_header(recipe)?;
 
-    Ok(())
+    // Emit event.
+    let mut token_inputs = vec![];
+    let mut lamport_inputs: u64 = 0;
+    for input in &recipe_account.inputs {
+        token_inputs.extend(input.token_cost(quantity)?);
+        lamport_inputs = lamport_inputs
+            .checked_add(input.lamport_cost(quantity)?)
+            .ok_or::<ProgramError>(TokenRecipesError::NumericalOverflow.into())?;
+    }
+    TokenRecipesEvent::Craft {
+        recipe: *recipe.key,
+        owner: *owner.key,
+        quantity,
+        token_inputs,
+        lamport_inputs,
+        token_outputs,
+        fees: total_fees,
+        admin_fees,
+        shards,
+        experience,
+    }
+    .emit()
 }
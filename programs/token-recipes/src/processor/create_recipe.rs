@@ -10,6 +10,7 @@ use crate::{
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    pubkey::Pubkey,
     system_program,
 };
 
@@ -42,8 +43,8 @@ pub(crate) fn create_recipe(accounts: &[AccountInfo]) -> ProgramResult {
 
     // Create the recipe account.
     let mut seeds = Recipe::seeds(base.key);
-    let recipe_bump = &[recipe_bump];
-    seeds.push(recipe_bump);
+    let bump = [recipe_bump];
+    seeds.push(&bump);
     create_account(
         recipe,
         payer,
@@ -66,6 +67,13 @@ pub(crate) fn create_recipe(accounts: &[AccountInfo]) -> ProgramResult {
         accumulated_shards: 0,
         accumulated_experience: 0,
         feature_levels: FeatureLevels::default(),
+        version: Recipe::VERSION,
+        bump: recipe_bump,
+        pending_authority: Pubkey::default(),
+        max_crafts_per_wallet: 0,
+        start_time: 0,
+        end_time: 0,
+        allowlist_root: [0; 32],
         inputs: vec![],
         outputs: vec![],
     }
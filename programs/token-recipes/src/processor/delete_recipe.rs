@@ -1,6 +1,7 @@
 use crate::{
-    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
     error::TokenRecipesError,
+    events::TokenRecipesEvent,
     state::{
         features::{
             fees::{collect_fees, collect_shards, FeesFeature},
@@ -34,6 +35,7 @@ pub(crate) fn delete_recipe<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResul
     let ata_program = next_account_info(account_info_iter)?;
 
     // Check: recipe.
+    Recipe::migrate(recipe, payer, system_program)?;
     let recipe_account = Recipe::get_writable(recipe)?;
     assert_writable("authority", authority)?;
     recipe_account.assert_signer_authority(authority)?;
@@ -46,7 +48,7 @@ pub(crate) fn delete_recipe<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResul
 
     // Check: programs.
     assert_same_pubkeys("system_program", system_program, &system_program::id())?;
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
     assert_same_pubkeys(
         "ata_program",
         ata_program,
@@ -65,6 +67,7 @@ pub(crate) fn delete_recipe<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResul
         experience_mint,
         experience_token,
         wisdom_feature_pda,
+        wisdom_feature_account.bump,
         payer,
     )?;
 
@@ -76,11 +79,12 @@ pub(crate) fn delete_recipe<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResul
         shards_mint,
         shards_token,
         fees_feature_pda,
+        fees_feature_account.bump,
         payer,
     )?;
 
     // Collect the lamports.
-    collect_fees(
+    let authority_fees = collect_fees(
         recipe_account.accumulated_admin_fees,
         &fees_feature_account.admin_destination,
         recipe,
@@ -88,6 +92,16 @@ pub(crate) fn delete_recipe<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResul
         admin_fees_destination,
     )?;
 
+    // Emit event.
+    TokenRecipesEvent::DeleteRecipe {
+        recipe: *recipe.key,
+        authority_fees,
+        admin_fees: recipe_account.accumulated_admin_fees,
+        shards: recipe_account.accumulated_shards,
+        experience: recipe_account.accumulated_experience,
+    }
+    .emit()?;
+
     // Delete the account.
     close_account(recipe, authority)
 }
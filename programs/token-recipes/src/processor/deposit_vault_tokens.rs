@@ -0,0 +1,77 @@
+use crate::{
+    assertions::{
+        assert_mint_account, assert_program_owner, assert_same_pubkeys, assert_signer,
+        assert_token_account, assert_token_account_or_create_ata, assert_token_program,
+        assert_writable,
+    },
+    state::recipe::Recipe,
+    utils::{next_transfer_additional_accounts, transfer_tokens},
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    system_program,
+};
+
+pub(crate) fn deposit_vault_tokens<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    amount: u64,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let mint = next_account_info(account_info_iter)?;
+    let token = next_account_info(account_info_iter)?;
+    let vault = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let ata_program = next_account_info(account_info_iter)?;
+
+    // Check: recipe and authority.
+    let recipe_account = Recipe::get(recipe)?;
+    recipe_account.assert_signer_authority(authority)?;
+
+    // Check: payer.
+    assert_signer("payer", payer)?;
+    assert_writable("payer", payer)?;
+
+    // Check: programs.
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+    assert_token_program("token_program", token_program)?;
+    assert_same_pubkeys(
+        "ata_program",
+        ata_program,
+        &spl_associated_token_account::id(),
+    )?;
+
+    // Check: mint.
+    let mint_account = assert_mint_account("mint", mint)?;
+    assert_program_owner("mint", mint, token_program.key)?;
+
+    // Check: token.
+    assert_writable("token", token)?;
+    let token_account = assert_token_account("token", token)?;
+    assert_same_pubkeys("mint", mint, &token_account.mint)?;
+    assert_same_pubkeys("authority", authority, &token_account.owner)?;
+
+    // Check: vault.
+    assert_token_account_or_create_ata("vault", vault, "mint", mint, "recipe", recipe, payer)?;
+    recipe_account.assert_vault(vault, recipe, mint)?;
+
+    // Check: Token-2022 extensions.
+    let additional_accounts = next_transfer_additional_accounts(account_info_iter, mint, vault)?;
+
+    // Deposit the tokens.
+    transfer_tokens(
+        mint,
+        authority,
+        token,
+        vault,
+        &additional_accounts,
+        amount,
+        mint_account.decimals,
+        None,
+    )
+}
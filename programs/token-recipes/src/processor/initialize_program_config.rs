@@ -0,0 +1,36 @@
+use crate::{
+    assertions::{assert_same_pubkeys, assert_signer, assert_upgrade_authority, assert_writable},
+    state::program_config::ProgramConfig,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    system_program,
+};
+
+pub(crate) fn initialize_program_config(accounts: &[AccountInfo]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let program_config = next_account_info(account_info_iter)?;
+    let admin = next_account_info(account_info_iter)?;
+    let upgrade_authority = next_account_info(account_info_iter)?;
+    let program_data = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check: admin.
+    // No check needed.
+
+    // Check: upgrade_authority and program_data.
+    assert_upgrade_authority(program_data, upgrade_authority)?;
+
+    // Check: payer.
+    assert_writable("payer", payer)?;
+    assert_signer("payer", payer)?;
+
+    // Check: system_program.
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+
+    // Create the program config account.
+    ProgramConfig::create(program_config, admin, payer, system_program)?.save(program_config)
+}
@@ -0,0 +1,82 @@
+use crate::{
+    assertions::{assert_program_owner, assert_same_pubkeys, assert_signer, assert_writable},
+    error::TokenRecipesError,
+    state::{
+        crafter_record::CrafterRecord, delegated_ingredient::DelegatedIngredient,
+        features::Feature, ingredient_record::IngredientRecord, key::Key, loot_roll::LootRoll,
+        program_config::ProgramConfig, recipe::Recipe,
+    },
+    utils::realloc_legacy_account,
+};
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg, system_program,
+};
+
+pub(crate) fn migrate_account<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check: account.
+    assert_writable("account", account)?;
+    assert_program_owner("account", account, &crate::id())?;
+    let key = Key::try_from_slice(
+        account
+            .try_borrow_data()?
+            .get(..Key::LEN)
+            .unwrap_or_default(),
+    )
+    .map_err(|_| TokenRecipesError::InvalidAccountKey)?;
+
+    // Check: payer.
+    assert_signer("payer", payer)?;
+    assert_writable("payer", payer)?;
+
+    // Check: system_program.
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+
+    // Load the account with the layout it was created with and save it with the current one.
+    match key {
+        Key::Uninitialized => {
+            msg!("Account [{}] is not initialized", account.key);
+            Err(TokenRecipesError::InvalidAccountKey.into())
+        }
+        // Legacy recipes grow by the fields appended to their header,
+        // which shifts their ingredients when the recipe is saved.
+        Key::Recipe => {
+            let recipe_account = Recipe::load(account)?;
+            realloc_legacy_account(account, payer, system_program, recipe_account.size())?;
+            recipe_account.save(account)
+        }
+        Key::IngredientRecord => {
+            let ingredient_record_account = IngredientRecord::load(account)?;
+            realloc_legacy_account(account, payer, system_program, IngredientRecord::LEN)?;
+            ingredient_record_account.save(account)
+        }
+        Key::DelegatedIngredient => {
+            let delegated_ingredient_account = DelegatedIngredient::load(account)?;
+            realloc_legacy_account(account, payer, system_program, DelegatedIngredient::LEN)?;
+            delegated_ingredient_account.save(account)
+        }
+        // Crafter records and program configs were introduced with their current layout.
+        Key::CrafterRecord => CrafterRecord::load(account)?.save(account),
+        Key::ProgramConfig => ProgramConfig::get(account)?.save(account),
+        // Loot rolls were introduced with their current layout.
+        Key::LootRoll => LootRoll::get(account)?.save(account),
+        Key::FeesFeature
+        | Key::AdditionalOutputsFeature
+        | Key::TransferInputsFeature
+        | Key::MaxSupplyFeature
+        | Key::SolPaymentFeature
+        | Key::WisdomFeature => {
+            let feature = Feature::load(account)?;
+            realloc_legacy_account(account, payer, system_program, feature.size())?;
+            feature.save(account)
+        }
+    }
+}
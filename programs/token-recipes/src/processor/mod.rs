@@ -1,28 +1,53 @@
 use crate::{
     instruction::TokenRecipesInstruction,
     processor::{
-        activate_recipe::activate_recipe, add_ingredient::add_ingredient,
+        accept_admin_authority::accept_admin_authority,
+        accept_recipe_authority::accept_recipe_authority, activate_recipe::activate_recipe,
+        add_ingredient::add_ingredient, add_loot_table_output::add_loot_table_output,
         admin_set_feature::admin_set_feature, collect_experience::collect_experience,
         collect_fees::collect_fees, craft::craft, create_recipe::create_recipe,
-        delete_recipe::delete_recipe, pause_recipe::pause_recipe,
-        remove_ingredient::remove_ingredient, set_fees::set_fees, unlock_feature::unlock_feature,
+        delete_recipe::delete_recipe, deposit_vault_tokens::deposit_vault_tokens,
+        initialize_program_config::initialize_program_config, migrate_account::migrate_account,
+        pause_recipe::pause_recipe, quote_craft::quote_craft, remove_ingredient::remove_ingredient,
+        remove_loot_table_output::remove_loot_table_output, reveal_loot::reveal_loot,
+        set_allowlist_root::set_allowlist_root, set_fees::set_fees,
+        set_max_crafts_per_wallet::set_max_crafts_per_wallet, set_time_window::set_time_window,
+        transfer_admin_authority::transfer_admin_authority,
+        transfer_recipe_authority::transfer_recipe_authority, unlock_feature::unlock_feature,
+        update_ingredient::update_ingredient, withdraw_vault_tokens::withdraw_vault_tokens,
     },
 };
 use borsh::BorshDeserialize;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
 
+pub mod accept_admin_authority;
+pub mod accept_recipe_authority;
 pub mod activate_recipe;
 pub mod add_ingredient;
+pub mod add_loot_table_output;
 pub mod admin_set_feature;
 pub mod collect_experience;
 pub mod collect_fees;
 pub mod craft;
 pub mod create_recipe;
 pub mod delete_recipe;
+pub mod deposit_vault_tokens;
+pub mod initialize_program_config;
+pub mod migrate_account;
 pub mod pause_recipe;
+pub mod quote_craft;
 pub mod remove_ingredient;
+pub mod remove_loot_table_output;
+pub mod reveal_loot;
+pub mod set_allowlist_root;
 pub mod set_fees;
+pub mod set_max_crafts_per_wallet;
+pub mod set_time_window;
+pub mod transfer_admin_authority;
+pub mod transfer_recipe_authority;
 pub mod unlock_feature;
+pub mod update_ingredient;
+pub mod withdraw_vault_tokens;
 
 pub struct Processor;
 impl Processor {
@@ -61,7 +86,7 @@ impl Processor {
             }
             TokenRecipesInstruction::Craft { quantity } => {
                 msg!("Instruction: Craft");
-                craft(accounts, quantity)
+                craft(accounts, quantity, vec![])
             }
             TokenRecipesInstruction::DeleteRecipe => {
                 msg!("Instruction: DeleteRecipe");
@@ -87,6 +112,92 @@ impl Processor {
                 msg!("Instruction: CollectExperience");
                 collect_experience(accounts)
             }
+            TokenRecipesInstruction::DepositVaultTokens { amount } => {
+                msg!("Instruction: DepositVaultTokens");
+                deposit_vault_tokens(accounts, amount)
+            }
+            TokenRecipesInstruction::WithdrawVaultTokens { amount } => {
+                msg!("Instruction: WithdrawVaultTokens");
+                withdraw_vault_tokens(accounts, amount)
+            }
+            TokenRecipesInstruction::AddLootTableOutput { rolls, entries } => {
+                msg!("Instruction: AddLootTableOutput");
+                add_loot_table_output(accounts, rolls, entries)
+            }
+            TokenRecipesInstruction::RemoveLootTableOutput { index } => {
+                msg!("Instruction: RemoveLootTableOutput");
+                remove_loot_table_output(accounts, index)
+            }
+            TokenRecipesInstruction::TransferRecipeAuthority => {
+                msg!("Instruction: TransferRecipeAuthority");
+                transfer_recipe_authority(accounts)
+            }
+            TokenRecipesInstruction::AcceptRecipeAuthority => {
+                msg!("Instruction: AcceptRecipeAuthority");
+                accept_recipe_authority(accounts)
+            }
+            TokenRecipesInstruction::UpdateIngredient {
+                ingredient_type,
+                new_ingredient_type,
+                amount,
+                destination,
+                max_supply,
+            } => {
+                msg!("Instruction: UpdateIngredient");
+                update_ingredient(
+                    accounts,
+                    ingredient_type,
+                    new_ingredient_type,
+                    amount,
+                    destination,
+                    max_supply,
+                )
+            }
+            TokenRecipesInstruction::SetMaxCraftsPerWallet {
+                max_crafts_per_wallet,
+            } => {
+                msg!("Instruction: SetMaxCraftsPerWallet");
+                set_max_crafts_per_wallet(accounts, max_crafts_per_wallet)
+            }
+            TokenRecipesInstruction::SetTimeWindow {
+                start_time,
+                end_time,
+            } => {
+                msg!("Instruction: SetTimeWindow");
+                set_time_window(accounts, start_time, end_time)
+            }
+            TokenRecipesInstruction::SetAllowlistRoot { root } => {
+                msg!("Instruction: SetAllowlistRoot");
+                set_allowlist_root(accounts, root)
+            }
+            TokenRecipesInstruction::QuoteCraft { quantity } => {
+                msg!("Instruction: QuoteCraft");
+                quote_craft(accounts, quantity)
+            }
+            TokenRecipesInstruction::MigrateAccount => {
+                msg!("Instruction: MigrateAccount");
+                migrate_account(accounts)
+            }
+            TokenRecipesInstruction::InitializeProgramConfig => {
+                msg!("Instruction: InitializeProgramConfig");
+                initialize_program_config(accounts)
+            }
+            TokenRecipesInstruction::TransferAdminAuthority => {
+                msg!("Instruction: TransferAdminAuthority");
+                transfer_admin_authority(accounts)
+            }
+            TokenRecipesInstruction::AcceptAdminAuthority => {
+                msg!("Instruction: AcceptAdminAuthority");
+                accept_admin_authority(accounts)
+            }
+            TokenRecipesInstruction::RevealLoot => {
+                msg!("Instruction: RevealLoot");
+                reveal_loot(accounts)
+            }
+            TokenRecipesInstruction::CraftWithProof { quantity, proof } => {
+                msg!("Instruction: CraftWithProof");
+                craft(accounts, quantity, proof)
+            }
         }
     }
 }
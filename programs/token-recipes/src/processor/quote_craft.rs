@@ -0,0 +1,109 @@
+use crate::{
+    error::TokenRecipesError,
+    state::{
+        craft_quote::CraftQuote,
+        crafter_record::CrafterRecord,
+        features::{
+            fees::{get_fees_and_shards_per_craft, FeesFeature},
+            wisdom::get_experience_per_craft,
+        },
+        ingredient_input::IngredientInput,
+        recipe::Recipe,
+    },
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::set_return_data,
+};
+
+pub(crate) fn quote_craft<'a>(accounts: &'a [AccountInfo<'a>], quantity: u64) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let crafter_record = next_account_info(account_info_iter)?;
+    let fees_feature_pda = next_account_info(account_info_iter)?;
+
+    // Check: recipe.
+    let recipe_account = Recipe::get(recipe)?;
+    recipe_account.assert_active()?;
+    let token_inputs = recipe_account
+        .inputs
+        .iter()
+        .filter(|input| {
+            matches!(
+                input,
+                IngredientInput::BurnToken { .. } | IngredientInput::TransferToken { .. }
+            )
+        })
+        .count();
+    if token_inputs > CraftQuote::MAX_TOKEN_COSTS {
+        msg!(
+            "Recipe has [{}] token inputs but a quote can only return [{}] token costs.",
+            token_inputs,
+            CraftQuote::MAX_TOKEN_COSTS
+        );
+        return Err(TokenRecipesError::CraftQuoteTooLarge.into());
+    }
+
+    // Check: crafter_record.
+    if recipe_account.max_crafts_per_wallet > 0 {
+        let mut crafter_record_account =
+            CrafterRecord::get_or_default(crafter_record, recipe, owner)?;
+        crafter_record_account.increment(quantity, recipe_account.max_crafts_per_wallet)?;
+    }
+
+    // Check: fees_feature_pda.
+    let fees_feature = FeesFeature::get_or_default(fees_feature_pda)?;
+
+    // Compute fees, shards and experience.
+    let (fees, admin_fees, shards) = get_fees_and_shards_per_craft(&recipe_account, &fees_feature)?;
+    let mut quote = CraftQuote {
+        quantity,
+        token_costs: vec![],
+        lamport_costs: 0,
+        fees,
+        admin_fees,
+        shards,
+        experience: get_experience_per_craft(&recipe_account, &fees_feature),
+    };
+
+    // Quote ingredient inputs.
+    recipe_account
+        .inputs
+        .iter()
+        .map(|input| input.quote(account_info_iter, owner, quantity, &mut quote))
+        .collect::<ProgramResult>()?;
+
+    // Quote ingredient outputs.
+    recipe_account
+        .outputs
+        .iter()
+        .map(|output| output.quote(account_info_iter, recipe, &recipe_account, quantity))
+        .collect::<ProgramResult>()?;
+
+    // Check: payer.
+    let total_lamports = quote
+        .total_lamports()
+        .ok_or(TokenRecipesError::NumericalOverflow)?;
+    if payer.lamports() < total_lamports {
+        msg!(
+            "Payer [{}] only holds [{}] lamports, [{}] are needed.",
+            payer.key,
+            payer.lamports(),
+            total_lamports
+        );
+        return Err(TokenRecipesError::NotEnoughLamports.into());
+    }
+
+    // Return the quote.
+    let data = quote
+        .try_to_vec()
+        .map_err(|_| TokenRecipesError::SerializationError)?;
+    set_return_data(&data);
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::{
-    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
+    events::TokenRecipesEvent,
     state::recipe::{Ingredient, IngredientType, Recipe},
 };
 use solana_program::{
@@ -24,14 +25,15 @@ pub(crate) fn remove_ingredient(
     let token_program = next_account_info(account_info_iter)?;
 
     // Check accounts.
+    Recipe::migrate(recipe, payer, system_program)?;
     let mut recipe_account = Recipe::get_writable(recipe)?;
     recipe_account.assert_signer_authority(authority)?;
     assert_writable("payer", payer)?;
     assert_signer("payer", payer)?;
     assert_same_pubkeys("system_program", system_program, &system_program::id())?;
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
 
-    let (ingredient, index) = recipe_account.find_ingredient(ingredient_type, mint)?;
+    let (ingredient, index) = recipe_account.find_ingredient(ingredient_type.clone(), mint)?;
     match ingredient {
         Ingredient::Input(input) => input.remove(
             &mut recipe_account,
@@ -49,9 +51,16 @@ pub(crate) fn remove_ingredient(
             mint,
             ingredient_record,
             delegated_ingredient,
-            authority,
             payer,
             system_program,
         ),
+    }?;
+
+    // Emit event.
+    TokenRecipesEvent::RemoveIngredient {
+        recipe: *recipe.key,
+        mint: *mint.key,
+        ingredient_type,
     }
+    .emit()
 }
@@ -0,0 +1,53 @@
+use crate::{
+    assertions::{assert_same_pubkeys, assert_signer, assert_token_program, assert_writable},
+    error::TokenRecipesError,
+    state::recipe::Recipe,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg, system_program,
+};
+
+pub(crate) fn remove_loot_table_output<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    index: u16,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    // Check accounts.
+    Recipe::migrate(recipe, payer, system_program)?;
+    let mut recipe_account = Recipe::get_writable(recipe)?;
+    recipe_account.assert_signer_authority(authority)?;
+    assert_writable("payer", payer)?;
+    assert_signer("payer", payer)?;
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+    assert_token_program("token_program", token_program)?;
+
+    // Remove the ingredient.
+    let index = index as usize;
+    let ingredient = match recipe_account.outputs.get(index) {
+        Some(ingredient) => ingredient.clone(),
+        None => {
+            msg!(
+                "Ingredient #{} is not part of this recipe as an output.",
+                index,
+            );
+            return Err(TokenRecipesError::MissingIngredient.into());
+        }
+    };
+    ingredient.remove_loot_table(
+        account_info_iter,
+        &mut recipe_account,
+        index,
+        recipe,
+        payer,
+        system_program,
+    )
+}
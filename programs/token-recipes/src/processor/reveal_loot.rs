@@ -0,0 +1,85 @@
+use crate::{
+    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    events::TokenRecipesEvent,
+    state::{ingredient_output::mint_loot_table_entries, loot_roll::LootRoll},
+    utils::close_account,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg, system_program,
+    sysvar::{slot_hashes, Sysvar},
+};
+
+pub(crate) fn reveal_loot<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let loot_roll = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let craft_payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let ata_program = next_account_info(account_info_iter)?;
+    let slot_hashes_sysvar = next_account_info(account_info_iter)?;
+
+    // Check: loot_roll.
+    let loot_roll_account = LootRoll::get(loot_roll)?;
+
+    // Check: owner.
+    assert_same_pubkeys("owner", owner, &loot_roll_account.owner)?;
+
+    // Check: payer.
+    assert_signer("payer", payer)?;
+    assert_writable("payer", payer)?;
+
+    // Check: craft_payer.
+    assert_writable("craft_payer", craft_payer)?;
+    assert_same_pubkeys("craft_payer", craft_payer, &loot_roll_account.payer)?;
+
+    // Check: programs and sysvars.
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+    assert_same_pubkeys(
+        "ata_program",
+        ata_program,
+        &spl_associated_token_account::id(),
+    )?;
+    assert_same_pubkeys("slot_hashes", slot_hashes_sysvar, &slot_hashes::id())?;
+
+    // Roll and mint the loot tables, unless the loot roll expired.
+    let seed = loot_roll_account.seed(
+        loot_roll.key,
+        &slot_hashes_sysvar.try_borrow_data()?,
+        Clock::get()?.slot,
+    )?;
+    let mut token_outputs = vec![];
+    match seed {
+        Some(seed) => {
+            let results = loot_roll_account.roll(&seed);
+            for (loot_table, results) in loot_roll_account.loot_tables.iter().zip(results) {
+                token_outputs.extend(mint_loot_table_entries(
+                    account_info_iter,
+                    &loot_table.entries,
+                    &results,
+                    owner,
+                    payer,
+                )?);
+            }
+        }
+        None => msg!(
+            "The hash of slot {} is no longer available, the loot is forfeited.",
+            loot_roll_account.slot
+        ),
+    }
+
+    // Close the loot roll.
+    close_account(loot_roll, craft_payer)?;
+
+    // Emit event.
+    TokenRecipesEvent::RevealLoot {
+        recipe: loot_roll_account.recipe,
+        owner: *owner.key,
+        token_outputs,
+    }
+    .emit()
+}
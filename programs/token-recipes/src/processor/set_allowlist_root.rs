@@ -0,0 +1,29 @@
+use crate::{
+    error::TokenRecipesError,
+    state::{recipe::RecipeStatus, recipe_header::RecipeHeader},
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+};
+
+pub(crate) fn set_allowlist_root(
+    accounts: &[AccountInfo],
+    root: Option<[u8; 32]>,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+
+    // Check: recipe.
+    let mut recipe_header = RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
+    if recipe_header.status()? != RecipeStatus::Paused {
+        return Err(TokenRecipesError::RecipeMustBePaused.into());
+    }
+
+    // Set the allowlist root.
+    recipe_header.allowlist_root = root.unwrap_or([0; 32]);
+    Ok(())
+}
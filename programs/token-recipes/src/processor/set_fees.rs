@@ -1,4 +1,4 @@
-use crate::state::{features::fees::asserts_can_set_fees, recipe::Recipe};
+use crate::state::{features::fees::asserts_can_set_fees, recipe_header::RecipeHeader};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -11,11 +11,11 @@ pub(crate) fn set_fees(accounts: &[AccountInfo], fees: u64) -> ProgramResult {
     let authority = next_account_info(account_info_iter)?;
 
     // Check: recipe.
-    let mut recipe_account = Recipe::get_writable(recipe)?;
-    recipe_account.assert_signer_authority(authority)?;
+    let mut recipe_header = RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
 
     // Set the fees.
-    asserts_can_set_fees(&recipe_account)?;
-    recipe_account.fees = fees;
-    recipe_account.save(recipe)
+    asserts_can_set_fees(&recipe_header)?;
+    recipe_header.fees = fees.into();
+    Ok(())
 }
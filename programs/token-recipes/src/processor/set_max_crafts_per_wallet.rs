@@ -0,0 +1,23 @@
+use crate::state::recipe_header::RecipeHeader;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+};
+
+pub(crate) fn set_max_crafts_per_wallet(
+    accounts: &[AccountInfo],
+    max_crafts_per_wallet: u64,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+
+    // Check: recipe.
+    let mut recipe_header = RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
+
+    // Set the limit.
+    recipe_header.max_crafts_per_wallet = max_crafts_per_wallet.into();
+    Ok(())
+}
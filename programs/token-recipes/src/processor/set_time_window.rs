@@ -0,0 +1,42 @@
+use crate::{error::TokenRecipesError, state::recipe_header::RecipeHeader};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+};
+
+pub(crate) fn set_time_window(
+    accounts: &[AccountInfo],
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+
+    // Check: recipe.
+    let mut recipe_header = RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
+
+    // Check: time window.
+    if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+        if start_time >= end_time {
+            msg!(
+                "Start time {} must be before end time {}",
+                start_time,
+                end_time
+            );
+            return Err(TokenRecipesError::InvalidTimeWindow.into());
+        }
+    }
+    if matches!(start_time, Some(t) if t <= 0) || matches!(end_time, Some(t) if t <= 0) {
+        msg!("Timestamps must be positive, use None to remove a limit");
+        return Err(TokenRecipesError::InvalidTimeWindow.into());
+    }
+
+    // Set the time window.
+    recipe_header.start_time = start_time.unwrap_or(0).into();
+    recipe_header.end_time = end_time.unwrap_or(0).into();
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::state::program_config::ProgramConfig;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+pub(crate) fn transfer_admin_authority(accounts: &[AccountInfo]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let program_config = next_account_info(account_info_iter)?;
+    let admin = next_account_info(account_info_iter)?;
+    let new_admin = next_account_info(account_info_iter)?;
+
+    // Check: program_config.
+    let mut program_config_account = ProgramConfig::get_writable(program_config)?;
+    program_config_account.assert_signer_admin(admin)?;
+
+    // Check: new_admin.
+    // No check needed.
+
+    // Set the pending admin, or cancel the pending transfer
+    // if the new admin is the current admin.
+    program_config_account.pending_admin = match new_admin.key == admin.key {
+        true => Pubkey::default(),
+        false => *new_admin.key,
+    };
+    program_config_account.save(program_config)
+}
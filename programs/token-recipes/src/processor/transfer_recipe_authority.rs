@@ -0,0 +1,29 @@
+use crate::state::recipe_header::RecipeHeader;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+pub(crate) fn transfer_recipe_authority(accounts: &[AccountInfo]) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let new_authority = next_account_info(account_info_iter)?;
+
+    // Check: recipe.
+    let mut recipe_header = RecipeHeader::get_writable(recipe)?;
+    recipe_header.assert_signer_authority(authority)?;
+
+    // Check: new_authority.
+    // No check needed.
+
+    // Set the pending authority, or cancel the pending transfer
+    // if the new authority is the current authority.
+    recipe_header.pending_authority = match new_authority.key == authority.key {
+        true => Pubkey::default(),
+        false => *new_authority.key,
+    };
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use crate::{
     assertions::{
         assert_enough_tokens, assert_mint_account, assert_program_owner, assert_same_pubkeys,
-        assert_signer, assert_token_account, assert_writable,
+        assert_signer, assert_token_account, assert_token_program, assert_writable,
     },
+    events::TokenRecipesEvent,
     state::{
         features::{Feature, UnlockFeatureContext},
-        recipe::Recipe,
+        recipe_header::RecipeHeader,
     },
 };
 use solana_program::{
@@ -25,8 +26,7 @@ pub(crate) fn unlock_feature<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResu
     let token_program = next_account_info(account_info_iter)?;
 
     // Check: recipe and authority.
-    let recipe_account = Recipe::get_writable(recipe)?;
-    recipe_account.assert_signer_authority(authority)?;
+    RecipeHeader::get_writable(recipe)?.assert_signer_authority(authority)?;
 
     // Check: feature_pda.
     assert_program_owner("feature_pda", feature_pda, &crate::id())?;
@@ -43,12 +43,22 @@ pub(crate) fn unlock_feature<'a>(accounts: &'a [AccountInfo<'a>]) -> ProgramResu
     assert_enough_tokens("token", token, token_account, 1)?;
 
     // Check: token_program.
-    assert_same_pubkeys("token_program", token_program, &spl_token::id())?;
+    assert_token_program("token_program", token_program)?;
+    assert_program_owner("mint", mint, token_program.key)?;
 
     feature_account.unlock(&UnlockFeatureContext {
         recipe,
         owner,
         mint,
         token,
-    })
+    })?;
+
+    // Emit event.
+    TokenRecipesEvent::UnlockFeature {
+        recipe: *recipe.key,
+        feature: feature_account.key(),
+        mint: *mint.key,
+        feature_levels: RecipeHeader::load(recipe)?.feature_levels,
+    }
+    .emit()
 }
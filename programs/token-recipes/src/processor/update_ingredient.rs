@@ -0,0 +1,174 @@
+use crate::{
+    assertions::{assert_same_pubkeys, assert_signer, assert_writable},
+    error::TokenRecipesError,
+    state::{
+        features::{
+            additional_outputs::assert_valid_additional_outputs,
+            max_supply::assert_valid_max_supply_outputs,
+            sol_payment::assert_valid_sol_payment_inputs,
+            transfer_inputs::assert_valid_transfer_inputs,
+        },
+        ingredient_input::IngredientInput,
+        ingredient_output::IngredientOutput,
+        recipe::{Ingredient, IngredientType, Recipe},
+    },
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    system_program,
+};
+
+pub(crate) fn update_ingredient(
+    accounts: &[AccountInfo],
+    ingredient_type: IngredientType,
+    new_ingredient_type: Option<IngredientType>,
+    amount: Option<u64>,
+    destination: Option<Pubkey>,
+    max_supply: Option<u64>,
+) -> ProgramResult {
+    // Accounts.
+    let account_info_iter = &mut accounts.iter();
+    let recipe = next_account_info(account_info_iter)?;
+    let mint = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check accounts.
+    Recipe::migrate(recipe, payer, system_program)?;
+    let mut recipe_account = Recipe::get_writable(recipe)?;
+    recipe_account.assert_signer_authority(authority)?;
+    assert_writable("payer", payer)?;
+    assert_signer("payer", payer)?;
+    assert_same_pubkeys("system_program", system_program, &system_program::id())?;
+
+    // Check: amount
+    if amount == Some(0) {
+        return Err(TokenRecipesError::CannotAddIngredientWithZeroAmount.into());
+    }
+
+    // Find the ingredient to update.
+    let (ingredient, index) = recipe_account.find_ingredient(ingredient_type.clone(), mint)?;
+    let new_ingredient_type = new_ingredient_type.unwrap_or(ingredient_type);
+
+    // Get the updated ingredient. Only ingredients sharing the same
+    // ingredient record and delegated ingredient can be swapped.
+    let updated_ingredient: Ingredient = match (ingredient, new_ingredient_type) {
+        (
+            Ingredient::Input(
+                IngredientInput::BurnToken { mint, amount: a }
+                | IngredientInput::TransferToken {
+                    mint, amount: a, ..
+                },
+            ),
+            IngredientType::BurnTokenInput,
+        ) => Ingredient::Input(IngredientInput::BurnToken {
+            mint,
+            amount: amount.unwrap_or(a),
+        }),
+        (
+            Ingredient::Input(IngredientInput::BurnToken { mint, amount: a }),
+            IngredientType::TransferTokenInput,
+        ) => Ingredient::Input(IngredientInput::TransferToken {
+            mint,
+            amount: amount.unwrap_or(a),
+            destination: destination.ok_or(TokenRecipesError::MissingDestinationArgument)?,
+        }),
+        (
+            Ingredient::Input(IngredientInput::TransferToken {
+                mint,
+                amount: a,
+                destination: d,
+            }),
+            IngredientType::TransferTokenInput,
+        ) => Ingredient::Input(IngredientInput::TransferToken {
+            mint,
+            amount: amount.unwrap_or(a),
+            destination: destination.unwrap_or(d),
+        }),
+        (
+            Ingredient::Input(IngredientInput::TransferSol {
+                lamports,
+                destination: d,
+            }),
+            IngredientType::TransferSolInput,
+        ) => Ingredient::Input(IngredientInput::TransferSol {
+            lamports: amount.unwrap_or(lamports),
+            destination: destination.unwrap_or(d),
+        }),
+        (
+            Ingredient::Input(IngredientInput::HoldToken { mint, amount: a }),
+            IngredientType::HoldTokenInput,
+        ) => Ingredient::Input(IngredientInput::HoldToken {
+            mint,
+            amount: amount.unwrap_or(a),
+        }),
+        (
+            Ingredient::Output(
+                IngredientOutput::MintToken { mint, amount: a }
+                | IngredientOutput::MintTokenWithMaxSupply {
+                    mint, amount: a, ..
+                },
+            ),
+            IngredientType::MintTokenOutput,
+        ) => Ingredient::Output(IngredientOutput::MintToken {
+            mint,
+            amount: amount.unwrap_or(a),
+        }),
+        (
+            Ingredient::Output(IngredientOutput::MintToken { mint, amount: a }),
+            IngredientType::MintTokenWithMaxSupplyOutput,
+        ) => Ingredient::Output(IngredientOutput::MintTokenWithMaxSupply {
+            mint,
+            amount: amount.unwrap_or(a),
+            max_supply: max_supply.ok_or(TokenRecipesError::MissingMaxSupplyArgument)?,
+        }),
+        (
+            Ingredient::Output(IngredientOutput::MintTokenWithMaxSupply {
+                mint,
+                amount: a,
+                max_supply: m,
+            }),
+            IngredientType::MintTokenWithMaxSupplyOutput,
+        ) => Ingredient::Output(IngredientOutput::MintTokenWithMaxSupply {
+            mint,
+            amount: amount.unwrap_or(a),
+            max_supply: max_supply.unwrap_or(m),
+        }),
+        (
+            Ingredient::Output(IngredientOutput::TransferToken { mint, amount: a }),
+            IngredientType::TransferTokenOutput,
+        ) => Ingredient::Output(IngredientOutput::TransferToken {
+            mint,
+            amount: amount.unwrap_or(a),
+        }),
+        (_, new_ingredient_type) => {
+            msg!(
+                "Ingredient cannot be updated to a {:?} ingredient. Remove it and add it again instead.",
+                new_ingredient_type
+            );
+            return Err(TokenRecipesError::InvalidIngredientUpdate.into());
+        }
+    };
+
+    // Update the ingredient.
+    match updated_ingredient {
+        Ingredient::Input(input) => {
+            recipe_account.update_ingredient_input(index, &input, recipe, payer, system_program)
+        }
+        Ingredient::Output(output) => {
+            recipe_account.update_ingredient_output(index, &output, recipe, payer, system_program)
+        }
+    }?;
+
+    // Check feature invariants.
+    assert_valid_transfer_inputs(&recipe_account)?;
+    assert_valid_sol_payment_inputs(&recipe_account)?;
+    assert_valid_additional_outputs(&recipe_account)?;
+    assert_valid_max_supply_outputs(&recipe_account)?;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program::MAX_RETURN_DATA, pubkey::Pubkey};
+
+/// The costs and rewards of crafting a recipe, returned by the QuoteCraft instruction.
+/// Storage fees of the token accounts created when crafting are not included.
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct CraftQuote {
+    /// The amount of recipes to craft.
+    pub quantity: u64,
+    /// The tokens burned or transferred by the input ingredients, per mint.
+    pub token_costs: Vec<TokenAmount>,
+    /// The lamports transferred by the TransferSol input ingredient.
+    pub lamport_costs: u64,
+    /// The fees paid to the recipe, including the admin fees.
+    pub fees: u64,
+    /// The part of the fees going to the program admin.
+    pub admin_fees: u64,
+    /// The shards accumulated on the recipe.
+    pub shards: u64,
+    /// The experience accumulated on the recipe.
+    pub experience: u64,
+}
+
+impl CraftQuote {
+    pub const BASE_LEN: usize = 8 + 4 + 8 + 8 + 8 + 8 + 8;
+    /// The maximum number of token costs of a quote that fits in the return data of a transaction.
+    pub const MAX_TOKEN_COSTS: usize = (MAX_RETURN_DATA - Self::BASE_LEN) / TokenAmount::LEN;
+
+    /// The total lamports paid by the payer.
+    pub fn total_lamports(&self) -> Option<u64> {
+        self.lamport_costs.checked_add(self.fees)
+    }
+
+    /// Deserializes a quote from the return data of a simulated QuoteCraft instruction.
+    /// The runtime trims trailing zero bytes from return data so the data is zero-extended
+    /// to the maximum size of return data first, which covers any number of trimmed bytes.
+    pub fn from_return_data(data: &[u8]) -> std::io::Result<Self> {
+        let mut bytes = data.to_vec();
+        bytes.resize(data.len().max(MAX_RETURN_DATA), 0);
+        Self::deserialize(&mut bytes.as_slice())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct TokenAmount {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl TokenAmount {
+    pub const LEN: usize = 32 + 8;
+}
@@ -0,0 +1,168 @@
+use crate::{
+    assertions::{
+        assert_account_key, assert_empty, assert_pda, assert_program_owner, assert_same_pubkeys,
+        assert_writable,
+    },
+    error::TokenRecipesError,
+    state::key::Key,
+    utils::{create_account, write_account_data},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+pub struct CrafterRecord {
+    pub key: Key,
+    pub recipe: Pubkey,
+    pub owner: Pubkey,
+    pub total_crafts_with_quantity: u64,
+    pub version: u8,
+}
+
+impl CrafterRecord {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 1;
+    pub const VERSION: u8 = 1;
+
+    /// Add the given quantity to the crafts of the owner and
+    /// assert that it does not exceed the limit of the recipe.
+    pub fn increment(&mut self, quantity: u64, max_crafts_per_wallet: u64) -> ProgramResult {
+        let total_crafts_with_quantity = self
+            .total_crafts_with_quantity
+            .checked_add(quantity)
+            .ok_or(TokenRecipesError::NumericalOverflow)?;
+        if total_crafts_with_quantity > max_crafts_per_wallet {
+            msg!(
+                "Owner [{}] can only craft this recipe {} times and already crafted it {} times.",
+                self.owner,
+                max_crafts_per_wallet,
+                self.total_crafts_with_quantity,
+            );
+            return Err(TokenRecipesError::CraftLimitReached.into());
+        }
+        self.total_crafts_with_quantity = total_crafts_with_quantity;
+        Ok(())
+    }
+
+    pub fn seeds<'a>(recipe: &'a Pubkey, owner: &'a Pubkey) -> Vec<&'a [u8]> {
+        vec!["crafter_record".as_bytes(), recipe.as_ref(), owner.as_ref()]
+    }
+
+    pub fn create<'a>(
+        crafter_record: &AccountInfo<'a>,
+        recipe: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        assert_empty("crafter_record", crafter_record)?;
+        assert_writable("crafter_record", crafter_record)?;
+        let crafter_record_bump = assert_pda(
+            "crafter_record",
+            crafter_record,
+            &crate::id(),
+            &Self::seeds(recipe.key, owner.key),
+        )?;
+
+        let mut seeds = Self::seeds(recipe.key, owner.key);
+        let bump = [crafter_record_bump];
+        seeds.push(&bump);
+        create_account(
+            crafter_record,
+            payer,
+            system_program,
+            Self::LEN,
+            &crate::id(),
+            Some(&[&seeds]),
+        )?;
+        Ok(Self {
+            key: Key::CrafterRecord,
+            recipe: *recipe.key,
+            owner: *owner.key,
+            total_crafts_with_quantity: 0,
+            version: Self::VERSION,
+        })
+    }
+
+    pub fn get<'a>(
+        crafter_record: &AccountInfo<'a>,
+        recipe: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        assert_writable("crafter_record", crafter_record)?;
+        assert_program_owner("crafter_record", crafter_record, &crate::id())?;
+        assert_account_key("crafter_record", crafter_record, Key::CrafterRecord)?;
+        assert_pda(
+            "crafter_record",
+            crafter_record,
+            &crate::id(),
+            &Self::seeds(recipe.key, owner.key),
+        )?;
+        let crafter_record_account = Self::load(crafter_record)?;
+        assert_same_pubkeys("recipe", recipe, &crafter_record_account.recipe)?;
+        assert_same_pubkeys("owner", owner, &crafter_record_account.owner)?;
+        Ok(crafter_record_account)
+    }
+
+    /// Get the crafter record of the owner without requiring it to be writable,
+    /// or an empty record if the owner has never crafted the recipe.
+    pub fn get_or_default<'a>(
+        crafter_record: &AccountInfo<'a>,
+        recipe: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        assert_pda(
+            "crafter_record",
+            crafter_record,
+            &crate::id(),
+            &Self::seeds(recipe.key, owner.key),
+        )?;
+        if crafter_record.data_is_empty() {
+            return Ok(Self {
+                key: Key::CrafterRecord,
+                recipe: *recipe.key,
+                owner: *owner.key,
+                total_crafts_with_quantity: 0,
+                version: Self::VERSION,
+            });
+        }
+        assert_program_owner("crafter_record", crafter_record, &crate::id())?;
+        assert_account_key("crafter_record", crafter_record, Key::CrafterRecord)?;
+        Self::load(crafter_record)
+    }
+
+    pub fn get_or_create<'a>(
+        crafter_record: &AccountInfo<'a>,
+        recipe: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        match crafter_record.data_is_empty() {
+            true => Self::create(crafter_record, recipe, owner, payer, system_program),
+            false => Self::get(crafter_record, recipe, owner),
+        }
+    }
+
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        let mut bytes: &[u8] = &(*account.data).borrow();
+        Self::deserialize(&mut bytes).map_err(|error| {
+            msg!("Error deserializing CrafterRecord account: {}", error);
+            TokenRecipesError::DeserializationError.into()
+        })
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        let mut bytes = Vec::with_capacity(account.data_len());
+        self.serialize(&mut bytes).map_err(|error| {
+            msg!("Error serializing CrafterRecord account: {}", error);
+            TokenRecipesError::SerializationError
+        })?;
+        write_account_data(account, &bytes);
+        Ok(())
+    }
+}
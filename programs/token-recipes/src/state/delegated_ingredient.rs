@@ -1,19 +1,21 @@
 use crate::{
     assertions::{
-        assert_account_key, assert_empty, assert_mint_authority, assert_pda, assert_program_owner,
-        assert_same_pubkeys, assert_writable,
+        assert_account_key, assert_empty, assert_mint_account, assert_mint_authority, assert_pda,
+        assert_pda_with_bump, assert_program_owner, assert_same_pubkeys, assert_writable,
     },
     error::TokenRecipesError,
     state::key::Key,
-    utils::{close_account, create_account, transfer_mint_authority},
+    utils::{
+        close_account, create_account, deserialize_account, realloc_legacy_account,
+        transfer_mint_authority, write_account_data,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey,
+    pubkey::Pubkey,
 };
-use spl_token::state::Mint;
 
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
@@ -22,10 +24,15 @@ pub struct DelegatedIngredient {
     pub mint: Pubkey,
     pub authority: Pubkey,
     pub counter: u32,
+    pub bump: u8,
+    pub version: u8,
 }
 
 impl DelegatedIngredient {
-    pub const LEN: usize = 1 + 32 + 32 + 4;
+    pub const LEN: usize = 1 + 32 + 32 + 4 + 1 + 1;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 32 + 32 + 4;
+    pub const VERSION: u8 = 1;
 
     pub fn should_be_closed(&mut self) -> bool {
         self.counter == 0
@@ -44,7 +51,7 @@ impl DelegatedIngredient {
     ) -> Result<Self, ProgramError> {
         assert_empty("delegated_ingredient", delegated_ingredient)?;
         assert_writable("delegated_ingredient", delegated_ingredient)?;
-        assert_pda(
+        let delegated_ingredient_bump = assert_pda(
             "delegated_ingredient",
             delegated_ingredient,
             &crate::id(),
@@ -52,12 +59,11 @@ impl DelegatedIngredient {
         )?;
 
         // Check: mint authority.
-        let mint_account = Mint::unpack(&mint.data.borrow())?;
+        let mint_account = assert_mint_account("mint", mint)?;
         assert_mint_authority("mint", mint, &mint_account, authority.key)?;
 
         let mut seeds = Self::seeds(mint.key);
-        let (_, bump) = Pubkey::find_program_address(&seeds, &crate::id());
-        let bump = [bump];
+        let bump = [delegated_ingredient_bump];
         seeds.push(&bump);
         create_account(
             delegated_ingredient,
@@ -67,12 +73,14 @@ impl DelegatedIngredient {
             &crate::id(),
             Some(&[&seeds]),
         )?;
-        transfer_mint_authority(mint, authority, delegated_ingredient, None)?;
+        transfer_mint_authority(mint, authority, delegated_ingredient.key, None)?;
         Ok(Self {
             key: Key::DelegatedIngredient,
             mint: *mint.key,
             authority: *authority.key,
             counter: 0,
+            bump: delegated_ingredient_bump,
+            version: Self::VERSION,
         })
     }
 
@@ -81,20 +89,7 @@ impl DelegatedIngredient {
         mint: &AccountInfo<'a>,
         authority: &AccountInfo<'a>,
     ) -> Result<Self, ProgramError> {
-        assert_writable("delegated_ingredient", delegated_ingredient)?;
-        assert_pda(
-            "delegated_ingredient",
-            delegated_ingredient,
-            &crate::id(),
-            &Self::seeds(mint.key),
-        )?;
-        assert_program_owner("delegated_ingredient", delegated_ingredient, &crate::id())?;
-        assert_account_key(
-            "delegated_ingredient",
-            delegated_ingredient,
-            Key::DelegatedIngredient,
-        )?;
-        let delegated_ingredient_account = Self::load(delegated_ingredient)?;
+        let delegated_ingredient_account = Self::get_for_mint(delegated_ingredient, mint.key)?;
         assert_same_pubkeys("mint", mint, &delegated_ingredient_account.mint)?;
         assert_same_pubkeys(
             "authority",
@@ -113,24 +108,34 @@ impl DelegatedIngredient {
     ) -> Result<Self, ProgramError> {
         match delegated_ingredient.data_is_empty() {
             true => Self::create(delegated_ingredient, mint, authority, payer, system_program),
-            false => Self::get(delegated_ingredient, mint, authority),
+            false => {
+                let delegated_ingredient_account =
+                    Self::get(delegated_ingredient, mint, authority)?;
+                realloc_legacy_account(delegated_ingredient, payer, system_program, Self::LEN)?;
+                Ok(delegated_ingredient_account)
+            }
         }
     }
 
+    /// Save the delegated ingredient or, if no recipe uses it anymore, close it
+    /// and transfer the mint authority back to the authority that delegated it.
     pub fn save_or_close<'a>(
         &mut self,
         delegated_ingredient: &AccountInfo<'a>,
         mint: &AccountInfo<'a>,
-        authority: &AccountInfo<'a>,
         payer: &AccountInfo<'a>,
     ) -> ProgramResult {
         match self.should_be_closed() {
             true => {
                 let mut seeds = Self::seeds(mint.key);
-                let (_, bump) = Pubkey::find_program_address(&seeds, &crate::id());
-                let bump = [bump];
+                let bump = [self.bump];
                 seeds.push(&bump);
-                transfer_mint_authority(mint, delegated_ingredient, authority, Some(&[&seeds]))?;
+                transfer_mint_authority(
+                    mint,
+                    delegated_ingredient,
+                    &self.authority,
+                    Some(&[&seeds]),
+                )?;
                 close_account(delegated_ingredient, payer)
             }
             false => self.save(delegated_ingredient),
@@ -150,23 +155,90 @@ impl DelegatedIngredient {
         delegated_ingredient_account.save(delegated_ingredient)
     }
 
+    /// Decrement the recipes using the delegated ingredient. Its authority may differ from the
+    /// authority of the recipe when the recipe was transferred while other recipes used it.
     pub fn close_or_decrement<'a>(
         delegated_ingredient: &AccountInfo<'a>,
         mint: &AccountInfo<'a>,
-        authority: &AccountInfo<'a>,
         payer: &AccountInfo<'a>,
     ) -> ProgramResult {
-        let mut delegated_ingredient_account = Self::get(delegated_ingredient, mint, authority)?;
+        let mut delegated_ingredient_account = Self::get_for_mint(delegated_ingredient, mint.key)?;
         delegated_ingredient_account.counter -= 1;
-        delegated_ingredient_account.save_or_close(delegated_ingredient, mint, authority, payer)
+        delegated_ingredient_account.save_or_close(delegated_ingredient, mint, payer)
+    }
+
+    /// Hand the delegated ingredient over to the new authority of the recipe that uses it.
+    /// Delegated ingredients also used by other recipes, which all belong to the current
+    /// authority, stay with the current authority. It gets the mint authority back once
+    /// every recipe removed the mint from its outputs, including the transferred recipe.
+    pub fn migrate_authority(
+        delegated_ingredient: &AccountInfo,
+        mint: &Pubkey,
+        authority: &Pubkey,
+        new_authority: &Pubkey,
+        uses: u32,
+    ) -> ProgramResult {
+        let mut delegated_ingredient_account = Self::get_for_mint(delegated_ingredient, mint)?;
+        if delegated_ingredient_account.authority != *authority {
+            msg!(
+                "Delegated ingredient [{}] must be owned by the current authority [{}]",
+                delegated_ingredient.key,
+                authority
+            );
+            return Err(TokenRecipesError::AccountMismatch.into());
+        }
+        if delegated_ingredient_account.counter > uses {
+            msg!(
+                "Mint [{}] is also used as an output by other recipes, its delegated ingredient stays with [{}].",
+                mint,
+                authority
+            );
+            return Ok(());
+        }
+        delegated_ingredient_account.authority = *new_authority;
+        delegated_ingredient_account.save(delegated_ingredient)
+    }
+
+    fn get_for_mint(
+        delegated_ingredient: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        assert_writable("delegated_ingredient", delegated_ingredient)?;
+        assert_program_owner("delegated_ingredient", delegated_ingredient, &crate::id())?;
+        assert_account_key(
+            "delegated_ingredient",
+            delegated_ingredient,
+            Key::DelegatedIngredient,
+        )?;
+        let delegated_ingredient_account = Self::load(delegated_ingredient)?;
+        assert_pda_with_bump(
+            "delegated_ingredient",
+            delegated_ingredient,
+            &crate::id(),
+            &Self::seeds(mint),
+            delegated_ingredient_account.bump,
+        )?;
+        Ok(delegated_ingredient_account)
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        DelegatedIngredient::deserialize(&mut bytes).map_err(|error| {
-            msg!("Error deserializing DelegatedIngredient account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut delegated_ingredient: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!("Error deserializing DelegatedIngredient account: {}", error);
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if delegated_ingredient.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if delegated_ingredient.bump == 0 {
+                let seeds = Self::seeds(&delegated_ingredient.mint);
+                delegated_ingredient.bump = Pubkey::find_program_address(&seeds, &crate::id()).1;
+            }
+            delegated_ingredient.version = Self::VERSION;
+        }
+        Ok(delegated_ingredient)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -175,7 +247,7 @@ impl DelegatedIngredient {
             msg!("Error serializing DelegatedIngredient account: {}", error);
             TokenRecipesError::SerializationError
         })?;
-        account.try_borrow_mut_data().unwrap()[..bytes.len()].copy_from_slice(&bytes);
+        write_account_data(account, &bytes);
         Ok(())
     }
 }
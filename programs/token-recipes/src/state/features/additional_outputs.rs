@@ -1,8 +1,11 @@
 use crate::{
     assertions::assert_mint_account,
     error::TokenRecipesError,
-    state::{features::UnlockFeatureContext, key::Key, recipe::Recipe},
-    utils::burn_tokens,
+    state::{
+        features::UnlockFeatureContext, ingredient_output::IngredientOutput, key::Key,
+        recipe::Recipe, recipe_header::RecipeHeader,
+    },
+    utils::{burn_tokens, deserialize_account},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
@@ -17,6 +20,8 @@ use solana_program::{
 /// - Level 1: 2 output ingredients allowed.
 /// - Level 2: 3 output ingredients allowed.
 /// - Level 3: Unlimited output ingredients allowed.
+///
+/// Every entry of a loot table counts as an output ingredient.
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
 pub struct AdditionalOutputsFeature {
@@ -32,38 +37,45 @@ pub struct AdditionalOutputsFeature {
     pub mint_skill_1: Pubkey,
     /// Without burning, allows leveling up straight to 3.
     pub mint_skill_2: Pubkey,
+    /// The bump of the feature PDA.
+    pub bump: u8,
+    /// The version of the account layout.
+    pub version: u8,
 }
 
 impl AdditionalOutputsFeature {
-    pub const LEN: usize = 1 + 32 * 5;
+    pub const LEN: usize = 1 + 32 * 5 + 1 + 1;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 32 * 5;
+    pub const VERSION: u8 = 1;
     pub const MAX_LEVEL: u8 = 3;
 
     pub fn unlock(&self, context: &UnlockFeatureContext) -> ProgramResult {
-        let mut recipe_account = Recipe::get_writable(context.recipe)?;
-        let level = recipe_account.feature_levels.additional_outputs;
+        let mut recipe_header = RecipeHeader::get_writable(context.recipe)?;
+        let level = recipe_header.feature_levels.additional_outputs;
         if level >= Self::MAX_LEVEL {
             return Err(TokenRecipesError::MaxFeatureLevelReached.into());
         }
 
         let result: Result<u64, ProgramError> = match context.mint.key {
             x if *x == self.mint_burn_1 && level < 2 => {
-                recipe_account.feature_levels.additional_outputs += 1;
+                recipe_header.feature_levels.additional_outputs += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_2 && level < 3 => {
-                recipe_account.feature_levels.additional_outputs += 1;
+                recipe_header.feature_levels.additional_outputs += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_3 && level < 3 => {
-                recipe_account.feature_levels.additional_outputs = 3;
+                recipe_header.feature_levels.additional_outputs = 3;
                 Ok(1)
             }
             x if *x == self.mint_skill_1 && level < 2 => {
-                recipe_account.feature_levels.additional_outputs = 2;
+                recipe_header.feature_levels.additional_outputs = 2;
                 Ok(0)
             }
             x if *x == self.mint_skill_2 && level < 3 => {
-                recipe_account.feature_levels.additional_outputs = 3;
+                recipe_header.feature_levels.additional_outputs = 3;
                 Ok(0)
             }
             _ => Err(TokenRecipesError::InvalidMintToLevelUpFeature.into()),
@@ -81,7 +93,7 @@ impl AdditionalOutputsFeature {
             )?;
         }
 
-        recipe_account.save(context.recipe)
+        Ok(())
     }
 
     pub fn seeds<'a>() -> Vec<&'a [u8]> {
@@ -89,14 +101,25 @@ impl AdditionalOutputsFeature {
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        AdditionalOutputsFeature::deserialize(&mut bytes).map_err(|error| {
-            msg!(
-                "Error deserializing AdditionalOutputsFeature account: {}",
-                error
-            );
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut feature: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!(
+                        "Error deserializing AdditionalOutputsFeature account: {}",
+                        error
+                    );
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if feature.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if feature.bump == 0 {
+                feature.bump = Pubkey::find_program_address(&Self::seeds(), &crate::id()).1;
+            }
+            feature.version = Self::VERSION;
+        }
+        Ok(feature)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -114,9 +137,10 @@ impl AdditionalOutputsFeature {
 }
 
 /// Asserts that the recipe has a valid number of additional outputs.
+/// Every entry of a loot table counts as an output since it rewards its own mint.
 /// Make sure to use AFTER the recipe was updated.
 pub fn assert_valid_additional_outputs(recipe: &Recipe) -> ProgramResult {
-    let total_outputs = recipe.outputs.len();
+    let total_outputs = recipe.outputs.iter().map(IngredientOutput::mints_len).sum();
     match recipe.feature_levels.additional_outputs {
         0 => assert_max_outputs(total_outputs, 1),
         1 => assert_max_outputs(total_outputs, 2),
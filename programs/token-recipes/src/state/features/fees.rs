@@ -1,11 +1,14 @@
 use crate::{
     assertions::{
-        assert_account_key, assert_mint_account, assert_pda, assert_program_owner,
-        assert_same_pubkeys, assert_token_account_or_create_ata, assert_writable,
+        assert_account_key, assert_mint_account, assert_pda, assert_pda_with_bump,
+        assert_program_owner, assert_same_pubkeys, assert_token_account_or_create_ata,
+        assert_writable,
     },
     error::TokenRecipesError,
-    state::{features::UnlockFeatureContext, key::Key, recipe::Recipe},
-    utils::{burn_tokens, mint_tokens, transfer_lamports_from_pdas},
+    state::{
+        features::UnlockFeatureContext, key::Key, recipe::Recipe, recipe_header::RecipeHeader,
+    },
+    utils::{burn_tokens, deserialize_account, mint_tokens, transfer_lamports_from_pdas},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
@@ -14,15 +17,22 @@ use solana_program::{
     pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
 };
 
-/// Base fees for crafting.
-/// 0.02 SOL.
-pub const BASE_FEES: u64 = 20_000_000;
+/// Default base fees for crafting, used by fees features created before they were stored
+/// and until the fees feature is created. 0.02 SOL.
+pub const DEFAULT_BASE_FEES: u64 = 20_000_000;
+
+/// Default percentages of the fees going to the recipe for each level, used by
+/// fees features created before they were stored and until the fees feature is created.
+pub const DEFAULT_FEE_PERCENTAGES: [u8; 12] = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 90, 100];
 
 /// Unlocks recipe fees.
 /// Aside from level 0, for every lamport that goes to the program admin, a shard is accumulated on the recipe.
 /// Accumulated shards are automatically minted as a special ingredient when collecting fees.
 ///
-/// - Level 0: 0% of base fees (see above) when crafting,
+/// The base fees and the percentage of the fees going to the recipe at each level are stored
+/// in the feature account. The rest of the fees goes to the program admin. By default:
+///
+/// - Level 0: 0% of base fees when crafting,
 ///            100% of base fees go to the program admin but no shards are accumulated.
 /// - Level 1: 10% of base fees, 90% shards.
 /// - Level 2: 20% of base fees, 80% shards.
@@ -33,7 +43,7 @@ pub const BASE_FEES: u64 = 20_000_000;
 /// - Level 7: 70% of base fees, 30% shards.
 /// - Level 8: 80% of base fees, 20% shards.
 /// - Level 9: 90% of base fees, 10% shards.
-/// - Level 10: 90% of custom fees, 10% shards. If custom fees are set below base fees, no experience is gained on crafting.
+/// - Level 10: 90% of custom fees, 10% shards. If custom fees are set below base fees, no shards nor experience are gained on crafting.
 /// - Level 11: 100% of custom fees. No shards nor experience are gained on crafting.
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
@@ -60,50 +70,80 @@ pub struct FeesFeature {
     pub mint_skill_2: Pubkey,
     /// Without burning, allows leveling up straight to 11.
     pub mint_skill_3: Pubkey,
+    /// The bump of the feature PDA.
+    pub bump: u8,
+    /// The version of the account layout.
+    pub version: u8,
+    /// The base fees for crafting, in lamports.
+    pub base_fees: u64,
+    /// The percentage of the fees going to the recipe at each level, from level 0 to 11.
+    pub fee_percentages: [u8; 12],
 }
 
 impl FeesFeature {
-    pub const LEN: usize = 1 + 32 * 10;
+    pub const LEN: usize = 1 + 32 * 10 + 1 + 1 + 8 + 12;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 32 * 10;
+    pub const VERSION: u8 = 2;
     pub const MAX_LEVEL: u8 = 11;
 
+    /// Assert that every fee percentage is at most 100%.
+    pub fn assert_valid_fee_percentages(&self) -> ProgramResult {
+        match self
+            .fee_percentages
+            .iter()
+            .position(|percent| *percent > 100)
+        {
+            Some(level) => {
+                msg!(
+                    "Fee percentage of level {} must be at most 100, got {}",
+                    level,
+                    self.fee_percentages[level]
+                );
+                Err(TokenRecipesError::InvalidFeePercentage.into())
+            }
+            None => Ok(()),
+        }
+    }
+
     pub fn unlock(&self, context: &UnlockFeatureContext) -> ProgramResult {
-        let mut recipe_account = Recipe::get_writable(context.recipe)?;
-        let level = recipe_account.feature_levels.fees;
+        let mut recipe_header = RecipeHeader::get_writable(context.recipe)?;
+        let level = recipe_header.feature_levels.fees;
         if level >= Self::MAX_LEVEL {
             return Err(TokenRecipesError::MaxFeatureLevelReached.into());
         }
 
         let result: Result<u64, ProgramError> = match context.mint.key {
             x if *x == self.mint_burn_1 && level < 1 => {
-                recipe_account.feature_levels.fees += 1;
+                recipe_header.feature_levels.fees += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_2 && level < 10 => {
-                recipe_account.feature_levels.fees += 1;
+                recipe_header.feature_levels.fees += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_3 && level < 11 => {
-                recipe_account.feature_levels.fees += 1;
+                recipe_header.feature_levels.fees += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_4 && level < 10 => {
-                recipe_account.feature_levels.fees = 10;
+                recipe_header.feature_levels.fees = 10;
                 Ok(1)
             }
             x if *x == self.mint_burn_5 && level < 11 => {
-                recipe_account.feature_levels.fees = 11;
+                recipe_header.feature_levels.fees = 11;
                 Ok(1)
             }
             x if *x == self.mint_skill_1 && level < 1 => {
-                recipe_account.feature_levels.fees = 1;
+                recipe_header.feature_levels.fees = 1;
                 Ok(0)
             }
             x if *x == self.mint_skill_2 && level < 10 => {
-                recipe_account.feature_levels.fees = 10;
+                recipe_header.feature_levels.fees = 10;
                 Ok(0)
             }
             x if *x == self.mint_skill_3 && level < 11 => {
-                recipe_account.feature_levels.fees = 11;
+                recipe_header.feature_levels.fees = 11;
                 Ok(0)
             }
             _ => Err(TokenRecipesError::InvalidMintToLevelUpFeature.into()),
@@ -122,11 +162,11 @@ impl FeesFeature {
         }
 
         // If level 10 is reached for the first time, set custom fees to base fees.
-        if level < 10 && recipe_account.feature_levels.fees >= 10 {
-            recipe_account.fees = BASE_FEES;
+        if level < 10 && recipe_header.feature_levels.fees >= 10 {
+            recipe_header.fees = self.base_fees.into();
         }
 
-        recipe_account.save(context.recipe)
+        Ok(())
     }
 
     pub fn seeds<'a>() -> Vec<&'a [u8]> {
@@ -136,15 +176,70 @@ impl FeesFeature {
     pub fn get(fees_feature_pda: &AccountInfo) -> Result<Self, ProgramError> {
         assert_program_owner("fees_feature_pda", fees_feature_pda, &crate::id())?;
         assert_account_key("fees_feature_pda", fees_feature_pda, Key::FeesFeature)?;
-        Self::load(fees_feature_pda)
+        let fees_feature_account = Self::load(fees_feature_pda)?;
+        assert_pda_with_bump(
+            "fees_feature_pda",
+            fees_feature_pda,
+            &crate::id(),
+            &Self::seeds(),
+            fees_feature_account.bump,
+        )?;
+        Ok(fees_feature_account)
+    }
+
+    /// Get the fees feature, or a fees feature using the default fees
+    /// if the admin has not created it yet.
+    pub fn get_or_default(fees_feature_pda: &AccountInfo) -> Result<Self, ProgramError> {
+        if fees_feature_pda.data_is_empty() {
+            let bump = assert_pda(
+                "fees_feature_pda",
+                fees_feature_pda,
+                &crate::id(),
+                &Self::seeds(),
+            )?;
+            return Ok(Self {
+                key: Key::FeesFeature,
+                admin_destination: Pubkey::default(),
+                shard_mint: Pubkey::default(),
+                mint_burn_1: Pubkey::default(),
+                mint_burn_2: Pubkey::default(),
+                mint_burn_3: Pubkey::default(),
+                mint_burn_4: Pubkey::default(),
+                mint_burn_5: Pubkey::default(),
+                mint_skill_1: Pubkey::default(),
+                mint_skill_2: Pubkey::default(),
+                mint_skill_3: Pubkey::default(),
+                bump,
+                version: Self::VERSION,
+                base_fees: DEFAULT_BASE_FEES,
+                fee_percentages: DEFAULT_FEE_PERCENTAGES,
+            });
+        }
+        Self::get(fees_feature_pda)
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        FeesFeature::deserialize(&mut bytes).map_err(|error| {
-            msg!("Error deserializing FeesFeature account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut feature: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!("Error deserializing FeesFeature account: {}", error);
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if feature.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if feature.bump == 0 {
+                feature.bump = Pubkey::find_program_address(&Self::seeds(), &crate::id()).1;
+            }
+            // Use the default fees of accounts created before they were stored.
+            if feature.version < 2 {
+                feature.base_fees = DEFAULT_BASE_FEES;
+                feature.fee_percentages = DEFAULT_FEE_PERCENTAGES;
+            }
+            feature.version = Self::VERSION;
+        }
+        Ok(feature)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -160,25 +255,18 @@ impl FeesFeature {
 
 /// Returns the total fees, admin fees and shards per craft.
 /// Returns (total fees, admin fees, shards).
-pub fn get_fees_and_shards_per_craft(recipe: &Recipe) -> Result<(u64, u64, u64), ProgramError> {
-    let fee_percent: u64 = match recipe.feature_levels.fees {
-        1 => 10,
-        2 => 20,
-        3 => 30,
-        4 => 40,
-        5 => 50,
-        6 => 60,
-        7 => 70,
-        8 => 80,
-        9 => 90,
-        10 => 90,
-        11 => 100,
-        _ => 0, // Level 0.
-    };
+pub fn get_fees_and_shards_per_craft(
+    recipe: &Recipe,
+    fees_feature: &FeesFeature,
+) -> Result<(u64, u64, u64), ProgramError> {
+    let fee_percent: u64 = fees_feature
+        .fee_percentages
+        .get(recipe.feature_levels.fees as usize)
+        .map_or(0, |percent| *percent as u64);
 
     let total_fees: u64 = match recipe.feature_levels.fees {
         10 | 11 => recipe.fees,
-        _ => BASE_FEES,
+        _ => fees_feature.base_fees,
     };
 
     let recipe_fees = total_fees
@@ -192,7 +280,7 @@ pub fn get_fees_and_shards_per_craft(recipe: &Recipe) -> Result<(u64, u64, u64),
 
     let shards = match recipe.feature_levels.fees {
         0 => 0,
-        _ if total_fees < BASE_FEES => 0,
+        _ if total_fees < fees_feature.base_fees => 0,
         _ => admin_fees,
     };
 
@@ -200,7 +288,7 @@ pub fn get_fees_and_shards_per_craft(recipe: &Recipe) -> Result<(u64, u64, u64),
 }
 
 /// Asserts that the recipe can set custom fees.
-pub fn asserts_can_set_fees(recipe: &Recipe) -> ProgramResult {
+pub fn asserts_can_set_fees(recipe: &RecipeHeader) -> ProgramResult {
     let level = recipe.feature_levels.fees;
     if level < 10 {
         msg!(
@@ -212,13 +300,16 @@ pub fn asserts_can_set_fees(recipe: &Recipe) -> ProgramResult {
     }
 }
 
+/// Transfer the accumulated admin fees to the admin destination and
+/// the rest of the recipe lamports above rent to the authority.
+/// Returns the lamports transferred to the authority.
 pub fn collect_fees<'a>(
     accumulated_admin_fees: u64,
     expected_admin_fees_destination: &Pubkey,
     recipe: &'a AccountInfo<'a>,
     authority: &'a AccountInfo<'a>,
     admin_fees_destination: &'a AccountInfo<'a>,
-) -> ProgramResult {
+) -> Result<u64, ProgramError> {
     msg!("Collecting fees...");
 
     // Rent.
@@ -247,7 +338,8 @@ pub fn collect_fees<'a>(
         admin_fees_destination,
         &expected_admin_fees_destination,
     )?;
-    transfer_lamports_from_pdas(recipe, admin_fees_destination, accumulated_admin_fees)
+    transfer_lamports_from_pdas(recipe, admin_fees_destination, accumulated_admin_fees)?;
+    Ok(authority_fees)
 }
 
 pub fn collect_shards<'a>(
@@ -257,18 +349,11 @@ pub fn collect_shards<'a>(
     shards_mint: &'a AccountInfo<'a>,
     shards_token: &'a AccountInfo<'a>,
     fees_feature_pda: &'a AccountInfo<'a>,
+    fees_feature_bump: u8,
     payer: &'a AccountInfo<'a>,
 ) -> ProgramResult {
     msg!("Collecting shards...");
 
-    // Check: fees_feature_pda.
-    let bump = assert_pda(
-        "fees_feature_pda",
-        fees_feature_pda,
-        &crate::id(),
-        &FeesFeature::seeds(),
-    )?;
-
     // Check: shards_mint
     assert_same_pubkeys("shards_mint", shards_mint, &expected_shards_mint)?;
     assert_writable("shards_mint", shards_mint)?;
@@ -287,7 +372,7 @@ pub fn collect_shards<'a>(
 
     // Mint shards.
     let mut seeds = FeesFeature::seeds();
-    let bump = &[bump];
+    let bump = &[fees_feature_bump];
     seeds.push(bump);
     mint_tokens(
         shards_token,
@@ -3,9 +3,9 @@ use crate::{
     error::TokenRecipesError,
     state::{
         features::UnlockFeatureContext, ingredient_output::IngredientOutput, key::Key,
-        recipe::Recipe,
+        recipe::Recipe, recipe_header::RecipeHeader,
     },
-    utils::burn_tokens,
+    utils::{burn_tokens, deserialize_account},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
@@ -27,26 +27,33 @@ pub struct MaxSupplyFeature {
     pub mint_burn_1: Pubkey,
     /// Without burning, allows leveling up straight to 1.
     pub mint_skill_1: Pubkey,
+    /// The bump of the feature PDA.
+    pub bump: u8,
+    /// The version of the account layout.
+    pub version: u8,
 }
 
 impl MaxSupplyFeature {
-    pub const LEN: usize = 1 + 32 * 2;
+    pub const LEN: usize = 1 + 32 * 2 + 1 + 1;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 32 * 2;
+    pub const VERSION: u8 = 1;
     pub const MAX_LEVEL: u8 = 1;
 
     pub fn unlock(&self, context: &UnlockFeatureContext) -> ProgramResult {
-        let mut recipe_account = Recipe::get_writable(context.recipe)?;
-        let level = recipe_account.feature_levels.max_supply;
+        let mut recipe_header = RecipeHeader::get_writable(context.recipe)?;
+        let level = recipe_header.feature_levels.max_supply;
         if level >= Self::MAX_LEVEL {
             return Err(TokenRecipesError::MaxFeatureLevelReached.into());
         }
 
         let result: Result<u64, ProgramError> = match context.mint.key {
             x if *x == self.mint_burn_1 && level < 1 => {
-                recipe_account.feature_levels.max_supply += 1;
+                recipe_header.feature_levels.max_supply += 1;
                 Ok(1)
             }
             x if *x == self.mint_skill_1 && level < 1 => {
-                recipe_account.feature_levels.max_supply = 1;
+                recipe_header.feature_levels.max_supply = 1;
                 Ok(0)
             }
             _ => Err(TokenRecipesError::InvalidMintToLevelUpFeature.into()),
@@ -64,7 +71,7 @@ impl MaxSupplyFeature {
             )?;
         }
 
-        recipe_account.save(context.recipe)
+        Ok(())
     }
 
     pub fn seeds<'a>() -> Vec<&'a [u8]> {
@@ -72,11 +79,22 @@ impl MaxSupplyFeature {
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        MaxSupplyFeature::deserialize(&mut bytes).map_err(|error| {
-            msg!("Error deserializing MaxSupplyFeature account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut feature: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!("Error deserializing MaxSupplyFeature account: {}", error);
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if feature.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if feature.bump == 0 {
+                feature.bump = Pubkey::find_program_address(&Self::seeds(), &crate::id()).1;
+            }
+            feature.version = Self::VERSION;
+        }
+        Ok(feature)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -10,6 +10,7 @@ use crate::{
     },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
 };
@@ -21,7 +22,8 @@ pub mod sol_payment;
 pub mod transfer_inputs;
 pub mod wisdom;
 
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Default)]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
 pub struct FeatureLevels {
     pub fees: u8,
     pub additional_outputs: u8,
@@ -29,13 +31,17 @@ pub struct FeatureLevels {
     pub max_supply: u8,
     pub sol_payment: u8,
     pub wisdom: u8,
-    pub _padding: [u8; 10],
+    pub _padding: [u8; 8],
 }
 
 impl FeatureLevels {
-    pub const LEN: usize = 16;
+    pub const LEN: usize = 14;
 }
 
+// Safety: all fields are bytes, so the struct has no padding and any bit pattern is valid.
+unsafe impl Zeroable for FeatureLevels {}
+unsafe impl Pod for FeatureLevels {}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum Feature {
     Fees(FeesFeature),
@@ -64,6 +70,29 @@ impl Feature {
             Self::Wisdom(feature) => feature.unlock(context),
         }
     }
+    pub fn key(&self) -> Key {
+        match self {
+            Self::Fees(feature) => feature.key,
+            Self::AdditionalOutputs(feature) => feature.key,
+            Self::TransferInputs(feature) => feature.key,
+            Self::MaxSupply(feature) => feature.key,
+            Self::SolPayment(feature) => feature.key,
+            Self::Wisdom(feature) => feature.key,
+        }
+    }
+
+    /// The size of the feature account with the current layout.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Fees(_) => FeesFeature::LEN,
+            Self::AdditionalOutputs(_) => AdditionalOutputsFeature::LEN,
+            Self::TransferInputs(_) => TransferInputsFeature::LEN,
+            Self::MaxSupply(_) => MaxSupplyFeature::LEN,
+            Self::SolPayment(_) => SolPaymentFeature::LEN,
+            Self::Wisdom(_) => WisdomFeature::LEN,
+        }
+    }
+
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
         let key = account.try_borrow_data()?[0];
         match key {
@@ -87,4 +116,15 @@ impl Feature {
             }
         }
     }
+
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        match self {
+            Self::Fees(feature) => feature.save(account),
+            Self::AdditionalOutputs(feature) => feature.save(account),
+            Self::TransferInputs(feature) => feature.save(account),
+            Self::MaxSupply(feature) => feature.save(account),
+            Self::SolPayment(feature) => feature.save(account),
+            Self::Wisdom(feature) => feature.save(account),
+        }
+    }
 }
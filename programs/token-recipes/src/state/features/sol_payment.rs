@@ -2,9 +2,10 @@ use crate::{
     assertions::assert_mint_account,
     error::TokenRecipesError,
     state::{
-        features::UnlockFeatureContext, ingredient_input::IngredientInput, key::Key, recipe::Recipe,
+        features::UnlockFeatureContext, ingredient_input::IngredientInput, key::Key,
+        recipe::Recipe, recipe_header::RecipeHeader,
     },
-    utils::burn_tokens,
+    utils::{burn_tokens, deserialize_account},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
@@ -60,74 +61,81 @@ pub struct SolPaymentFeature {
     pub mint_skill_4: Pubkey,
     /// Without burning, allows leveling up straight to 11.
     pub mint_skill_5: Pubkey,
+    /// The bump of the feature PDA.
+    pub bump: u8,
+    /// The version of the account layout.
+    pub version: u8,
 }
 
 impl SolPaymentFeature {
-    pub const LEN: usize = 1 + 32 * 14;
+    pub const LEN: usize = 1 + 32 * 14 + 1 + 1;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 32 * 14;
+    pub const VERSION: u8 = 1;
     pub const MAX_LEVEL: u8 = 11;
 
     pub fn unlock(&self, context: &UnlockFeatureContext) -> ProgramResult {
-        let mut recipe_account = Recipe::get_writable(context.recipe)?;
-        let level = recipe_account.feature_levels.sol_payment;
+        let mut recipe_header = RecipeHeader::get_writable(context.recipe)?;
+        let level = recipe_header.feature_levels.sol_payment;
         if level >= Self::MAX_LEVEL {
             return Err(TokenRecipesError::MaxFeatureLevelReached.into());
         }
 
         let result: Result<u64, ProgramError> = match context.mint.key {
             x if *x == self.mint_burn_1 && level < 1 => {
-                recipe_account.feature_levels.sol_payment += 1;
+                recipe_header.feature_levels.sol_payment += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_2 && level < 3 => {
-                recipe_account.feature_levels.sol_payment += 1;
+                recipe_header.feature_levels.sol_payment += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_3 && level < 6 => {
-                recipe_account.feature_levels.sol_payment += 1;
+                recipe_header.feature_levels.sol_payment += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_4 && level < 10 => {
-                recipe_account.feature_levels.sol_payment += 1;
+                recipe_header.feature_levels.sol_payment += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_5 && level < 11 => {
-                recipe_account.feature_levels.sol_payment += 1;
+                recipe_header.feature_levels.sol_payment += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_6 && level < 3 => {
-                recipe_account.feature_levels.sol_payment = 3;
+                recipe_header.feature_levels.sol_payment = 3;
                 Ok(1)
             }
             x if *x == self.mint_burn_7 && level < 6 => {
-                recipe_account.feature_levels.sol_payment = 6;
+                recipe_header.feature_levels.sol_payment = 6;
                 Ok(1)
             }
             x if *x == self.mint_burn_8 && level < 10 => {
-                recipe_account.feature_levels.sol_payment = 10;
+                recipe_header.feature_levels.sol_payment = 10;
                 Ok(1)
             }
             x if *x == self.mint_burn_9 && level < 11 => {
-                recipe_account.feature_levels.sol_payment = 11;
+                recipe_header.feature_levels.sol_payment = 11;
                 Ok(1)
             }
             x if *x == self.mint_skill_1 && level < 1 => {
-                recipe_account.feature_levels.sol_payment = 1;
+                recipe_header.feature_levels.sol_payment = 1;
                 Ok(0)
             }
             x if *x == self.mint_skill_2 && level < 3 => {
-                recipe_account.feature_levels.sol_payment = 3;
+                recipe_header.feature_levels.sol_payment = 3;
                 Ok(0)
             }
             x if *x == self.mint_skill_3 && level < 6 => {
-                recipe_account.feature_levels.sol_payment = 6;
+                recipe_header.feature_levels.sol_payment = 6;
                 Ok(0)
             }
             x if *x == self.mint_skill_4 && level < 10 => {
-                recipe_account.feature_levels.sol_payment = 10;
+                recipe_header.feature_levels.sol_payment = 10;
                 Ok(0)
             }
             x if *x == self.mint_skill_5 && level < 11 => {
-                recipe_account.feature_levels.sol_payment = 11;
+                recipe_header.feature_levels.sol_payment = 11;
                 Ok(0)
             }
             _ => Err(TokenRecipesError::InvalidMintToLevelUpFeature.into()),
@@ -145,7 +153,7 @@ impl SolPaymentFeature {
             )?;
         }
 
-        recipe_account.save(context.recipe)
+        Ok(())
     }
 
     pub fn seeds<'a>() -> Vec<&'a [u8]> {
@@ -153,11 +161,22 @@ impl SolPaymentFeature {
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        SolPaymentFeature::deserialize(&mut bytes).map_err(|error| {
-            msg!("Error deserializing SolPaymentFeature account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut feature: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!("Error deserializing SolPaymentFeature account: {}", error);
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if feature.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if feature.bump == 0 {
+                feature.bump = Pubkey::find_program_address(&Self::seeds(), &crate::id()).1;
+            }
+            feature.version = Self::VERSION;
+        }
+        Ok(feature)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
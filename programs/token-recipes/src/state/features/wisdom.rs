@@ -1,15 +1,16 @@
 use crate::{
     assertions::{
-        assert_account_key, assert_mint_account, assert_pda, assert_program_owner,
+        assert_account_key, assert_mint_account, assert_pda_with_bump, assert_program_owner,
         assert_same_pubkeys, assert_token_account_or_create_ata, assert_writable,
     },
     error::TokenRecipesError,
     state::{
-        features::{fees::BASE_FEES, UnlockFeatureContext},
+        features::{fees::FeesFeature, UnlockFeatureContext},
         key::Key,
         recipe::Recipe,
+        recipe_header::RecipeHeader,
     },
-    utils::{burn_tokens, mint_tokens},
+    utils::{burn_tokens, deserialize_account, mint_tokens},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
@@ -38,26 +39,33 @@ pub struct WisdomFeature {
     pub mint_burn_1: Pubkey,
     /// When burned, allows leveling up by 1 from 0 to 6.
     pub mint_burn_2: Pubkey,
+    /// The bump of the feature PDA.
+    pub bump: u8,
+    /// The version of the account layout.
+    pub version: u8,
 }
 
 impl WisdomFeature {
-    pub const LEN: usize = 1 + 32 * 3;
+    pub const LEN: usize = 1 + 32 * 3 + 1 + 1;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 32 * 3;
+    pub const VERSION: u8 = 1;
     pub const MAX_LEVEL: u8 = 6;
 
     pub fn unlock(&self, context: &UnlockFeatureContext) -> ProgramResult {
-        let mut recipe_account = Recipe::get_writable(context.recipe)?;
-        let level = recipe_account.feature_levels.wisdom;
+        let mut recipe_header = RecipeHeader::get_writable(context.recipe)?;
+        let level = recipe_header.feature_levels.wisdom;
         if level >= Self::MAX_LEVEL {
             return Err(TokenRecipesError::MaxFeatureLevelReached.into());
         }
 
         let result: Result<u64, ProgramError> = match context.mint.key {
             x if *x == self.mint_burn_1 && level < 4 => {
-                recipe_account.feature_levels.wisdom += 1;
+                recipe_header.feature_levels.wisdom += 1;
                 Ok(1)
             }
             x if *x == self.mint_burn_2 && level < 6 => {
-                recipe_account.feature_levels.wisdom += 1;
+                recipe_header.feature_levels.wisdom += 1;
                 Ok(1)
             }
             _ => Err(TokenRecipesError::InvalidMintToLevelUpFeature.into()),
@@ -75,7 +83,7 @@ impl WisdomFeature {
             )?;
         }
 
-        recipe_account.save(context.recipe)
+        Ok(())
     }
 
     pub fn seeds<'a>() -> Vec<&'a [u8]> {
@@ -89,11 +97,22 @@ impl WisdomFeature {
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        WisdomFeature::deserialize(&mut bytes).map_err(|error| {
-            msg!("Error deserializing Wisdom account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut feature: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!("Error deserializing Wisdom account: {}", error);
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if feature.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if feature.bump == 0 {
+                feature.bump = Pubkey::find_program_address(&Self::seeds(), &crate::id()).1;
+            }
+            feature.version = Self::VERSION;
+        }
+        Ok(feature)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -107,8 +126,8 @@ impl WisdomFeature {
     }
 }
 
-pub fn get_experience_per_craft(recipe: &Recipe) -> u64 {
-    if recipe.feature_levels.fees == 10 && recipe.fees < BASE_FEES {
+pub fn get_experience_per_craft(recipe: &Recipe, fees_feature: &FeesFeature) -> u64 {
+    if recipe.feature_levels.fees == 10 && recipe.fees < fees_feature.base_fees {
         return 0;
     }
     if recipe.feature_levels.fees == 11 {
@@ -132,16 +151,18 @@ pub fn collect_experience<'a>(
     experience_mint: &'a AccountInfo<'a>,
     experience_token: &'a AccountInfo<'a>,
     wisdom_feature_pda: &'a AccountInfo<'a>,
+    wisdom_feature_bump: u8,
     payer: &'a AccountInfo<'a>,
 ) -> ProgramResult {
     msg!("Collecting experience...");
 
     // Check: wisdom_feature_pda.
-    let bump = assert_pda(
+    assert_pda_with_bump(
         "wisdom_feature_pda",
         wisdom_feature_pda,
         &crate::id(),
         &WisdomFeature::seeds(),
+        wisdom_feature_bump,
     )?;
 
     // Check: experience_mint
@@ -166,7 +187,7 @@ pub fn collect_experience<'a>(
 
     // Mint experience.
     let mut seeds = WisdomFeature::seeds();
-    let bump = &[bump];
+    let bump = &[wisdom_feature_bump];
     seeds.push(bump);
     mint_tokens(
         experience_token,
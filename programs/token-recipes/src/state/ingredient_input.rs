@@ -1,11 +1,17 @@
 use crate::{
     assertions::{
-        assert_enough_tokens, assert_mint_account, assert_same_pubkeys, assert_token_account,
-        assert_token_account_or_create_ata, assert_writable,
+        assert_enough_tokens, assert_mint_account, assert_program_owner, assert_same_pubkeys,
+        assert_token_account, assert_token_account_or_create_ata, assert_token_program,
+        assert_writable,
     },
     error::TokenRecipesError,
-    state::{ingredient_record::IngredientRecord, recipe::IngredientType, recipe::Recipe},
-    utils::{burn_tokens, transfer_lamports, transfer_tokens},
+    state::{
+        craft_quote::{CraftQuote, TokenAmount},
+        ingredient_record::IngredientRecord,
+        recipe::IngredientType,
+        recipe::Recipe,
+    },
+    utils::{burn_tokens, next_transfer_additional_accounts, transfer_lamports, transfer_tokens},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
@@ -15,7 +21,7 @@ use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
 };
-use spl_token::state::Mint;
+use spl_token_2022::state::Mint;
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub enum IngredientInput {
@@ -32,6 +38,10 @@ pub enum IngredientInput {
         lamports: u64,
         destination: Pubkey,
     },
+    HoldToken {
+        mint: Pubkey,
+        amount: u64,
+    },
 }
 
 impl IngredientInput {
@@ -40,6 +50,7 @@ impl IngredientInput {
             Self::BurnToken { .. } => 1 + 32 + 8,
             Self::TransferToken { .. } => 1 + 32 + 8 + 32,
             Self::TransferSol { .. } => 1 + 8 + 32,
+            Self::HoldToken { .. } => 1 + 32 + 8,
         }
     }
 
@@ -53,7 +64,7 @@ impl IngredientInput {
         system_program: &AccountInfo<'a>,
     ) -> ProgramResult {
         match self {
-            Self::BurnToken { .. } | Self::TransferToken { .. } => {
+            Self::BurnToken { .. } | Self::TransferToken { .. } | Self::HoldToken { .. } => {
                 assert_writable("mint", mint)?;
                 assert_mint_account("mint", mint)?;
                 recipe_account.add_ingredient_input(&self, recipe, payer, system_program)?;
@@ -90,7 +101,7 @@ impl IngredientInput {
         system_program: &AccountInfo<'a>,
     ) -> ProgramResult {
         match self {
-            Self::BurnToken { .. } | Self::TransferToken { .. } => {
+            Self::BurnToken { .. } | Self::TransferToken { .. } | Self::HoldToken { .. } => {
                 assert_writable("mint", mint)?;
                 assert_mint_account("mint", mint)?;
                 recipe_account.remove_ingredient_input(index, recipe, payer, system_program)?;
@@ -151,11 +162,19 @@ impl IngredientInput {
                     payer,
                 )?;
 
+                // Check: Token-2022 extensions.
+                let additional_accounts = next_transfer_additional_accounts(
+                    account_info_iter,
+                    input_mint,
+                    input_destination_token,
+                )?;
+
                 transfer_tokens(
                     input_mint,
                     owner,
                     input_token,
                     input_destination_token,
+                    &additional_accounts,
                     multiplied_amount,
                     input_mint_account.decimals,
                     None,
@@ -175,10 +194,106 @@ impl IngredientInput {
 
                 transfer_lamports(payer, input_destination, multiplied_lamports, None)
             }
+            Self::HoldToken { mint, amount } => {
+                let input_mint = next_account_info(account_info_iter)?;
+                let input_token = next_account_info(account_info_iter)?;
+                let input_token_program = next_account_info(account_info_iter)?;
+
+                // Check: ingredient mint.
+                assert_same_pubkeys("input_mint", input_mint, mint)?;
+                assert_mint_account("input_mint", input_mint)?;
+
+                // Check: ingredient token program.
+                assert_token_program("input_token_program", input_token_program)?;
+                assert_program_owner("input_mint", input_mint, input_token_program.key)?;
+
+                // Check: ingredient token.
+                let input_token_account = assert_token_account("input_token", input_token)?;
+                assert_program_owner("input_token", input_token, input_token_program.key)?;
+                assert_same_pubkeys("input_mint", input_mint, &input_token_account.mint)?;
+                assert_same_pubkeys("owner", owner, &input_token_account.owner)?;
+
+                // Held tokens are not consumed so the amount does not depend on the quantity.
+                assert_enough_tokens("input_token", input_token, input_token_account, *amount)
+            }
+        }
+    }
+
+    /// The tokens burned or transferred by the ingredient when crafting the given quantity.
+    pub fn token_cost(&self, quantity: u64) -> Result<Option<TokenAmount>, ProgramError> {
+        match self {
+            Self::BurnToken { mint, amount } | Self::TransferToken { mint, amount, .. } => {
+                Ok(Some(TokenAmount {
+                    mint: *mint,
+                    amount: amount
+                        .checked_mul(quantity)
+                        .ok_or(TokenRecipesError::NumericalOverflow)?,
+                }))
+            }
+            Self::TransferSol { .. } | Self::HoldToken { .. } => Ok(None),
+        }
+    }
+
+    /// The lamports transferred by the ingredient when crafting the given quantity.
+    pub fn lamport_cost(&self, quantity: u64) -> Result<u64, ProgramError> {
+        match self {
+            Self::TransferSol { lamports, .. } => Ok(lamports
+                .checked_mul(quantity)
+                .ok_or(TokenRecipesError::NumericalOverflow)?),
+            _ => Ok(0),
+        }
+    }
+
+    /// Assert that the owner has enough tokens to craft the ingredient and add its costs to the quote.
+    /// Token ingredients require the mint account and the token account of the ingredient.
+    pub fn quote<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+        &self,
+        account_info_iter: &mut I,
+        owner: &AccountInfo<'a>,
+        quantity: u64,
+        quote: &mut CraftQuote,
+    ) -> ProgramResult {
+        if let Some(token_cost) = self.token_cost(quantity)? {
+            assert_enough_owner_tokens(
+                account_info_iter,
+                owner,
+                &token_cost.mint,
+                token_cost.amount,
+            )?;
+            quote.token_costs.push(token_cost);
         }
+        if let Self::HoldToken { mint, amount } = self {
+            assert_enough_owner_tokens(account_info_iter, owner, mint, *amount)?;
+        }
+        quote.lamport_costs = quote
+            .lamport_costs
+            .checked_add(self.lamport_cost(quantity)?)
+            .ok_or(TokenRecipesError::NumericalOverflow)?;
+        Ok(())
     }
 }
 
+fn assert_enough_owner_tokens<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+    account_info_iter: &mut I,
+    owner: &AccountInfo<'a>,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let input_mint = next_account_info(account_info_iter)?;
+    let input_token = next_account_info(account_info_iter)?;
+
+    // Check: ingredient mint.
+    assert_same_pubkeys("input_mint", input_mint, mint)?;
+    assert_mint_account("input_mint", input_mint)?;
+
+    // Check: ingredient token.
+    let input_token_account = assert_token_account("input_token", input_token)?;
+    assert_program_owner("input_token", input_token, input_mint.owner)?;
+    assert_same_pubkeys("input_mint", input_mint, &input_token_account.mint)?;
+    assert_same_pubkeys("owner", owner, &input_token_account.owner)?;
+    assert_enough_tokens("input_token", input_token, input_token_account, amount)
+}
+
 fn next_input_mint_and_token<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
     account_info_iter: &mut I,
     owner: &AccountInfo<'a>,
@@ -188,15 +303,21 @@ fn next_input_mint_and_token<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
 ) -> Result<(&'a AccountInfo<'a>, Mint, &'a AccountInfo<'a>, u64), ProgramError> {
     let input_mint = next_account_info(account_info_iter)?;
     let input_token = next_account_info(account_info_iter)?;
+    let input_token_program = next_account_info(account_info_iter)?;
 
     // Check: ingredient mint.
     assert_same_pubkeys("input_mint", input_mint, mint)?;
     assert_writable("input_mint", input_mint)?;
     let input_mint_account = assert_mint_account("input_mint", input_mint)?;
 
+    // Check: ingredient token program.
+    assert_token_program("input_token_program", input_token_program)?;
+    assert_program_owner("input_mint", input_mint, input_token_program.key)?;
+
     // Check: ingredient token.
     assert_writable("input_token", input_token)?;
     let input_token_account = assert_token_account("input_token", input_token)?;
+    assert_program_owner("input_token", input_token, input_token_program.key)?;
     assert_same_pubkeys("input_mint", input_mint, &input_token_account.mint)?;
     assert_same_pubkeys("owner", owner, &input_token_account.owner)?;
 
@@ -1,23 +1,32 @@
 use crate::{
     assertions::{
-        assert_account_key, assert_mint_account, assert_pda, assert_program_owner,
-        assert_same_pubkeys, assert_token_account_or_create_ata, assert_writable,
+        assert_account_key, assert_mint_account, assert_pda_with_bump, assert_program_owner,
+        assert_same_pubkeys, assert_token_account_or_create_ata, assert_token_program,
+        assert_writable,
     },
     error::TokenRecipesError,
     state::{
-        delegated_ingredient::DelegatedIngredient, ingredient_record::IngredientRecord, key::Key,
+        craft_quote::TokenAmount,
+        delegated_ingredient::DelegatedIngredient,
+        ingredient_record::IngredientRecord,
+        key::Key,
+        loot_roll::{LootRoll, LootTableRolls},
         recipe::Recipe,
     },
-    utils::mint_tokens,
+    utils::{mint_tokens, next_transfer_additional_accounts, transfer_tokens},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
+    hash::{hashv, Hash},
+    msg,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
-use spl_token::state::Mint;
+use spl_token_2022::state::Mint;
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub enum IngredientOutput {
@@ -30,13 +39,47 @@ pub enum IngredientOutput {
         amount: u64,
         max_supply: u64,
     },
+    TransferToken {
+        mint: Pubkey,
+        amount: u64,
+    },
+    MintTokenFromLootTable {
+        rolls: u8,
+        entries: Vec<LootTableEntry>,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct LootTableEntry {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub weight: u16,
 }
 
+impl LootTableEntry {
+    pub const LEN: usize = 32 + 8 + 2;
+}
+
+/// The maximum number of times loot tables can be rolled in a single craft.
+pub const MAX_LOOT_TABLE_ROLLS_PER_CRAFT: u64 = 64;
+
 impl IngredientOutput {
+    /// The number of mints this output can reward, i.e. every entry of a loot table.
+    pub fn mints_len(&self) -> usize {
+        match self {
+            Self::MintTokenFromLootTable { entries, .. } => entries.len(),
+            _ => 1,
+        }
+    }
+
     pub fn len(&self) -> usize {
         match self {
             Self::MintToken { .. } => 1 + 32 + 8,
             Self::MintTokenWithMaxSupply { .. } => 1 + 32 + 8 + 8,
+            Self::TransferToken { .. } => 1 + 32 + 8,
+            Self::MintTokenFromLootTable { entries, .. } => {
+                1 + 1 + 4 + entries.len() * LootTableEntry::LEN
+            }
         }
     }
 
@@ -73,6 +116,23 @@ impl IngredientOutput {
                     system_program,
                 )
             }
+            Self::TransferToken { .. } => {
+                assert_mint_account("mint", mint)?;
+                recipe_account.add_ingredient_output(&self, recipe, payer, system_program)?;
+                let mut ingredient_record_account = IngredientRecord::get_or_create(
+                    ingredient_record,
+                    mint,
+                    recipe,
+                    payer,
+                    system_program,
+                )?;
+                ingredient_record_account.set_output(true)?;
+                ingredient_record_account.save(ingredient_record)
+            }
+            Self::MintTokenFromLootTable { .. } => {
+                msg!("Loot table outputs must be added using the AddLootTableOutput instruction.");
+                Err(TokenRecipesError::InvalidLootTable.into())
+            }
         }
     }
 
@@ -84,7 +144,6 @@ impl IngredientOutput {
         mint: &AccountInfo<'a>,
         ingredient_record: &AccountInfo<'a>,
         delegated_ingredient: &AccountInfo<'a>,
-        authority: &AccountInfo<'a>,
         payer: &AccountInfo<'a>,
         system_program: &AccountInfo<'a>,
     ) -> ProgramResult {
@@ -97,23 +156,128 @@ impl IngredientOutput {
                     IngredientRecord::get(ingredient_record, mint, recipe)?;
                 ingredient_record_account.set_output(false)?;
                 ingredient_record_account.save_or_close(ingredient_record, payer)?;
-                DelegatedIngredient::close_or_decrement(
-                    delegated_ingredient,
-                    mint,
-                    authority,
-                    payer,
-                )
+                DelegatedIngredient::close_or_decrement(delegated_ingredient, mint, payer)
+            }
+            Self::TransferToken { .. } => {
+                assert_mint_account("mint", mint)?;
+                recipe_account.remove_ingredient_output(index, recipe, payer, system_program)?;
+                let mut ingredient_record_account =
+                    IngredientRecord::get(ingredient_record, mint, recipe)?;
+                ingredient_record_account.set_output(false)?;
+                ingredient_record_account.save_or_close(ingredient_record, payer)
+            }
+            Self::MintTokenFromLootTable { .. } => {
+                msg!("Loot table outputs must be removed using the RemoveLootTableOutput instruction.");
+                Err(TokenRecipesError::InvalidLootTable.into())
+            }
+        }
+    }
+
+    /// Add a loot table output to the recipe.
+    /// The remaining accounts must provide, for each entry of the loot table:
+    ///   - [writable] The mint account of the entry.
+    ///   - [writable] The ingredient record PDA of the entry.
+    ///   - [writable] The delegated ingredient PDA of the entry.
+    pub fn add_loot_table<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+        &self,
+        account_info_iter: &mut I,
+        recipe_account: &mut Recipe,
+        recipe: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let entries = match self {
+            Self::MintTokenFromLootTable { rolls, entries } => {
+                assert_valid_loot_table(*rolls, entries)?;
+                entries
             }
+            _ => return Err(TokenRecipesError::InvalidLootTable.into()),
+        };
+
+        recipe_account.add_ingredient_output(self, recipe, payer, system_program)?;
+        for entry in entries {
+            let mint = next_account_info(account_info_iter)?;
+            let ingredient_record = next_account_info(account_info_iter)?;
+            let delegated_ingredient = next_account_info(account_info_iter)?;
+
+            assert_same_pubkeys("mint", mint, &entry.mint)?;
+            assert_writable("mint", mint)?;
+            assert_mint_account("mint", mint)?;
+            let mut ingredient_record_account = IngredientRecord::get_or_create(
+                ingredient_record,
+                mint,
+                recipe,
+                payer,
+                system_program,
+            )?;
+            ingredient_record_account.set_output(true)?;
+            ingredient_record_account.save(ingredient_record)?;
+            DelegatedIngredient::create_or_increment(
+                delegated_ingredient,
+                mint,
+                authority,
+                payer,
+                system_program,
+            )?;
         }
+
+        Ok(())
     }
 
+    /// Remove a loot table output from the recipe.
+    /// The remaining accounts must provide, for each entry of the loot table:
+    ///   - [writable] The mint account of the entry.
+    ///   - [writable] The ingredient record PDA of the entry.
+    ///   - [writable] The delegated ingredient PDA of the entry.
+    pub fn remove_loot_table<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+        &self,
+        account_info_iter: &mut I,
+        recipe_account: &mut Recipe,
+        index: usize,
+        recipe: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let entries = match self {
+            Self::MintTokenFromLootTable { entries, .. } => entries,
+            _ => {
+                msg!("Ingredient #{} is not a loot table output.", index);
+                return Err(TokenRecipesError::MissingIngredient.into());
+            }
+        };
+
+        recipe_account.remove_ingredient_output(index, recipe, payer, system_program)?;
+        for entry in entries {
+            let mint = next_account_info(account_info_iter)?;
+            let ingredient_record = next_account_info(account_info_iter)?;
+            let delegated_ingredient = next_account_info(account_info_iter)?;
+
+            assert_same_pubkeys("mint", mint, &entry.mint)?;
+            assert_writable("mint", mint)?;
+            assert_mint_account("mint", mint)?;
+            let mut ingredient_record_account =
+                IngredientRecord::get(ingredient_record, mint, recipe)?;
+            ingredient_record_account.set_output(false)?;
+            ingredient_record_account.save_or_close(ingredient_record, payer)?;
+            DelegatedIngredient::close_or_decrement(delegated_ingredient, mint, payer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Craft the ingredient and return the tokens received by the owner.
+    /// Loot tables are only committed and their tokens are minted by the RevealLoot instruction.
     pub fn craft<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
         &self,
         account_info_iter: &mut I,
+        recipe: &'a AccountInfo<'a>,
+        recipe_account: &Recipe,
         owner: &'a AccountInfo<'a>,
         payer: &'a AccountInfo<'a>,
+        system_program: &'a AccountInfo<'a>,
         quantity: u64,
-    ) -> ProgramResult {
+    ) -> Result<Vec<TokenAmount>, ProgramError> {
         match self {
             Self::MintToken { mint, amount } => {
                 let (
@@ -143,7 +307,11 @@ impl IngredientOutput {
                     multiplied_amount,
                     output_mint_account.decimals,
                     Some(&[&seeds]),
-                )
+                )?;
+                Ok(vec![TokenAmount {
+                    mint: *mint,
+                    amount: multiplied_amount,
+                }])
             }
             Self::MintTokenWithMaxSupply {
                 mint,
@@ -186,10 +354,284 @@ impl IngredientOutput {
                     multiplied_amount,
                     output_mint_account.decimals,
                     Some(&[&seeds]),
-                )
+                )?;
+                Ok(vec![TokenAmount {
+                    mint: *mint,
+                    amount: multiplied_amount,
+                }])
+            }
+            Self::TransferToken { mint, amount } => {
+                let output_mint = next_account_info(account_info_iter)?;
+                let output_token = next_account_info(account_info_iter)?;
+                let output_token_program = next_account_info(account_info_iter)?;
+                let vault = next_account_info(account_info_iter)?;
+
+                // Check: ingredient mint and token.
+                let output_mint_account = assert_output_mint_and_token(
+                    output_mint,
+                    output_token,
+                    output_token_program,
+                    owner,
+                    payer,
+                    mint,
+                )?;
+
+                // Check: vault.
+                assert_writable("vault", vault)?;
+                let vault_account = recipe_account.assert_vault(vault, recipe, output_mint)?;
+
+                // Check: the vault has enough tokens.
+                let multiplied_amount = amount
+                    .checked_mul(quantity)
+                    .ok_or(TokenRecipesError::NumericalOverflow)?;
+                if vault_account.amount < multiplied_amount {
+                    msg!(
+                        "The vault [{}] of ingredient [{}] only holds [{}] tokens, [{}] are needed.",
+                        vault.key,
+                        mint,
+                        vault_account.amount,
+                        multiplied_amount
+                    );
+                    return Err(TokenRecipesError::NotEnoughTokensInVault.into());
+                }
+
+                // Check: Token-2022 extensions.
+                let additional_accounts = next_transfer_additional_accounts(
+                    account_info_iter,
+                    output_mint,
+                    output_token,
+                )?;
+
+                // Transfer the ingredient token from the vault.
+                let base = recipe_account.base;
+                let mut seeds = Recipe::seeds(&base);
+                let recipe_bump = [recipe_account.bump];
+                seeds.push(&recipe_bump);
+                transfer_tokens(
+                    output_mint,
+                    recipe,
+                    vault,
+                    output_token,
+                    &additional_accounts,
+                    multiplied_amount,
+                    output_mint_account.decimals,
+                    Some(&[&seeds]),
+                )?;
+                Ok(vec![TokenAmount {
+                    mint: *mint,
+                    amount: multiplied_amount,
+                }])
+            }
+            Self::MintTokenFromLootTable { rolls, entries } => {
+                let loot_roll = next_account_info(account_info_iter)?;
+
+                // Commit the loot table, it is rolled and minted by the RevealLoot instruction.
+                LootRoll::commit(
+                    loot_roll,
+                    recipe,
+                    owner,
+                    payer,
+                    system_program,
+                    recipe_account.total_crafts,
+                    Clock::get()?.slot,
+                    LootTableRolls {
+                        rolls: loot_table_rolls(*rolls, quantity)?,
+                        entries: entries.clone(),
+                    },
+                )?;
+                Ok(vec![])
+            }
+        }
+    }
+
+    /// Assert that the ingredient can be crafted without exceeding its supply.
+    /// Mint outputs with a maximum supply require the mint account of the ingredient and
+    /// transfer outputs require the mint account and the vault of the ingredient.
+    pub fn quote<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+        &self,
+        account_info_iter: &mut I,
+        recipe: &AccountInfo<'a>,
+        recipe_account: &Recipe,
+        quantity: u64,
+    ) -> ProgramResult {
+        match self {
+            Self::MintToken { .. } => Ok(()),
+            Self::MintTokenWithMaxSupply {
+                mint,
+                amount,
+                max_supply,
+            } => {
+                let output_mint = next_account_info(account_info_iter)?;
+                assert_same_pubkeys("output_mint", output_mint, mint)?;
+                let output_mint_account = assert_mint_account("output_mint", output_mint)?;
+
+                // Assert max supply is not exceeded.
+                let new_supply = amount
+                    .checked_mul(quantity)
+                    .and_then(|amount| output_mint_account.supply.checked_add(amount))
+                    .ok_or(TokenRecipesError::NumericalOverflow)?;
+                if new_supply > *max_supply {
+                    return Err(TokenRecipesError::MaximumSupplyReached.into());
+                }
+                Ok(())
+            }
+            Self::TransferToken { mint, amount } => {
+                let output_mint = next_account_info(account_info_iter)?;
+                let vault = next_account_info(account_info_iter)?;
+                assert_same_pubkeys("output_mint", output_mint, mint)?;
+                assert_mint_account("output_mint", output_mint)?;
+                let vault_account = recipe_account.assert_vault(vault, recipe, output_mint)?;
+
+                // Check: the vault has enough tokens.
+                let multiplied_amount = amount
+                    .checked_mul(quantity)
+                    .ok_or(TokenRecipesError::NumericalOverflow)?;
+                if vault_account.amount < multiplied_amount {
+                    msg!(
+                        "The vault [{}] of ingredient [{}] only holds [{}] tokens, [{}] are needed.",
+                        vault.key,
+                        mint,
+                        vault_account.amount,
+                        multiplied_amount
+                    );
+                    return Err(TokenRecipesError::NotEnoughTokensInVault.into());
+                }
+                Ok(())
+            }
+            Self::MintTokenFromLootTable { rolls, .. } => {
+                loot_table_rolls(*rolls, quantity)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Assert that the given loot table can be rolled.
+pub fn assert_valid_loot_table(rolls: u8, entries: &[LootTableEntry]) -> ProgramResult {
+    if rolls == 0 {
+        msg!("A loot table must be rolled at least once.");
+        return Err(TokenRecipesError::InvalidLootTable.into());
+    }
+    if entries.is_empty() {
+        msg!("A loot table must have at least one entry.");
+        return Err(TokenRecipesError::InvalidLootTable.into());
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.amount == 0 {
+            return Err(TokenRecipesError::CannotAddIngredientWithZeroAmount.into());
+        }
+        if entry.weight == 0 {
+            msg!("Loot table entry #{} must have a positive weight.", index);
+            return Err(TokenRecipesError::InvalidLootTable.into());
+        }
+        if entries[..index].iter().any(|e| e.mint == entry.mint) {
+            msg!(
+                "Loot table entry #{} uses the same mint as a previous entry.",
+                index
+            );
+            return Err(TokenRecipesError::InvalidLootTable.into());
+        }
+    }
+    Ok(())
+}
+
+/// The number of times a loot table is rolled when crafting the given quantity.
+fn loot_table_rolls(rolls: u8, quantity: u64) -> Result<u64, ProgramError> {
+    let total_rolls = (rolls as u64)
+        .checked_mul(quantity)
+        .ok_or(TokenRecipesError::NumericalOverflow)?;
+    if total_rolls > MAX_LOOT_TABLE_ROLLS_PER_CRAFT {
+        msg!(
+            "Cannot roll loot tables more than {} times per craft, got {}.",
+            MAX_LOOT_TABLE_ROLLS_PER_CRAFT,
+            total_rolls
+        );
+        return Err(TokenRecipesError::InvalidLootTable.into());
+    }
+    Ok(total_rolls)
+}
+
+/// Roll the loot table the given amount of times and
+/// return how many times each entry was picked.
+pub fn roll_loot_table(entries: &[LootTableEntry], total_rolls: u64, seed: &Hash) -> Vec<u64> {
+    let total_weight: u64 = entries.iter().map(|entry| entry.weight as u64).sum();
+    let mut results = vec![0u64; entries.len()];
+    for roll in 0..total_rolls {
+        let hash = hashv(&[seed.as_ref(), &roll.to_le_bytes()]).to_bytes();
+        let mut value = u64::from_le_bytes(hash[..8].try_into().unwrap()) % total_weight;
+        for (index, entry) in entries.iter().enumerate() {
+            if value < entry.weight as u64 {
+                results[index] += 1;
+                break;
             }
+            value -= entry.weight as u64;
         }
     }
+    results
+}
+
+/// Mint the rolled entries of a loot table and return the tokens received by the owner.
+/// Every entry requires the same accounts as a mint output, even if it was not rolled.
+pub fn mint_loot_table_entries<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+    account_info_iter: &mut I,
+    entries: &[LootTableEntry],
+    results: &[u64],
+    owner: &'a AccountInfo<'a>,
+    payer: &'a AccountInfo<'a>,
+) -> Result<Vec<TokenAmount>, ProgramError> {
+    let mut token_outputs = vec![];
+    for (entry_index, (entry, times_rolled)) in entries.iter().zip(results).enumerate() {
+        let entry_accounts = [
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+        ];
+        if *times_rolled == 0 {
+            continue;
+        }
+
+        let (
+            output_mint,
+            output_mint_account,
+            output_token,
+            delegated_ingredient,
+            delegated_ingredient_bump,
+            multiplied_amount,
+        ) = next_output_mint_and_token(
+            &mut entry_accounts.into_iter(),
+            owner,
+            payer,
+            *times_rolled,
+            &entry.mint,
+            &entry.amount,
+        )?;
+
+        msg!(
+            "Loot table rolled entry #{} {} time(s): minting [{}] tokens of [{}].",
+            entry_index,
+            times_rolled,
+            multiplied_amount,
+            entry.mint
+        );
+
+        let mut seeds = DelegatedIngredient::seeds(output_mint.key);
+        let delegated_ingredient_bump = [delegated_ingredient_bump];
+        seeds.push(&delegated_ingredient_bump);
+        mint_tokens(
+            output_token,
+            output_mint,
+            delegated_ingredient,
+            multiplied_amount,
+            output_mint_account.decimals,
+            Some(&[&seeds]),
+        )?;
+        token_outputs.push(TokenAmount {
+            mint: entry.mint,
+            amount: multiplied_amount,
+        });
+    }
+    Ok(token_outputs)
 }
 
 fn next_output_mint_and_token<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
@@ -212,6 +654,7 @@ fn next_output_mint_and_token<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
 > {
     let output_mint = next_account_info(account_info_iter)?;
     let output_token = next_account_info(account_info_iter)?;
+    let output_token_program = next_account_info(account_info_iter)?;
     let delegated_ingredient = next_account_info(account_info_iter)?;
 
     // Check: delegated ingredient.
@@ -221,27 +664,24 @@ fn next_output_mint_and_token<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
         delegated_ingredient,
         Key::DelegatedIngredient,
     )?;
-    let delegated_ingredient_bump = assert_pda(
+    let delegated_ingredient_bump = DelegatedIngredient::load(delegated_ingredient)?.bump;
+    assert_pda_with_bump(
         "delegated_ingredient",
         delegated_ingredient,
         &crate::id(),
         &DelegatedIngredient::seeds(output_mint.key),
+        delegated_ingredient_bump,
     )?;
 
-    // Check: ingredient mint.
-    assert_same_pubkeys("output_mint", output_mint, mint)?;
+    // Check: ingredient mint and token.
     assert_writable("output_mint", output_mint)?;
-    let output_mint_account = assert_mint_account("output_mint", output_mint)?;
-
-    // Check: ingredient token.
-    assert_token_account_or_create_ata(
-        "output_token",
-        output_token,
-        "output_mint",
+    let output_mint_account = assert_output_mint_and_token(
         output_mint,
-        "owner",
+        output_token,
+        output_token_program,
         owner,
         payer,
+        mint,
     )?;
 
     // Compute the total amount of tokens required.
@@ -258,3 +698,33 @@ fn next_output_mint_and_token<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
         multiplied_amount,
     ))
 }
+
+fn assert_output_mint_and_token<'a>(
+    output_mint: &'a AccountInfo<'a>,
+    output_token: &'a AccountInfo<'a>,
+    output_token_program: &'a AccountInfo<'a>,
+    owner: &'a AccountInfo<'a>,
+    payer: &'a AccountInfo<'a>,
+    mint: &Pubkey,
+) -> Result<Mint, ProgramError> {
+    // Check: ingredient mint.
+    assert_same_pubkeys("output_mint", output_mint, mint)?;
+    let output_mint_account = assert_mint_account("output_mint", output_mint)?;
+
+    // Check: ingredient token program.
+    assert_token_program("output_token_program", output_token_program)?;
+    assert_program_owner("output_mint", output_mint, output_token_program.key)?;
+
+    // Check: ingredient token.
+    assert_token_account_or_create_ata(
+        "output_token",
+        output_token,
+        "output_mint",
+        output_mint,
+        "owner",
+        owner,
+        payer,
+    )?;
+
+    Ok(output_mint_account)
+}
@@ -1,11 +1,14 @@
 use crate::{
     assertions::{
-        assert_account_key, assert_empty, assert_pda, assert_program_owner, assert_same_pubkeys,
-        assert_writable,
+        assert_account_key, assert_empty, assert_pda, assert_pda_with_bump, assert_program_owner,
+        assert_same_pubkeys, assert_writable,
     },
     error::TokenRecipesError,
     state::key::Key,
-    utils::{close_account, create_account},
+    utils::{
+        close_account, create_account, deserialize_account, realloc_legacy_account,
+        write_account_data,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
@@ -22,10 +25,15 @@ pub struct IngredientRecord {
     pub output: bool,
     pub mint: Pubkey,
     pub recipe: Pubkey,
+    pub bump: u8,
+    pub version: u8,
 }
 
 impl IngredientRecord {
-    pub const LEN: usize = 1 + 1 + 1 + 32 + 32;
+    pub const LEN: usize = 1 + 1 + 1 + 32 + 32 + 1 + 1;
+    /// The size of accounts created before the bump and version were stored.
+    pub const LEGACY_LEN: usize = 1 + 1 + 1 + 32 + 32;
+    pub const VERSION: u8 = 1;
 
     pub fn set_input(&mut self, value: bool) -> ProgramResult {
         match (self.input == value, value) {
@@ -118,6 +126,8 @@ impl IngredientRecord {
             output: false,
             mint: *mint.key,
             recipe: *recipe.key,
+            bump: ingredient_record_bump,
+            version: Self::VERSION,
         })
     }
 
@@ -133,13 +143,14 @@ impl IngredientRecord {
             ingredient_record,
             Key::IngredientRecord,
         )?;
-        assert_pda(
+        let ingredient_record_account = Self::load(ingredient_record)?;
+        assert_pda_with_bump(
             "ingredient_record",
             ingredient_record,
             &crate::id(),
             &Self::seeds(mint.key, recipe.key),
+            ingredient_record_account.bump,
         )?;
-        let ingredient_record_account = Self::load(ingredient_record)?;
         assert_same_pubkeys("recipe", recipe, &ingredient_record_account.recipe)?;
         assert_same_pubkeys("mint", mint, &ingredient_record_account.mint)?;
         Ok(ingredient_record_account)
@@ -154,7 +165,11 @@ impl IngredientRecord {
     ) -> Result<Self, ProgramError> {
         match ingredient_record.data_is_empty() {
             true => Self::create(ingredient_record, mint, recipe, payer, system_program),
-            false => Self::get(ingredient_record, mint, recipe),
+            false => {
+                let ingredient_record_account = Self::get(ingredient_record, mint, recipe)?;
+                realloc_legacy_account(ingredient_record, payer, system_program, Self::LEN)?;
+                Ok(ingredient_record_account)
+            }
         }
     }
 
@@ -170,11 +185,23 @@ impl IngredientRecord {
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        Self::deserialize(&mut bytes).map_err(|error| {
-            msg!("Error deserializing IngredientRecord account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+        let mut ingredient_record: Self =
+            deserialize_account(&account.data.borrow(), Self::LEN, Self::LEGACY_LEN).map_err(
+                |error| {
+                    msg!("Error deserializing IngredientRecord account: {}", error);
+                    TokenRecipesError::DeserializationError
+                },
+            )?;
+        // Upgrade accounts created with an older layout.
+        if ingredient_record.version < Self::VERSION {
+            // Derive the bump of accounts created before it was stored.
+            if ingredient_record.bump == 0 {
+                let seeds = Self::seeds(&ingredient_record.mint, &ingredient_record.recipe);
+                ingredient_record.bump = Pubkey::find_program_address(&seeds, &crate::id()).1;
+            }
+            ingredient_record.version = Self::VERSION;
+        }
+        Ok(ingredient_record)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -183,7 +210,7 @@ impl IngredientRecord {
             msg!("Error serializing IngredientRecord account: {}", error);
             TokenRecipesError::SerializationError
         })?;
-        account.try_borrow_mut_data().unwrap()[..bytes.len()].copy_from_slice(&bytes);
+        write_account_data(account, &bytes);
         Ok(())
     }
 }
@@ -12,6 +12,9 @@ pub enum Key {
     MaxSupplyFeature,
     SolPaymentFeature,
     WisdomFeature,
+    CrafterRecord,
+    ProgramConfig,
+    LootRoll,
 }
 
 impl Key {
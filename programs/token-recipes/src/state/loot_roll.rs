@@ -0,0 +1,211 @@
+use crate::{
+    assertions::{assert_account_key, assert_pda, assert_program_owner, assert_writable},
+    error::TokenRecipesError,
+    state::{
+        ingredient_output::{roll_loot_table, LootTableEntry},
+        key::Key,
+    },
+    utils::{create_account, realloc_account, write_account_data},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    hash::{hashv, Hash},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    slot_hashes::MAX_ENTRIES,
+};
+
+/// The loot tables committed by a craft, rolled once the hash of
+/// the slot of the craft is known using the RevealLoot instruction.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+pub struct LootRoll {
+    pub key: Key,
+    pub recipe: Pubkey,
+    pub owner: Pubkey,
+    /// The account that paid for the loot roll and receives its rent back.
+    pub payer: Pubkey,
+    /// The total crafts of the recipe before the craft that committed the loot roll.
+    pub total_crafts: u64,
+    /// The slot of the craft, whose hash decides the rolls.
+    pub slot: u64,
+    pub version: u8,
+    pub loot_tables: Vec<LootTableRolls>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct LootTableRolls {
+    /// How many times the loot table is rolled, i.e. its rolls times the crafted quantity.
+    pub rolls: u64,
+    pub entries: Vec<LootTableEntry>,
+}
+
+impl LootTableRolls {
+    pub fn len(&self) -> usize {
+        8 + 4 + self.entries.len() * LootTableEntry::LEN
+    }
+}
+
+impl LootRoll {
+    pub const BASE_LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 1 + 4;
+    pub const VERSION: u8 = 1;
+
+    pub fn size(&self) -> usize {
+        Self::BASE_LEN + self.loot_tables.iter().map(|t| t.len()).sum::<usize>()
+    }
+
+    pub fn seeds<'a>(recipe: &'a Pubkey, owner: &'a Pubkey) -> Vec<&'a [u8]> {
+        vec!["loot_roll".as_bytes(), recipe.as_ref(), owner.as_ref()]
+    }
+
+    /// Commit a loot table of the given craft, creating the loot roll of the owner if needed.
+    /// A recipe with several loot tables commits all of them to the same loot roll, but the
+    /// owner must reveal the loot of a previous craft before crafting the recipe again.
+    pub fn commit<'a>(
+        loot_roll: &AccountInfo<'a>,
+        recipe: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        total_crafts: u64,
+        slot: u64,
+        loot_table: LootTableRolls,
+    ) -> ProgramResult {
+        assert_writable("loot_roll", loot_roll)?;
+        let bump = assert_pda(
+            "loot_roll",
+            loot_roll,
+            &crate::id(),
+            &Self::seeds(recipe.key, owner.key),
+        )?;
+
+        if loot_roll.data_is_empty() {
+            let loot_roll_account = Self {
+                key: Key::LootRoll,
+                recipe: *recipe.key,
+                owner: *owner.key,
+                payer: *payer.key,
+                total_crafts,
+                slot,
+                version: Self::VERSION,
+                loot_tables: vec![loot_table],
+            };
+            let mut seeds = Self::seeds(recipe.key, owner.key);
+            let bump = [bump];
+            seeds.push(&bump);
+            create_account(
+                loot_roll,
+                payer,
+                system_program,
+                loot_roll_account.size(),
+                &crate::id(),
+                Some(&[&seeds]),
+            )?;
+            return loot_roll_account.save(loot_roll);
+        }
+
+        let mut loot_roll_account = Self::get(loot_roll)?;
+        if loot_roll_account.total_crafts != total_crafts {
+            msg!(
+                "Owner [{}] must reveal the loot of their previous craft before crafting again.",
+                owner.key
+            );
+            return Err(TokenRecipesError::LootRollPending.into());
+        }
+        loot_roll_account.loot_tables.push(loot_table);
+        realloc_account(
+            loot_roll,
+            payer,
+            system_program,
+            loot_roll_account.size(),
+            false,
+        )?;
+        loot_roll_account.save(loot_roll)
+    }
+
+    /// The seed of the rolls, derived from the hash of the slot of the craft.
+    /// That hash only exists once the slot is over, so the crafter cannot know
+    /// the outcome when crafting. Returns `None` when the hash is no longer stored
+    /// in the SlotHashes sysvar, in which case the loot is forfeited.
+    pub fn seed(
+        &self,
+        loot_roll: &Pubkey,
+        slot_hashes: &[u8],
+        current_slot: u64,
+    ) -> Result<Option<Hash>, ProgramError> {
+        if current_slot <= self.slot {
+            msg!(
+                "The loot of slot {} can only be revealed in a later slot.",
+                self.slot
+            );
+            return Err(TokenRecipesError::LootRollNotRevealable.into());
+        }
+        if current_slot - self.slot > MAX_ENTRIES as u64 {
+            return Ok(None);
+        }
+
+        // The SlotHashes sysvar starts with the number of entries
+        // followed by (slot, hash) pairs, the most recent one being the first.
+        let slot_hash = slot_hashes
+            .get(8..)
+            .unwrap_or_default()
+            .chunks_exact(8 + 32)
+            .find(|entry| entry[..8] == self.slot.to_le_bytes())
+            .map(|entry| &entry[8..])
+            .ok_or(TokenRecipesError::LootRollNotRevealable)?;
+        Ok(Some(hashv(&[
+            slot_hash,
+            loot_roll.as_ref(),
+            &self.total_crafts.to_le_bytes(),
+        ])))
+    }
+
+    /// Roll every loot table with the given seed and
+    /// return how many times each of their entries was picked.
+    pub fn roll(&self, seed: &Hash) -> Vec<Vec<u64>> {
+        self.loot_tables
+            .iter()
+            .enumerate()
+            .map(|(index, loot_table)| {
+                let seed = hashv(&[seed.as_ref(), &(index as u64).to_le_bytes()]);
+                roll_loot_table(&loot_table.entries, loot_table.rolls, &seed)
+            })
+            .collect()
+    }
+
+    pub fn get(loot_roll: &AccountInfo) -> Result<Self, ProgramError> {
+        assert_writable("loot_roll", loot_roll)?;
+        assert_program_owner("loot_roll", loot_roll, &crate::id())?;
+        assert_account_key("loot_roll", loot_roll, Key::LootRoll)?;
+        let loot_roll_account = Self::load(loot_roll)?;
+        assert_pda(
+            "loot_roll",
+            loot_roll,
+            &crate::id(),
+            &Self::seeds(&loot_roll_account.recipe, &loot_roll_account.owner),
+        )?;
+        Ok(loot_roll_account)
+    }
+
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        let mut bytes: &[u8] = &(*account.data).borrow();
+        Self::deserialize(&mut bytes).map_err(|error| {
+            msg!("Error deserializing LootRoll account: {}", error);
+            TokenRecipesError::DeserializationError.into()
+        })
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        let mut bytes = Vec::with_capacity(account.data_len());
+        self.serialize(&mut bytes).map_err(|error| {
+            msg!("Error serializing LootRoll account: {}", error);
+            TokenRecipesError::SerializationError
+        })?;
+        write_account_data(account, &bytes);
+        Ok(())
+    }
+}
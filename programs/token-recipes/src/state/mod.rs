@@ -1,7 +1,12 @@
+pub mod craft_quote;
+pub mod crafter_record;
 pub mod delegated_ingredient;
 pub mod features;
 pub mod ingredient_input;
 pub mod ingredient_output;
 pub mod ingredient_record;
 pub mod key;
+pub mod loot_roll;
+pub mod program_config;
 pub mod recipe;
+pub mod recipe_header;
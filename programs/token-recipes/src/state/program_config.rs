@@ -0,0 +1,124 @@
+use crate::{
+    assertions::{
+        assert_account_key, assert_empty, assert_pda, assert_pda_with_bump, assert_program_owner,
+        assert_same_pubkeys, assert_signer, assert_writable,
+    },
+    error::TokenRecipesError,
+    state::key::Key,
+    utils::{create_account, write_account_data},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The global configuration of the program, storing the admin authority
+/// allowed to run the admin instructions.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+pub struct ProgramConfig {
+    pub key: Key,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 1;
+    pub const VERSION: u8 = 1;
+
+    pub fn seeds<'a>() -> Vec<&'a [u8]> {
+        vec!["program_config".as_bytes()]
+    }
+
+    pub fn create<'a>(
+        program_config: &AccountInfo<'a>,
+        admin: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        assert_empty("program_config", program_config)?;
+        assert_writable("program_config", program_config)?;
+        let program_config_bump = assert_pda(
+            "program_config",
+            program_config,
+            &crate::id(),
+            &Self::seeds(),
+        )?;
+
+        let mut seeds = Self::seeds();
+        let bump = [program_config_bump];
+        seeds.push(&bump);
+        create_account(
+            program_config,
+            payer,
+            system_program,
+            Self::LEN,
+            &crate::id(),
+            Some(&[&seeds]),
+        )?;
+        Ok(Self {
+            key: Key::ProgramConfig,
+            admin: *admin.key,
+            pending_admin: Pubkey::default(),
+            bump: program_config_bump,
+            version: Self::VERSION,
+        })
+    }
+
+    pub fn get(program_config: &AccountInfo) -> Result<Self, ProgramError> {
+        assert_program_owner("program_config", program_config, &crate::id())?;
+        assert_account_key("program_config", program_config, Key::ProgramConfig)?;
+        let program_config_account = Self::load(program_config)?;
+        assert_pda_with_bump(
+            "program_config",
+            program_config,
+            &crate::id(),
+            &Self::seeds(),
+            program_config_account.bump,
+        )?;
+        Ok(program_config_account)
+    }
+
+    pub fn get_writable(program_config: &AccountInfo) -> Result<Self, ProgramError> {
+        assert_writable("program_config", program_config)?;
+        Self::get(program_config)
+    }
+
+    pub fn assert_signer_admin(&self, admin: &AccountInfo) -> ProgramResult {
+        assert_same_pubkeys("admin", admin, &self.admin)?;
+        assert_signer("admin", admin)
+    }
+
+    /// Assert that the given account is the pending admin of the program
+    /// and that it signed the transaction.
+    pub fn assert_signer_pending_admin(&self, pending_admin: &AccountInfo) -> ProgramResult {
+        if self.pending_admin == Pubkey::default() {
+            msg!("Program config has no pending admin to accept");
+            return Err(TokenRecipesError::NoPendingAuthority.into());
+        }
+        assert_same_pubkeys("pending_admin", pending_admin, &self.pending_admin)?;
+        assert_signer("pending_admin", pending_admin)
+    }
+
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        let mut bytes: &[u8] = &(*account.data).borrow();
+        Self::deserialize(&mut bytes).map_err(|error| {
+            msg!("Error deserializing ProgramConfig account: {}", error);
+            TokenRecipesError::DeserializationError.into()
+        })
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        let mut bytes = Vec::with_capacity(account.data_len());
+        self.serialize(&mut bytes).map_err(|error| {
+            msg!("Error serializing ProgramConfig account: {}", error);
+            TokenRecipesError::SerializationError
+        })?;
+        write_account_data(account, &bytes);
+        Ok(())
+    }
+}
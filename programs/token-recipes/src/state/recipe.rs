@@ -1,22 +1,26 @@
 use crate::{
     assertions::{
-        assert_account_key, assert_program_owner, assert_same_pubkeys, assert_signer,
-        assert_writable,
+        assert_account_key, assert_pda, assert_program_owner, assert_same_pubkeys, assert_signer,
+        assert_token_account, assert_writable,
     },
     error::TokenRecipesError,
     state::{
         features::FeatureLevels, ingredient_input::IngredientInput,
-        ingredient_output::IngredientOutput, key::Key,
+        ingredient_output::IngredientOutput, key::Key, recipe_header::RecipeHeader,
     },
-    utils::realloc_account,
+    utils::{realloc_account, realloc_legacy_account, verify_merkle_proof},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
+use solana_program::keccak;
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+use spl_token_2022::state::Account;
 
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
@@ -32,6 +36,13 @@ pub struct Recipe {
     pub accumulated_shards: u64,
     pub accumulated_experience: u64,
     pub feature_levels: FeatureLevels,
+    pub version: u8,
+    pub bump: u8,
+    pub pending_authority: Pubkey,
+    pub max_crafts_per_wallet: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub allowlist_root: [u8; 32],
     pub inputs: Vec<IngredientInput>,
     pub outputs: Vec<IngredientOutput>,
 }
@@ -48,20 +59,81 @@ impl Recipe {
         + 8 // accumulated_shards
         + 8 // accumulated_experience
         + FeatureLevels::LEN // feature_levels
+        + 1 // version
+        + 1 // bump
+        + 32 // pending_authority
+        + 8 // max_crafts_per_wallet
+        + 8 // start_time
+        + 8 // end_time
+        + 32 // allowlist_root
         + 4 // inputs.len()
         + 4; // outputs.len()
+    pub const VERSION: u8 = 1;
+    /// The length of the header of recipes created before versions were stored, which
+    /// lacks the pending authority, the craft limit, the time window and the allowlist root.
+    pub const LEGACY_HEADER_LEN: usize = RecipeHeader::LEN - 32 - 8 - 8 - 8 - 32;
+    /// The version and the bump end the header of legacy recipes.
+    const VERSION_OFFSET: usize = Self::LEGACY_HEADER_LEN - 2;
 
+    /// Get a recipe to read it. Legacy recipes are decoded with their own layout.
     pub fn get(recipe: &AccountInfo) -> Result<Self, ProgramError> {
         assert_program_owner("recipe", recipe, &crate::id())?;
         assert_account_key("recipe", recipe, Key::Recipe)?;
         Recipe::load(recipe)
     }
 
+    /// Get a recipe to write it, which requires it to use the current layout.
     pub fn get_writable(recipe: &AccountInfo) -> Result<Self, ProgramError> {
         assert_writable("recipe", recipe)?;
+        Self::assert_migrated(recipe)?;
         Self::get(recipe)
     }
 
+    /// Whether the recipe uses the legacy layout, whose header is shorter than the current one.
+    pub fn is_legacy(recipe: &AccountInfo) -> Result<bool, ProgramError> {
+        Ok(recipe.try_borrow_data()?.get(Self::VERSION_OFFSET) == Some(&0))
+    }
+
+    /// Assert that the recipe does not use the legacy layout. Legacy recipes must be
+    /// migrated using MigrateAccount or an instruction with a payer before being written.
+    pub fn assert_migrated(recipe: &AccountInfo) -> ProgramResult {
+        if Self::is_legacy(recipe)? {
+            msg!(
+                "Recipe [{}] must be migrated using MigrateAccount",
+                recipe.key
+            );
+            return Err(TokenRecipesError::RecipeMustBeMigrated.into());
+        }
+        Ok(())
+    }
+
+    /// Migrate a legacy recipe in place so it can be written, the payer funding the rent
+    /// of the fields appended to its header. Other recipes are left untouched.
+    pub fn migrate<'a>(
+        recipe: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if !Self::is_legacy(recipe)? {
+            return Ok(());
+        }
+        assert_writable("recipe", recipe)?;
+        let recipe_account = Self::get(recipe)?;
+        realloc_legacy_account(recipe, payer, system_program, recipe_account.size())?;
+        recipe_account.save(recipe)
+    }
+
+    /// The size of the recipe account, i.e. its Borsh-serialized size.
+    pub fn size(&self) -> usize {
+        Self::INITIAL_LEN
+            + self.inputs.iter().map(|input| input.len()).sum::<usize>()
+            + self
+                .outputs
+                .iter()
+                .map(|output| output.len())
+                .sum::<usize>()
+    }
+
     pub fn assert_authority(&self, authority: &AccountInfo) -> ProgramResult {
         assert_same_pubkeys("authority", authority, &self.authority)
     }
@@ -71,24 +143,127 @@ impl Recipe {
         assert_signer("authority", authority)
     }
 
+    /// Assert that the given account is the pending authority of the recipe
+    /// and that it signed the transaction.
+    pub fn assert_signer_pending_authority(
+        &self,
+        pending_authority: &AccountInfo,
+    ) -> ProgramResult {
+        if self.pending_authority == Pubkey::default() {
+            msg!("Recipe has no pending authority to accept");
+            return Err(TokenRecipesError::NoPendingAuthority.into());
+        }
+        assert_same_pubkeys(
+            "pending_authority",
+            pending_authority,
+            &self.pending_authority,
+        )?;
+        assert_signer("pending_authority", pending_authority)
+    }
+
+    /// Get the distinct mints whose mint authority was delegated to the program by the
+    /// outputs of this recipe. This excludes the mints that are transferred from a vault.
+    pub fn delegated_mints(&self) -> Vec<Pubkey> {
+        let mut mints: Vec<Pubkey> = vec![];
+        for mint in self.delegated_mint_outputs() {
+            if !mints.contains(&mint) {
+                mints.push(mint);
+            }
+        }
+        mints
+    }
+
+    /// Get how many outputs of this recipe use the delegated ingredient of the given mint.
+    pub fn delegated_mint_uses(&self, mint: &Pubkey) -> u32 {
+        self.delegated_mint_outputs()
+            .filter(|output_mint| output_mint == mint)
+            .count() as u32
+    }
+
+    fn delegated_mint_outputs(&self) -> impl Iterator<Item = Pubkey> + '_ {
+        self.outputs.iter().flat_map(|output| match output {
+            IngredientOutput::MintToken { mint, .. }
+            | IngredientOutput::MintTokenWithMaxSupply { mint, .. } => vec![*mint],
+            IngredientOutput::MintTokenFromLootTable { entries, .. } => {
+                entries.iter().map(|entry| entry.mint).collect()
+            }
+            IngredientOutput::TransferToken { .. } => vec![],
+        })
+    }
+
+    /// Assert that the given account is the vault of the recipe for the given mint,
+    /// i.e. the associated token account of the recipe, and return its parsed content.
+    pub fn assert_vault(
+        &self,
+        vault: &AccountInfo,
+        recipe: &AccountInfo,
+        mint: &AccountInfo,
+    ) -> Result<Account, ProgramError> {
+        assert_pda(
+            "vault",
+            vault,
+            &spl_associated_token_account::id(),
+            &[recipe.key.as_ref(), mint.owner.as_ref(), mint.key.as_ref()],
+        )?;
+        let vault_account = assert_token_account("vault", vault)?;
+        assert_program_owner("vault", vault, mint.owner)?;
+        assert_same_pubkeys("mint", mint, &vault_account.mint)?;
+        assert_same_pubkeys("recipe", recipe, &vault_account.owner)?;
+        Ok(vault_account)
+    }
+
     pub fn assert_active(&self) -> ProgramResult {
         if !matches!(self.status, RecipeStatus::Active) {
-            Err(TokenRecipesError::RecipeIsNotActive.into())
+            return Err(TokenRecipesError::RecipeIsNotActive.into());
+        }
+
+        // Check the time window, a zero timestamp means there is no limit.
+        if self.start_time == 0 && self.end_time == 0 {
+            return Ok(());
+        }
+        let now = Clock::get()?.unix_timestamp;
+        if self.start_time != 0 && now < self.start_time {
+            msg!("Recipe starts at {} but it is {}", self.start_time, now);
+            Err(TokenRecipesError::RecipeHasNotStarted.into())
+        } else if self.end_time != 0 && now >= self.end_time {
+            msg!("Recipe ended at {} but it is {}", self.end_time, now);
+            Err(TokenRecipesError::RecipeHasEnded.into())
         } else {
             Ok(())
         }
     }
 
+    /// Assert that the owner is part of the allowlist of the recipe, if any,
+    /// by verifying the provided merkle proof against the allowlist root.
+    pub fn assert_allowlisted(&self, owner: &AccountInfo, proof: &[[u8; 32]]) -> ProgramResult {
+        if self.allowlist_root == [0; 32] {
+            return Ok(());
+        }
+        let leaf = keccak::hashv(&[owner.key.as_ref()]).to_bytes();
+        if verify_merkle_proof(&self.allowlist_root, leaf, proof) {
+            Ok(())
+        } else {
+            msg!(
+                "Owner [{}] is not part of the allowlist of this recipe",
+                owner.key
+            );
+            Err(TokenRecipesError::NotInAllowlist.into())
+        }
+    }
+
     pub fn find_ingredient(
         &self,
         ingredient_type: IngredientType,
         mint: &AccountInfo,
     ) -> Result<(Ingredient, usize), ProgramError> {
         match ingredient_type {
-            IngredientType::BurnTokenInput | IngredientType::TransferTokenInput => {
+            IngredientType::BurnTokenInput
+            | IngredientType::TransferTokenInput
+            | IngredientType::HoldTokenInput => {
                 let maybe_index = self.inputs.iter().position(|i| match i {
                     IngredientInput::BurnToken { mint: m, .. }
-                    | IngredientInput::TransferToken { mint: m, .. } => m == mint.key,
+                    | IngredientInput::TransferToken { mint: m, .. }
+                    | IngredientInput::HoldToken { mint: m, .. } => m == mint.key,
                     _ => false,
                 });
                 match maybe_index {
@@ -118,10 +293,14 @@ impl Recipe {
                     }
                 }
             }
-            IngredientType::MintTokenOutput | IngredientType::MintTokenWithMaxSupplyOutput => {
+            IngredientType::MintTokenOutput
+            | IngredientType::MintTokenWithMaxSupplyOutput
+            | IngredientType::TransferTokenOutput => {
                 let maybe_index = self.outputs.iter().position(|i| match i {
                     IngredientOutput::MintToken { mint: m, .. }
-                    | IngredientOutput::MintTokenWithMaxSupply { mint: m, .. } => m == mint.key,
+                    | IngredientOutput::MintTokenWithMaxSupply { mint: m, .. }
+                    | IngredientOutput::TransferToken { mint: m, .. } => m == mint.key,
+                    _ => false,
                 });
                 match maybe_index {
                     Some(index) => Ok((Ingredient::Output(self.outputs[index].clone()), index)),
@@ -163,6 +342,34 @@ impl Recipe {
         self.save(recipe)
     }
 
+    pub fn update_ingredient_input<'a>(
+        &mut self,
+        index: usize,
+        ingredient: &IngredientInput,
+        recipe: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let old_ingredient = std::mem::replace(&mut self.inputs[index], ingredient.clone());
+        let new_size = recipe.data_len() - old_ingredient.len() + ingredient.len();
+        realloc_account(recipe, payer, system_program, new_size, true)?;
+        self.save(recipe)
+    }
+
+    pub fn update_ingredient_output<'a>(
+        &mut self,
+        index: usize,
+        ingredient: &IngredientOutput,
+        recipe: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let old_ingredient = std::mem::replace(&mut self.outputs[index], ingredient.clone());
+        let new_size = recipe.data_len() - old_ingredient.len() + ingredient.len();
+        realloc_account(recipe, payer, system_program, new_size, true)?;
+        self.save(recipe)
+    }
+
     pub fn remove_ingredient_input<'a>(
         &mut self,
         index: usize,
@@ -220,11 +427,21 @@ impl Recipe {
     }
 
     pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
-        let mut bytes: &[u8] = &(*account.data).borrow();
-        Self::deserialize(&mut bytes).map_err(|error| {
+        let mut recipe = deserialize_recipe(&account.data.borrow()).map_err(|error| {
             msg!("Error deserializing Recipe account: {}", error);
-            TokenRecipesError::DeserializationError.into()
-        })
+            TokenRecipesError::DeserializationError
+        })?;
+        // Upgrade recipes created with an older layout. The upgrade
+        // is stored the next time the recipe is saved.
+        if recipe.version < Self::VERSION {
+            // Recipes created before the bump was stored have a zero bump.
+            if recipe.bump == 0 {
+                recipe.bump =
+                    Pubkey::find_program_address(&Self::seeds(&recipe.base), &crate::id()).1;
+            }
+            recipe.version = Self::VERSION;
+        }
+        Ok(recipe)
     }
 
     pub fn save(&self, account: &AccountInfo) -> ProgramResult {
@@ -236,6 +453,72 @@ impl Recipe {
         account.try_borrow_mut_data().unwrap()[..bytes.len()].copy_from_slice(&bytes);
         Ok(())
     }
+
+    /// Write the header of the recipe in place, leaving the ingredients untouched.
+    /// Only use this when the ingredients have not been modified.
+    pub fn save_header(&self, account: &AccountInfo) -> ProgramResult {
+        *RecipeHeader::load_mut(account)? = RecipeHeader::from(self);
+        Ok(())
+    }
+}
+
+/// Deserialize a recipe account created with any version of its layout.
+///
+/// Recipes created before versions were stored use the `RecipeV0` layout. Their version
+/// and bump bytes were part of the feature levels padding so their version is zero.
+pub fn deserialize_recipe(data: &[u8]) -> std::io::Result<Recipe> {
+    match data.get(Recipe::VERSION_OFFSET) {
+        Some(0) => RecipeV0::deserialize(&mut &data[..]).map(Recipe::from),
+        _ => Recipe::deserialize(&mut &data[..]),
+    }
+}
+
+/// The layout of recipes created before versions were stored, i.e. without
+/// the fields that were appended to the header of the `Recipe` account.
+#[derive(BorshDeserialize)]
+struct RecipeV0 {
+    key: Key,
+    base: Pubkey,
+    authority: Pubkey,
+    status: RecipeStatus,
+    total_crafts: u64,
+    total_crafts_with_quantity: u64,
+    fees: u64,
+    accumulated_admin_fees: u64,
+    accumulated_shards: u64,
+    accumulated_experience: u64,
+    feature_levels: FeatureLevels,
+    version: u8,
+    bump: u8,
+    inputs: Vec<IngredientInput>,
+    outputs: Vec<IngredientOutput>,
+}
+
+impl From<RecipeV0> for Recipe {
+    fn from(recipe: RecipeV0) -> Self {
+        Self {
+            key: recipe.key,
+            base: recipe.base,
+            authority: recipe.authority,
+            status: recipe.status,
+            total_crafts: recipe.total_crafts,
+            total_crafts_with_quantity: recipe.total_crafts_with_quantity,
+            fees: recipe.fees,
+            accumulated_admin_fees: recipe.accumulated_admin_fees,
+            accumulated_shards: recipe.accumulated_shards,
+            accumulated_experience: recipe.accumulated_experience,
+            feature_levels: recipe.feature_levels,
+            version: recipe.version,
+            bump: recipe.bump,
+            pending_authority: Pubkey::default(),
+            max_crafts_per_wallet: 0,
+            start_time: 0,
+            end_time: 0,
+            allowlist_root: [0; 32],
+            inputs: recipe.inputs,
+            outputs: recipe.outputs,
+        }
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
@@ -255,6 +538,8 @@ pub enum IngredientType {
     MintTokenOutput,
     MintTokenWithMaxSupplyOutput,
     TransferSolInput,
+    TransferTokenOutput,
+    HoldTokenInput,
 }
 
 pub enum Ingredient {
@@ -0,0 +1,137 @@
+use crate::{
+    assertions::{
+        assert_account_key, assert_program_owner, assert_same_pubkeys, assert_signer,
+        assert_writable,
+    },
+    error::TokenRecipesError,
+    state::{
+        features::FeatureLevels,
+        key::Key,
+        recipe::{Recipe, RecipeStatus},
+    },
+};
+use bytemuck::{Pod, Zeroable};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_token_2022::pod::{PodI64, PodU64};
+use std::cell::{Ref, RefMut};
+
+/// The fixed-size fields at the start of a recipe account, i.e. everything but the ingredients.
+///
+/// The header shares the Borsh layout of the `Recipe` account so it can be read
+/// and written in place without deserializing or re-serializing the ingredients.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct RecipeHeader {
+    pub key: u8,
+    pub base: Pubkey,
+    pub authority: Pubkey,
+    pub status: u8,
+    pub total_crafts: PodU64,
+    pub total_crafts_with_quantity: PodU64,
+    pub fees: PodU64,
+    pub accumulated_admin_fees: PodU64,
+    pub accumulated_shards: PodU64,
+    pub accumulated_experience: PodU64,
+    pub feature_levels: FeatureLevels,
+    pub version: u8,
+    pub bump: u8,
+    pub pending_authority: Pubkey,
+    pub max_crafts_per_wallet: PodU64,
+    pub start_time: PodI64,
+    pub end_time: PodI64,
+    pub allowlist_root: [u8; 32],
+}
+
+// Safety: all fields have an alignment of 1, so the struct has no padding
+// and any bit pattern is valid.
+unsafe impl Zeroable for RecipeHeader {}
+unsafe impl Pod for RecipeHeader {}
+
+impl RecipeHeader {
+    pub const LEN: usize = std::mem::size_of::<Self>();
+
+    pub fn get<'a>(recipe: &'a AccountInfo) -> Result<Ref<'a, Self>, ProgramError> {
+        assert_program_owner("recipe", recipe, &crate::id())?;
+        assert_account_key("recipe", recipe, Key::Recipe)?;
+        Recipe::assert_migrated(recipe)?;
+        Self::load(recipe)
+    }
+
+    pub fn get_writable<'a>(recipe: &'a AccountInfo) -> Result<RefMut<'a, Self>, ProgramError> {
+        assert_writable("recipe", recipe)?;
+        assert_program_owner("recipe", recipe, &crate::id())?;
+        assert_account_key("recipe", recipe, Key::Recipe)?;
+        Recipe::assert_migrated(recipe)?;
+        Self::load_mut(recipe)
+    }
+
+    pub fn load<'a>(account: &'a AccountInfo) -> Result<Ref<'a, Self>, ProgramError> {
+        let data = account.try_borrow_data()?;
+        if data.len() < Self::LEN {
+            msg!("Error deserializing Recipe header: account data is too small");
+            return Err(TokenRecipesError::DeserializationError.into());
+        }
+        Ok(Ref::map(data, |data| {
+            bytemuck::from_bytes(&data[..Self::LEN])
+        }))
+    }
+
+    pub fn load_mut<'a>(account: &'a AccountInfo) -> Result<RefMut<'a, Self>, ProgramError> {
+        let data = account.try_borrow_mut_data()?;
+        if data.len() < Self::LEN {
+            msg!("Error deserializing Recipe header: account data is too small");
+            return Err(TokenRecipesError::DeserializationError.into());
+        }
+        Ok(RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut(&mut data[..Self::LEN])
+        }))
+    }
+
+    pub fn status(&self) -> Result<RecipeStatus, ProgramError> {
+        match self.status {
+            0 => Ok(RecipeStatus::Paused),
+            1 => Ok(RecipeStatus::Active),
+            _ => {
+                msg!("Invalid recipe status {}", self.status);
+                Err(TokenRecipesError::DeserializationError.into())
+            }
+        }
+    }
+
+    pub fn set_status(&mut self, status: RecipeStatus) {
+        self.status = status as u8;
+    }
+
+    pub fn assert_signer_authority(&self, authority: &AccountInfo) -> ProgramResult {
+        assert_same_pubkeys("authority", authority, &self.authority)?;
+        assert_signer("authority", authority)
+    }
+}
+
+impl From<&Recipe> for RecipeHeader {
+    fn from(recipe: &Recipe) -> Self {
+        Self {
+            key: recipe.key as u8,
+            base: recipe.base,
+            authority: recipe.authority,
+            status: recipe.status.clone() as u8,
+            total_crafts: recipe.total_crafts.into(),
+            total_crafts_with_quantity: recipe.total_crafts_with_quantity.into(),
+            fees: recipe.fees.into(),
+            accumulated_admin_fees: recipe.accumulated_admin_fees.into(),
+            accumulated_shards: recipe.accumulated_shards.into(),
+            accumulated_experience: recipe.accumulated_experience.into(),
+            feature_levels: recipe.feature_levels,
+            version: recipe.version,
+            bump: recipe.bump,
+            pending_authority: recipe.pending_authority,
+            max_crafts_per_wallet: recipe.max_crafts_per_wallet.into(),
+            start_time: recipe.start_time.into(),
+            end_time: recipe.end_time.into(),
+            allowlist_root: recipe.allowlist_root,
+        }
+    }
+}
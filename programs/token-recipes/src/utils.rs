@@ -1,16 +1,37 @@
+use borsh::BorshDeserialize;
 use solana_program::{
-    account_info::AccountInfo,
+    account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    keccak,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
     sysvar::Sysvar,
 };
-use spl_token::instruction::{set_authority, AuthorityType};
+use spl_token_2022::{
+    extension::ExtensionType,
+    instruction::{set_authority, AuthorityType},
+    state::Account,
+};
+
+use crate::{
+    assertions::{assert_pda, assert_same_pubkeys},
+    error::TokenRecipesError,
+};
+
+/// The Token-2022 extension type of transfer hooks on mint accounts.
+/// It is not yet exposed by the version of `spl-token-2022` we depend on.
+pub const TRANSFER_HOOK_EXTENSION_TYPE: u16 = 14;
 
-use crate::error::TokenRecipesError;
+/// The seed of the PDA listing the extra accounts required by a transfer hook.
+pub const TRANSFER_HOOK_VALIDATION_SEED: &str = "extra-account-metas";
+
+/// The memo attached to token transfers when the destination requires one.
+pub const TRANSFER_MEMO: &str = "Token Recipes";
 
 /// Create a new account from the given size.
 #[inline(always)]
@@ -72,6 +93,49 @@ pub fn realloc_account<'a>(
     target_account.realloc(new_size, false)
 }
 
+/// Resize an account created with an older layout to the size of the current layout.
+pub fn realloc_legacy_account<'a>(
+    target_account: &AccountInfo<'a>,
+    funding_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    size: usize,
+) -> ProgramResult {
+    if target_account.data_len() >= size {
+        return Ok(());
+    }
+    realloc_account(target_account, funding_account, system_program, size, false)
+}
+
+/// Deserialize a fixed-size account that may have been created with an older layout.
+/// Fields are only ever appended to fixed-size accounts, so accounts created with
+/// an older layout are shorter than the given size and their missing bytes are read
+/// as zeros. Accounts shorter than the size of the first layout are rejected.
+pub fn deserialize_account<T: BorshDeserialize>(
+    data: &[u8],
+    size: usize,
+    legacy_size: usize,
+) -> std::io::Result<T> {
+    if data.len() >= size {
+        T::deserialize(&mut &data[..])
+    } else if data.len() >= legacy_size {
+        let mut bytes = data.to_vec();
+        bytes.resize(size, 0);
+        T::try_from_slice(&bytes)
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "account data is smaller than any known layout",
+        ))
+    }
+}
+
+/// Write the serialized content of an account. The fields that do not fit
+/// in accounts created with an older layout are left out until they are resized.
+pub fn write_account_data(account: &AccountInfo, bytes: &[u8]) {
+    let len = bytes.len().min(account.data_len());
+    account.try_borrow_mut_data().unwrap()[..len].copy_from_slice(&bytes[..len]);
+}
+
 /// Close an account.
 #[inline(always)]
 pub fn close_account<'a>(
@@ -128,14 +192,14 @@ pub fn transfer_lamports_from_pdas<'a>(
 pub fn transfer_mint_authority<'a>(
     mint: &AccountInfo<'a>,
     from: &AccountInfo<'a>,
-    to: &AccountInfo<'a>,
+    to: &Pubkey,
     signer_seeds: Option<&[&[&[u8]]]>,
 ) -> ProgramResult {
     invoke_signed(
         &set_authority(
-            &spl_token::id(),
+            mint.owner,
             mint.key,
-            Some(to.key),
+            Some(to),
             AuthorityType::MintTokens,
             from.key,
             &[],
@@ -158,7 +222,7 @@ pub fn create_associated_token_account<'a>(
             payer_account.key,
             owner_account.key,
             mint_account.key,
-            &spl_token::id(),
+            mint_account.owner,
         ),
         &[
             payer_account.clone(),
@@ -179,8 +243,8 @@ pub fn burn_tokens<'a>(
     decimals: u8,
 ) -> ProgramResult {
     invoke(
-        &spl_token::instruction::burn_checked(
-            &spl_token::id(),
+        &spl_token_2022::instruction::burn_checked(
+            mint_account.owner,
             token_account.key,
             mint_account.key,
             owner_account.key,
@@ -207,8 +271,8 @@ pub fn mint_tokens<'a>(
     signer_seeds: Option<&[&[&[u8]]]>,
 ) -> ProgramResult {
     invoke_signed(
-        &spl_token::instruction::mint_to_checked(
-            &spl_token::id(),
+        &spl_token_2022::instruction::mint_to_checked(
+            mint_account.owner,
             mint_account.key,
             token_account.key,
             mint_authority_account.key,
@@ -226,33 +290,157 @@ pub fn mint_tokens<'a>(
 }
 
 /// Transfer tokens.
+/// Any additional accounts required by Token-2022 extensions
+/// (see `next_transfer_additional_accounts`) are forwarded to the token program.
 #[inline(always)]
 pub fn transfer_tokens<'a>(
     mint_account: &AccountInfo<'a>,
     from_owner_account: &AccountInfo<'a>,
     from_token_account: &AccountInfo<'a>,
     to_token_account: &AccountInfo<'a>,
+    additional_accounts: &[&AccountInfo<'a>],
     amount: u64,
     decimals: u8,
     signer_seeds: Option<&[&[&[u8]]]>,
 ) -> ProgramResult {
-    invoke_signed(
-        &spl_token::instruction::transfer_checked(
-            &spl_token::id(),
-            from_token_account.key,
-            mint_account.key,
-            to_token_account.key,
-            from_owner_account.key,
-            &[],
-            amount,
-            decimals,
-        )?,
-        &[
-            from_owner_account.clone(),
-            from_token_account.clone(),
-            to_token_account.clone(),
-            mint_account.clone(),
-        ],
-        signer_seeds.unwrap_or(&[]),
-    )
+    if is_memo_required(to_token_account) {
+        invoke(&spl_memo::build_memo(TRANSFER_MEMO.as_bytes(), &[]), &[])?;
+    }
+
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        mint_account.owner,
+        from_token_account.key,
+        mint_account.key,
+        to_token_account.key,
+        from_owner_account.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    let mut account_infos = vec![
+        from_owner_account.clone(),
+        from_token_account.clone(),
+        to_token_account.clone(),
+        mint_account.clone(),
+    ];
+    for account in additional_accounts {
+        instruction.accounts.push(match account.is_writable {
+            true => AccountMeta::new(*account.key, false),
+            false => AccountMeta::new_readonly(*account.key, false),
+        });
+        account_infos.push((*account).clone());
+    }
+
+    invoke_signed(&instruction, &account_infos, signer_seeds.unwrap_or(&[]))
+}
+
+/// Get the raw data of a Token-2022 extension on a mint or token account.
+/// The TLV entries are parsed manually so that extensions unknown to our
+/// version of `spl-token-2022` do not prevent us from reading the others.
+pub fn get_token_extension(account: &AccountInfo, extension_type: u16) -> Option<Vec<u8>> {
+    if *account.owner != spl_token_2022::id() {
+        return None;
+    }
+    find_token_extension(&account.data.borrow(), extension_type).map(|value| value.to_vec())
+}
+
+/// Find the value of the given extension in the raw data of a Token-2022 mint or token account.
+pub fn find_token_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    // Extensions start after the base account (mints are padded) and the account type.
+    let mut index = Account::LEN + 1;
+    while index + 4 <= data.len() {
+        let current_type = u16::from_le_bytes([data[index], data[index + 1]]);
+        let length = u16::from_le_bytes([data[index + 2], data[index + 3]]) as usize;
+        let value_start = index + 4;
+        let value_end = value_start + length;
+        if current_type == ExtensionType::Uninitialized as u16 || value_end > data.len() {
+            return None;
+        }
+        if current_type == extension_type {
+            return Some(&data[value_start..value_end]);
+        }
+        index = value_end;
+    }
+    None
+}
+
+/// Whether the given token account requires a memo on incoming transfers.
+pub fn is_memo_required(token_account: &AccountInfo) -> bool {
+    get_token_extension(token_account, ExtensionType::MemoTransfer as u16)
+        .map(|data| data.first() == Some(&1))
+        .unwrap_or(false)
+}
+
+/// Get the transfer hook program of the given mint, if any.
+pub fn get_transfer_hook_program_id(mint: &AccountInfo) -> Option<Pubkey> {
+    get_token_extension(mint, TRANSFER_HOOK_EXTENSION_TYPE)
+        .and_then(|data| <[u8; 32]>::try_from(data.get(32..64)?).ok())
+        .map(Pubkey::new_from_array)
+        .filter(|program_id| *program_id != Pubkey::default())
+}
+
+/// Get the next accounts required to transfer tokens of the given mint
+/// to the given destination token account. That is:
+///   - The SPL Memo program, if the destination requires incoming transfer memos.
+///   - The transfer hook program, its validation account and all the extra
+///     accounts listed in that validation account, if the mint has a transfer hook.
+pub fn next_transfer_additional_accounts<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+    account_info_iter: &mut I,
+    mint: &AccountInfo<'a>,
+    destination_token: &AccountInfo<'a>,
+) -> Result<Vec<&'a AccountInfo<'a>>, ProgramError> {
+    let mut additional_accounts = vec![];
+
+    // Memo program.
+    if is_memo_required(destination_token) {
+        let memo_program = next_account_info(account_info_iter)?;
+        assert_same_pubkeys("memo_program", memo_program, &spl_memo::id())?;
+        additional_accounts.push(memo_program);
+    }
+
+    // Transfer hook accounts.
+    if let Some(transfer_hook_program_id) = get_transfer_hook_program_id(mint) {
+        let transfer_hook_program = next_account_info(account_info_iter)?;
+        let validation = next_account_info(account_info_iter)?;
+        assert_same_pubkeys(
+            "transfer_hook_program",
+            transfer_hook_program,
+            &transfer_hook_program_id,
+        )?;
+        assert_pda(
+            "transfer_hook_validation",
+            validation,
+            &transfer_hook_program_id,
+            &[TRANSFER_HOOK_VALIDATION_SEED.as_bytes(), mint.key.as_ref()],
+        )?;
+
+        // The validation account stores a TLV entry whose value
+        // starts with the number of extra accounts required.
+        let extra_accounts_count = validation
+            .data
+            .borrow()
+            .get(12..16)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .unwrap_or(0);
+
+        additional_accounts.push(transfer_hook_program);
+        additional_accounts.push(validation);
+        for _ in 0..extra_accounts_count {
+            additional_accounts.push(next_account_info(account_info_iter)?);
+        }
+    }
+
+    Ok(additional_accounts)
+}
+
+/// Verify a merkle proof where each pair of nodes is hashed in sorted order using keccak.
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed_root = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed_root == *root
 }
@@ -0,0 +1,114 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use assert_matches::assert_matches;
+use solana_program_test::tokio;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::find_fees_feature_pda,
+    state::{features::Feature, key::Key, recipe::RecipeStatus},
+};
+
+#[tokio::test]
+async fn activate_and_pause_a_recipe() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let payer = context.payer.pubkey();
+
+    utils::activate_recipe(&mut context, &recipe).await;
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_matches!(recipe_account.status, RecipeStatus::Active);
+
+    let ix = token_recipes::instruction::pause_recipe(&recipe, &payer);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_matches!(recipe_account.status, RecipeStatus::Paused);
+}
+
+#[tokio::test]
+async fn cannot_activate_a_recipe_as_another_authority() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let authority = Keypair::new();
+
+    let ix = token_recipes::instruction::activate_recipe(&recipe, &authority.pubkey());
+    let result = utils::process(&mut context, &[ix], &[&authority]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
+
+#[tokio::test]
+async fn cannot_activate_a_recipe_without_the_authority_signature() {
+    let mut context = utils::start().await;
+    let authority = Keypair::new();
+    let recipe = utils::create_recipe_with_authority(&mut context, &authority.pubkey()).await;
+
+    let mut ix = token_recipes::instruction::activate_recipe(&recipe, &authority.pubkey());
+    ix.accounts[1].is_signer = false;
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::ExpectedSignerAccount);
+}
+
+#[tokio::test]
+async fn cannot_activate_a_read_only_recipe() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let payer = context.payer.pubkey();
+
+    let mut ix = token_recipes::instruction::activate_recipe(&recipe, &payer);
+    ix.accounts[0] = AccountMeta::new_readonly(recipe, false);
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::ExpectedWritableAccount);
+}
+
+#[tokio::test]
+async fn cannot_activate_an_account_owned_by_another_program() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+
+    let ix = token_recipes::instruction::activate_recipe(&payer, &payer);
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidProgramOwner);
+}
+
+#[tokio::test]
+async fn cannot_activate_an_account_that_is_not_a_recipe() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    utils::set_feature(&mut context, Feature::Fees(utils::fees_feature())).await;
+    let (fees_feature, _) = find_fees_feature_pda();
+
+    let ix = token_recipes::instruction::activate_recipe(&fees_feature, &payer);
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidAccountKey);
+}
+
+#[tokio::test]
+async fn cannot_activate_a_recipe_with_truncated_data() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = Pubkey::new_unique();
+    let account = Account {
+        lamports: 1_000_000_000,
+        data: vec![Key::Recipe as u8; 32],
+        owner: token_recipes::id(),
+        ..Account::default()
+    };
+    context.set_account(&recipe, &AccountSharedData::from(account));
+
+    let ix = token_recipes::instruction::activate_recipe(&recipe, &payer);
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::DeserializationError);
+}
@@ -0,0 +1,371 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshDeserialize;
+use solana_program::program_option::COption;
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{find_delegated_ingredient_pda, find_ingredient_record_pda},
+    state::{
+        delegated_ingredient::DelegatedIngredient, features::FeatureLevels,
+        ingredient_input::IngredientInput, ingredient_output::IngredientOutput,
+        ingredient_record::IngredientRecord, recipe::IngredientType,
+    },
+};
+
+#[tokio::test]
+async fn add_every_type_of_ingredient() {
+    // Given a recipe with every feature unlocked.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels = FeatureLevels {
+            additional_outputs: 3,
+            transfer_inputs: 3,
+            max_supply: 1,
+            sol_payment: 11,
+            ..FeatureLevels::default()
+        }
+    })
+    .await;
+    let mints = [
+        utils::create_mint(&mut context, &payer).await,
+        utils::create_mint(&mut context, &payer).await,
+        utils::create_mint(&mut context, &payer).await,
+        utils::create_mint(&mut context, &payer).await,
+        utils::create_mint(&mut context, &payer).await,
+        utils::create_mint(&mut context, &payer).await,
+    ];
+    let destination = Pubkey::new_unique();
+
+    // When we add one ingredient of each type.
+    let ingredients = [
+        (Some(&mints[0]), IngredientType::BurnTokenInput, None, None),
+        (
+            Some(&mints[1]),
+            IngredientType::TransferTokenInput,
+            Some(destination),
+            None,
+        ),
+        (
+            None,
+            IngredientType::TransferSolInput,
+            Some(destination),
+            None,
+        ),
+        (Some(&mints[2]), IngredientType::HoldTokenInput, None, None),
+        (Some(&mints[3]), IngredientType::MintTokenOutput, None, None),
+        (
+            Some(&mints[4]),
+            IngredientType::MintTokenWithMaxSupplyOutput,
+            None,
+            Some(100),
+        ),
+        (
+            Some(&mints[5]),
+            IngredientType::TransferTokenOutput,
+            None,
+            None,
+        ),
+    ];
+    for (mint, ingredient_type, destination, max_supply) in ingredients {
+        utils::add_ingredient(
+            &mut context,
+            &recipe,
+            mint,
+            ingredient_type,
+            2,
+            destination,
+            max_supply,
+        )
+        .await
+        .unwrap();
+    }
+
+    // Then the recipe contains all ingredients in order.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        recipe_account.inputs,
+        vec![
+            IngredientInput::BurnToken {
+                mint: mints[0],
+                amount: 2
+            },
+            IngredientInput::TransferToken {
+                mint: mints[1],
+                amount: 2,
+                destination
+            },
+            IngredientInput::TransferSol {
+                lamports: 2,
+                destination
+            },
+            IngredientInput::HoldToken {
+                mint: mints[2],
+                amount: 2
+            },
+        ]
+    );
+    assert_eq!(
+        recipe_account.outputs,
+        vec![
+            IngredientOutput::MintToken {
+                mint: mints[3],
+                amount: 2
+            },
+            IngredientOutput::MintTokenWithMaxSupply {
+                mint: mints[4],
+                amount: 2,
+                max_supply: 100
+            },
+            IngredientOutput::TransferToken {
+                mint: mints[5],
+                amount: 2
+            },
+        ]
+    );
+
+    // And every token ingredient has an ingredient record.
+    for (index, mint) in mints.iter().enumerate() {
+        let (ingredient_record, bump) = find_ingredient_record_pda(mint, &recipe);
+        let account = utils::get_account(&mut context, &ingredient_record)
+            .await
+            .unwrap();
+        let record = IngredientRecord::deserialize(&mut account.data.as_ref()).unwrap();
+        assert_eq!(record.mint, *mint);
+        assert_eq!(record.recipe, recipe);
+        assert_eq!(record.input, index < 3);
+        assert_eq!(record.output, index >= 3);
+        assert_eq!(record.bump, bump);
+    }
+
+    // And minted outputs are delegated to the program.
+    for mint in &mints[3..5] {
+        let (delegated_ingredient, _) = find_delegated_ingredient_pda(mint);
+        let account = utils::get_account(&mut context, &delegated_ingredient)
+            .await
+            .unwrap();
+        let delegated = DelegatedIngredient::deserialize(&mut account.data.as_ref()).unwrap();
+        assert_eq!(delegated.authority, payer);
+        assert_eq!(delegated.counter, 1);
+        let mint_account = utils::get_mint(&mut context, mint).await;
+        assert_eq!(
+            mint_account.mint_authority,
+            COption::Some(delegated_ingredient)
+        );
+    }
+}
+
+#[tokio::test]
+async fn cannot_add_an_ingredient_with_zero_amount() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        IngredientType::BurnTokenInput,
+        0,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::CannotAddIngredientWithZeroAmount);
+}
+
+#[tokio::test]
+async fn cannot_add_the_same_ingredient_twice() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let burn_input = IngredientType::BurnTokenInput;
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        2,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::IngredientAlreadyAdded);
+}
+
+#[tokio::test]
+async fn cannot_add_two_sol_payments() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.sol_payment = 11;
+    })
+    .await;
+    let destination = Some(Pubkey::new_unique());
+    let sol_input = IngredientType::TransferSolInput;
+    utils::add_ingredient(&mut context, &recipe, None, sol_input, 1, destination, None)
+        .await
+        .unwrap();
+
+    let sol_input = IngredientType::TransferSolInput;
+    let result =
+        utils::add_ingredient(&mut context, &recipe, None, sol_input, 2, destination, None).await;
+
+    utils::assert_program_error(result, TokenRecipesError::IngredientAlreadyAdded);
+}
+
+#[tokio::test]
+async fn cannot_add_a_transfer_without_destination() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        IngredientType::TransferTokenInput,
+        1,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::MissingDestinationArgument);
+}
+
+#[tokio::test]
+async fn cannot_add_a_max_supply_output_without_max_supply() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        IngredientType::MintTokenWithMaxSupplyOutput,
+        1,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::MissingMaxSupplyArgument);
+}
+
+#[tokio::test]
+async fn cannot_add_a_mint_output_without_its_mint_authority() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &Pubkey::new_unique()).await;
+
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        IngredientType::MintTokenOutput,
+        1,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidMintAuthority);
+}
+
+#[tokio::test]
+async fn cannot_add_an_ingredient_with_another_token_program() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+
+    let ix = token_recipes::instruction::add_ingredient(
+        &recipe,
+        Some(&mint),
+        &payer,
+        &payer,
+        &spl_memo::id(),
+        IngredientType::BurnTokenInput,
+        1,
+        None,
+        None,
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidTokenProgram);
+}
+
+#[tokio::test]
+async fn cannot_add_a_token_account_as_an_ingredient() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let token = utils::mint_to(&mut context, &mint, &payer, 1).await;
+
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&token),
+        IngredientType::BurnTokenInput,
+        1,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidDataSize);
+}
+
+#[tokio::test]
+async fn cannot_add_an_ingredient_as_another_authority() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let authority = Keypair::new();
+
+    let ix = token_recipes::instruction::add_ingredient(
+        &recipe,
+        Some(&mint),
+        &authority.pubkey(),
+        &payer,
+        &spl_token::id(),
+        IngredientType::BurnTokenInput,
+        1,
+        None,
+        None,
+    );
+    let result = utils::process(&mut context, &[ix], &[&authority]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+use token_recipes::{
+    error::TokenRecipesError,
+    state::{features::Feature, key::Key},
+};
+
+#[tokio::test]
+async fn set_and_update_every_feature() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let admin = utils::initialize_program_config(&mut context).await;
+
+    let features = [
+        Feature::Fees(utils::fees_feature()),
+        Feature::AdditionalOutputs(utils::additional_outputs_feature()),
+        Feature::TransferInputs(utils::transfer_inputs_feature()),
+        Feature::MaxSupply(utils::max_supply_feature()),
+        Feature::SolPayment(utils::sol_payment_feature()),
+        Feature::Wisdom(utils::wisdom_feature()),
+        // Update an existing feature.
+        Feature::Fees(utils::fees_feature()),
+    ];
+    for feature in features {
+        let ix =
+            token_recipes::instruction::admin_set_feature(&admin.pubkey(), &payer, feature.clone());
+        utils::process(&mut context, &[ix], &[&admin])
+            .await
+            .unwrap();
+
+        // The canonical bump and the current version are stored.
+        let (address, expected) = utils::feature_account(feature);
+        let account = utils::get_account(&mut context, &address).await.unwrap();
+        assert_eq!(account.owner, token_recipes::id());
+        assert_eq!(account.data, expected.data);
+    }
+}
+
+#[tokio::test]
+async fn cannot_set_a_feature_with_another_key() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let admin = utils::initialize_program_config(&mut context).await;
+    let mut fees_feature = utils::fees_feature();
+    fees_feature.key = Key::WisdomFeature;
+
+    let ix = token_recipes::instruction::admin_set_feature(
+        &admin.pubkey(),
+        &payer,
+        Feature::Fees(fees_feature),
+    );
+    let result = utils::process(&mut context, &[ix], &[&admin]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidAccountKey);
+}
+
+#[tokio::test]
+async fn cannot_set_fee_percentages_above_100() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let admin = utils::initialize_program_config(&mut context).await;
+    let mut fees_feature = utils::fees_feature();
+    fees_feature.fee_percentages[11] = 101;
+
+    let ix = token_recipes::instruction::admin_set_feature(
+        &admin.pubkey(),
+        &payer,
+        Feature::Fees(fees_feature),
+    );
+    let result = utils::process(&mut context, &[ix], &[&admin]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidFeePercentage);
+}
+
+#[tokio::test]
+async fn cannot_set_a_feature_without_the_admin_signature() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let admin = utils::initialize_program_config(&mut context).await;
+    let mut ix = token_recipes::instruction::admin_set_feature(
+        &admin.pubkey(),
+        &payer,
+        Feature::Fees(utils::fees_feature()),
+    );
+    ix.accounts[1].is_signer = false;
+
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::ExpectedSignerAccount);
+}
+
+#[tokio::test]
+async fn cannot_set_a_feature_as_another_admin() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    utils::initialize_program_config(&mut context).await;
+    let impostor = Keypair::new();
+
+    let ix = token_recipes::instruction::admin_set_feature(
+        &impostor.pubkey(),
+        &payer,
+        Feature::Fees(utils::fees_feature()),
+    );
+    let result = utils::process(&mut context, &[ix], &[&impostor]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
+
+#[tokio::test]
+async fn cannot_set_a_feature_before_initializing_the_program_config() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let admin = Keypair::new();
+
+    let ix = token_recipes::instruction::admin_set_feature(
+        &admin.pubkey(),
+        &payer,
+        Feature::Fees(utils::fees_feature()),
+    );
+    let result = utils::process(&mut context, &[ix], &[&admin]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidProgramOwner);
+}
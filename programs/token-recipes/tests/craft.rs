@@ -0,0 +1,260 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::tokio;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::find_associated_token_address,
+    state::{
+        features::{fees::DEFAULT_BASE_FEES, FeatureLevels},
+        recipe::IngredientType,
+    },
+};
+
+#[tokio::test]
+async fn craft_every_type_of_ingredient() {
+    // Given a recipe using every type of ingredient.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels = FeatureLevels {
+            additional_outputs: 3,
+            transfer_inputs: 3,
+            max_supply: 1,
+            sol_payment: 11,
+            ..FeatureLevels::default()
+        }
+    })
+    .await;
+    let burn_mint = utils::create_mint(&mut context, &payer).await;
+    let transfer_mint = utils::create_mint(&mut context, &payer).await;
+    let hold_mint = utils::create_mint(&mut context, &payer).await;
+    let output_mint = utils::create_mint(&mut context, &payer).await;
+    let max_supply_mint = utils::create_mint(&mut context, &payer).await;
+    let vault_mint = utils::create_mint(&mut context, &payer).await;
+    let destination = Pubkey::new_unique();
+    let ingredients = [
+        (
+            Some(&burn_mint),
+            IngredientType::BurnTokenInput,
+            2,
+            None,
+            None,
+        ),
+        (
+            Some(&transfer_mint),
+            IngredientType::TransferTokenInput,
+            1,
+            Some(destination),
+            None,
+        ),
+        (
+            None,
+            IngredientType::TransferSolInput,
+            1_000_000,
+            Some(destination),
+            None,
+        ),
+        (
+            Some(&hold_mint),
+            IngredientType::HoldTokenInput,
+            1,
+            None,
+            None,
+        ),
+        (
+            Some(&output_mint),
+            IngredientType::MintTokenOutput,
+            1,
+            None,
+            None,
+        ),
+        (
+            Some(&max_supply_mint),
+            IngredientType::MintTokenWithMaxSupplyOutput,
+            1,
+            None,
+            Some(100),
+        ),
+        (
+            Some(&vault_mint),
+            IngredientType::TransferTokenOutput,
+            1,
+            None,
+            None,
+        ),
+    ];
+    for (mint, ingredient_type, amount, destination, max_supply) in ingredients {
+        utils::add_ingredient(
+            &mut context,
+            &recipe,
+            mint,
+            ingredient_type,
+            amount,
+            destination,
+            max_supply,
+        )
+        .await
+        .unwrap();
+    }
+    utils::mint_to(&mut context, &vault_mint, &payer, 10).await;
+    let ix = token_recipes::instruction::deposit_vault_tokens(
+        &recipe,
+        &payer,
+        &payer,
+        &vault_mint,
+        &spl_token::id(),
+        10,
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    // And an owner holding enough input tokens.
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let burn_token = utils::mint_to(&mut context, &burn_mint, &owner.pubkey(), 4).await;
+    let transfer_token = utils::mint_to(&mut context, &transfer_mint, &owner.pubkey(), 2).await;
+    let hold_token = utils::mint_to(&mut context, &hold_mint, &owner.pubkey(), 1).await;
+    let recipe_lamports = utils::get_lamports(&mut context, &recipe).await;
+
+    // When the owner crafts the recipe twice.
+    utils::craft(&mut context, &recipe, &owner, 2)
+        .await
+        .unwrap();
+
+    // Then the inputs were consumed.
+    let find_ata = |owner: &Pubkey, mint: &Pubkey| {
+        find_associated_token_address(owner, mint, &spl_token::id())
+    };
+    assert_eq!(utils::get_token_balance(&mut context, &burn_token).await, 0);
+    assert_eq!(
+        utils::get_token_balance(&mut context, &transfer_token).await,
+        0
+    );
+    let destination_token = find_ata(&destination, &transfer_mint);
+    assert_eq!(
+        utils::get_token_balance(&mut context, &destination_token).await,
+        2
+    );
+    assert_eq!(
+        utils::get_lamports(&mut context, &destination).await,
+        2_000_000
+    );
+    assert_eq!(utils::get_token_balance(&mut context, &hold_token).await, 1);
+
+    // And the outputs were received.
+    for mint in [&output_mint, &max_supply_mint, &vault_mint] {
+        let token = find_ata(&owner.pubkey(), mint);
+        assert_eq!(utils::get_token_balance(&mut context, &token).await, 2);
+    }
+    let vault = find_ata(&recipe, &vault_mint);
+    assert_eq!(utils::get_token_balance(&mut context, &vault).await, 8);
+
+    // And the recipe received the base fees once.
+    assert_eq!(
+        utils::get_lamports(&mut context, &recipe).await,
+        recipe_lamports + DEFAULT_BASE_FEES
+    );
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.total_crafts, 1);
+    assert_eq!(recipe_account.total_crafts_with_quantity, 2);
+    assert_eq!(recipe_account.accumulated_admin_fees, DEFAULT_BASE_FEES);
+    assert_eq!(recipe_account.accumulated_shards, 0);
+    assert_eq!(recipe_account.accumulated_experience, 100);
+}
+
+#[tokio::test]
+async fn cannot_craft_a_paused_recipe() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    let result = utils::craft(&mut context, &recipe, &owner, 1).await;
+
+    utils::assert_program_error(result, TokenRecipesError::RecipeIsNotActive);
+}
+
+#[tokio::test]
+async fn cannot_craft_without_enough_input_tokens() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        2,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::mint_to(&mut context, &mint, &owner.pubkey(), 1).await;
+
+    let result = utils::craft(&mut context, &recipe, &owner, 1).await;
+
+    utils::assert_program_error(result, TokenRecipesError::NotEnoughTokens);
+}
+
+#[tokio::test]
+async fn cannot_craft_an_overflowing_quantity() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        2,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::mint_to(&mut context, &mint, &owner.pubkey(), 1).await;
+
+    let result = utils::craft(&mut context, &recipe, &owner, u64::MAX).await;
+
+    utils::assert_program_error(result, TokenRecipesError::NumericalOverflow);
+}
+
+#[tokio::test]
+async fn cannot_craft_above_the_maximum_supply() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.max_supply = 1;
+    })
+    .await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        IngredientType::MintTokenWithMaxSupplyOutput,
+        1,
+        None,
+        Some(2),
+    )
+    .await
+    .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    let result = utils::craft(&mut context, &recipe, &owner, 3).await;
+
+    utils::assert_program_error(result, TokenRecipesError::MaximumSupplyReached);
+}
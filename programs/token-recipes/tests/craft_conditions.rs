@@ -0,0 +1,159 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshDeserialize;
+use solana_program::keccak;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+use token_recipes::{
+    error::TokenRecipesError, pda::find_crafter_record_pda, state::crafter_record::CrafterRecord,
+};
+
+#[tokio::test]
+async fn craft_up_to_the_limit_per_wallet() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let ix = token_recipes::instruction::set_max_crafts_per_wallet(&recipe, &payer, 3);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    // The owner can craft 3 times.
+    utils::craft(&mut context, &recipe, &owner, 2)
+        .await
+        .unwrap();
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+    let (crafter_record, _) = find_crafter_record_pda(&recipe, &owner.pubkey());
+    let account = utils::get_account(&mut context, &crafter_record)
+        .await
+        .unwrap();
+    let record = CrafterRecord::deserialize(&mut account.data.as_ref()).unwrap();
+    assert_eq!(record.owner, owner.pubkey());
+    assert_eq!(record.total_crafts_with_quantity, 3);
+
+    // But not a 4th time.
+    let result = utils::craft(&mut context, &recipe, &owner, 4).await;
+    utils::assert_program_error(result, TokenRecipesError::CraftLimitReached);
+}
+
+#[tokio::test]
+async fn cannot_set_an_invalid_time_window() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+
+    // The start time is after the end time.
+    let ix = token_recipes::instruction::set_time_window(&recipe, &payer, Some(20), Some(10));
+    let result = utils::process(&mut context, &[ix], &[]).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidTimeWindow);
+
+    // A timestamp is not positive.
+    let ix = token_recipes::instruction::set_time_window(&recipe, &payer, None, Some(-1));
+    let result = utils::process(&mut context, &[ix], &[]).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidTimeWindow);
+}
+
+#[tokio::test]
+async fn cannot_craft_outside_the_time_window() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    // Before the start time.
+    let ix = token_recipes::instruction::set_time_window(&recipe, &payer, Some(i64::MAX), None);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let result = utils::craft(&mut context, &recipe, &owner, 1).await;
+    utils::assert_program_error(result, TokenRecipesError::RecipeHasNotStarted);
+
+    // After the end time.
+    let ix = token_recipes::instruction::set_time_window(&recipe, &payer, None, Some(1));
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let result = utils::craft(&mut context, &recipe, &owner, 2).await;
+    utils::assert_program_error(result, TokenRecipesError::RecipeHasEnded);
+
+    // Within the time window.
+    let ix = token_recipes::instruction::set_time_window(&recipe, &payer, Some(1), None);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    utils::craft(&mut context, &recipe, &owner, 3)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn craft_with_an_allowlist() {
+    // Given a recipe whose allowlist contains two owners.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let other_owner = Keypair::new();
+    let leaf = keccak::hashv(&[owner.pubkey().as_ref()]).to_bytes();
+    let sibling = keccak::hashv(&[other_owner.pubkey().as_ref()]).to_bytes();
+    let root = match leaf <= sibling {
+        true => keccak::hashv(&[&leaf, &sibling]).to_bytes(),
+        false => keccak::hashv(&[&sibling, &leaf]).to_bytes(),
+    };
+    let ix = token_recipes::instruction::set_allowlist_root(&recipe, &payer, Some(root));
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    // Then an allowlisted owner can craft with a proof.
+    utils::craft_with_proof(&mut context, &recipe, &owner, 1, vec![sibling])
+        .await
+        .unwrap();
+
+    // But not with an invalid proof.
+    let result = utils::craft_with_proof(&mut context, &recipe, &owner, 1, vec![leaf]).await;
+    utils::assert_program_error(result, TokenRecipesError::NotInAllowlist);
+
+    // And other owners cannot craft.
+    let stranger = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let result = utils::craft_with_proof(&mut context, &recipe, &stranger, 1, vec![sibling]).await;
+    utils::assert_program_error(result, TokenRecipesError::NotInAllowlist);
+
+    // Nor without a proof.
+    let result = utils::craft(&mut context, &recipe, &owner, 2).await;
+    utils::assert_program_error(result, TokenRecipesError::NotInAllowlist);
+}
+
+#[tokio::test]
+async fn craft_without_a_proof_keeps_the_original_instruction_data() {
+    // Given an active recipe without an allowlist.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    // When we build a Craft instruction without a proof.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    let ix = token_recipes::instruction::craft(&recipe, &recipe_account, &payer, &payer, 3, vec![]);
+
+    // Then its data only contains the discriminator and the quantity.
+    let mut data = vec![5];
+    data.extend(3u64.to_le_bytes());
+    assert_eq!(ix.data, data);
+
+    // And the recipe can be crafted with it.
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.total_crafts_with_quantity, 3);
+}
+
+#[tokio::test]
+async fn cannot_set_the_allowlist_of_an_active_recipe() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    let ix = token_recipes::instruction::set_allowlist_root(&recipe, &payer, Some([1; 32]));
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::RecipeMustBePaused);
+}
@@ -0,0 +1,56 @@
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use token_recipes::state::craft_quote::{CraftQuote, TokenAmount};
+
+/// Serialize the quote and trim its trailing zero bytes like the runtime does with return data.
+fn return_data(quote: &CraftQuote) -> Vec<u8> {
+    let mut data = quote.try_to_vec().unwrap();
+    while data.last() == Some(&0) {
+        data.pop();
+    }
+    data
+}
+
+#[test]
+fn decode_a_quote_without_costs() {
+    let quote = CraftQuote {
+        quantity: 1,
+        token_costs: vec![],
+        lamport_costs: 0,
+        fees: 0,
+        admin_fees: 0,
+        shards: 0,
+        experience: 0,
+    };
+
+    let data = return_data(&quote);
+
+    assert_eq!(data, vec![1]);
+    assert_eq!(CraftQuote::from_return_data(&data).unwrap(), quote);
+}
+
+#[test]
+fn decode_a_quote_whose_last_token_cost_is_trimmed() {
+    let quote = CraftQuote {
+        quantity: 2,
+        token_costs: vec![
+            TokenAmount {
+                mint: Pubkey::new_unique(),
+                amount: 5,
+            },
+            TokenAmount {
+                mint: Pubkey::new_from_array([0; 32]),
+                amount: 0,
+            },
+        ],
+        lamport_costs: 0,
+        fees: 0,
+        admin_fees: 0,
+        shards: 0,
+        experience: 0,
+    };
+
+    let data = return_data(&quote);
+
+    assert_eq!(CraftQuote::from_return_data(&data).unwrap(), quote);
+}
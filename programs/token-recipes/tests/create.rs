@@ -1,13 +1,23 @@
 #![cfg(feature = "test-bpf")]
 
+mod utils;
+
 use assert_matches::assert_matches;
 use borsh::BorshDeserialize;
 use solana_program_test::{tokio, ProgramTest};
 use solana_sdk::{
+    instruction::AccountMeta,
     signature::{Keypair, Signer},
     transaction::Transaction,
 };
-use token_recipes::state::{Key, Recipe, RecipeStatus};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::find_recipe_pda,
+    state::{
+        key::Key,
+        recipe::{Recipe, RecipeStatus},
+    },
+};
 
 #[tokio::test]
 async fn create_recipe() {
@@ -15,10 +25,11 @@ async fn create_recipe() {
         .start_with_context()
         .await;
 
-    let recipe = Keypair::new();
+    let base = Keypair::new();
+    let (recipe, bump) = find_recipe_pda(&base.pubkey());
 
     let ix = token_recipes::instruction::create_recipe(
-        &recipe.pubkey(),
+        &base.pubkey(),
         &context.payer.pubkey(),
         &context.payer.pubkey(),
     );
@@ -26,16 +37,12 @@ async fn create_recipe() {
     let tx = Transaction::new_signed_with_payer(
         &[ix],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &recipe],
+        &[&context.payer, &base],
         context.last_blockhash,
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    let account = context
-        .banks_client
-        .get_account(recipe.pubkey())
-        .await
-        .unwrap();
+    let account = context.banks_client.get_account(recipe).await.unwrap();
 
     assert!(account.is_some());
 
@@ -47,4 +54,37 @@ async fn create_recipe() {
     assert_matches!(my_account.key, Key::Recipe);
     assert_eq!(my_account.authority, context.payer.pubkey());
     assert_matches!(my_account.status, RecipeStatus::Paused);
+    assert_eq!(my_account.version, Recipe::VERSION);
+    assert_eq!(my_account.bump, bump);
+}
+
+#[tokio::test]
+async fn cannot_create_a_recipe_twice() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let base = Keypair::new();
+    let ix = token_recipes::instruction::create_recipe(&base.pubkey(), &payer, &payer);
+    utils::process(&mut context, &[ix], &[&base]).await.unwrap();
+
+    // A different payer avoids sending the exact same transaction twice.
+    let other_payer = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let ix =
+        token_recipes::instruction::create_recipe(&base.pubkey(), &payer, &other_payer.pubkey());
+    let result = utils::process(&mut context, &[ix], &[&base, &other_payer]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::ExpectedEmptyAccount);
+}
+
+#[tokio::test]
+async fn cannot_create_a_recipe_at_another_address() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let base = Keypair::new();
+    let (other_recipe, _) = find_recipe_pda(&Keypair::new().pubkey());
+
+    let mut ix = token_recipes::instruction::create_recipe(&base.pubkey(), &payer, &payer);
+    ix.accounts[1] = AccountMeta::new(other_recipe, false);
+    let result = utils::process(&mut context, &[ix], &[&base]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidPda);
 }
@@ -0,0 +1,97 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{find_fees_feature_pda, find_wisdom_feature_pda},
+    state::{
+        features::{fees::DEFAULT_BASE_FEES, Feature},
+        recipe::IngredientType,
+    },
+};
+
+/// Set up the fees and wisdom features with mints owned by their PDAs.
+/// Returns (admin destination, shard mint, experience mint).
+async fn set_up_features(context: &mut ProgramTestContext) -> (Pubkey, Pubkey, Pubkey) {
+    let shard_mint = utils::create_mint(context, &find_fees_feature_pda().0).await;
+    let experience_mint = utils::create_mint(context, &find_wisdom_feature_pda().0).await;
+    let mut fees_feature = utils::fees_feature();
+    fees_feature.shard_mint = shard_mint;
+    let admin_destination = fees_feature.admin_destination;
+    utils::set_feature(context, Feature::Fees(fees_feature)).await;
+    let mut wisdom_feature = utils::wisdom_feature();
+    wisdom_feature.experience_mint = experience_mint;
+    utils::set_feature(context, Feature::Wisdom(wisdom_feature)).await;
+    (admin_destination, shard_mint, experience_mint)
+}
+
+#[tokio::test]
+async fn delete_a_crafted_recipe() {
+    // Given a recipe crafted once.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let (admin_destination, shard_mint, experience_mint) = set_up_features(&mut context).await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    // When we delete it.
+    let ix = token_recipes::instruction::delete_recipe(
+        &recipe,
+        &payer,
+        &payer,
+        &admin_destination,
+        &shard_mint,
+        &experience_mint,
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Then the recipe is closed and its admin fees were collected.
+    assert!(utils::get_account(&mut context, &recipe).await.is_none());
+    assert_eq!(
+        utils::get_lamports(&mut context, &admin_destination).await,
+        DEFAULT_BASE_FEES
+    );
+}
+
+#[tokio::test]
+async fn cannot_delete_a_recipe_with_ingredients() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let (admin_destination, shard_mint, experience_mint) = set_up_features(&mut context).await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let ix = token_recipes::instruction::delete_recipe(
+        &recipe,
+        &payer,
+        &payer,
+        &admin_destination,
+        &shard_mint,
+        &experience_mint,
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(
+        result,
+        TokenRecipesError::RecipeMustBeEmptyBeforeItCanBeDeleted,
+    );
+}
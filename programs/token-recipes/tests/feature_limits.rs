@@ -0,0 +1,210 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program_test::tokio;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use token_recipes::{error::TokenRecipesError, state::recipe::IngredientType};
+
+#[tokio::test]
+async fn additional_outputs_are_limited_by_level() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+
+    // (level, allowed outputs).
+    for (level, max_outputs) in [(0, 1), (1, 2), (2, 3), (3, 4)] {
+        let recipe = utils::create_recipe(&mut context).await;
+        utils::update_recipe(&mut context, &recipe, |recipe| {
+            recipe.feature_levels.additional_outputs = level;
+        })
+        .await;
+        for _ in 0..max_outputs {
+            let mint = utils::create_mint(&mut context, &payer).await;
+            let mint_output = IngredientType::MintTokenOutput;
+            utils::add_ingredient(
+                &mut context,
+                &recipe,
+                Some(&mint),
+                mint_output,
+                1,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        if level == 3 {
+            continue;
+        }
+
+        let mint = utils::create_mint(&mut context, &payer).await;
+        let mint_output = IngredientType::MintTokenOutput;
+        let result = utils::add_ingredient(
+            &mut context,
+            &recipe,
+            Some(&mint),
+            mint_output,
+            1,
+            None,
+            None,
+        )
+        .await;
+        utils::assert_program_error(result, TokenRecipesError::InvalidAdditionalOutputsFeature);
+    }
+}
+
+#[tokio::test]
+async fn transfer_inputs_are_limited_by_level() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let destination = Some(Pubkey::new_unique());
+
+    // (level, allowed transfer inputs).
+    for (level, max_inputs) in [(0, 0), (1, 1), (2, 2), (3, 3)] {
+        let recipe = utils::create_recipe(&mut context).await;
+        utils::update_recipe(&mut context, &recipe, |recipe| {
+            recipe.feature_levels.transfer_inputs = level;
+        })
+        .await;
+        for _ in 0..max_inputs {
+            let mint = utils::create_mint(&mut context, &payer).await;
+            let transfer_input = IngredientType::TransferTokenInput;
+            utils::add_ingredient(
+                &mut context,
+                &recipe,
+                Some(&mint),
+                transfer_input,
+                1,
+                destination,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        if level == 3 {
+            continue;
+        }
+
+        let mint = utils::create_mint(&mut context, &payer).await;
+        let transfer_input = IngredientType::TransferTokenInput;
+        let result = utils::add_ingredient(
+            &mut context,
+            &recipe,
+            Some(&mint),
+            transfer_input,
+            1,
+            destination,
+            None,
+        )
+        .await;
+        utils::assert_program_error(result, TokenRecipesError::InvalidTransferInputsFeature);
+    }
+}
+
+#[tokio::test]
+async fn sol_payments_are_limited_by_level() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let destination = Some(Pubkey::new_unique());
+
+    // Level 0 does not allow any SOL payment.
+    let recipe = utils::create_recipe(&mut context).await;
+    let sol_input = IngredientType::TransferSolInput;
+    let result =
+        utils::add_ingredient(&mut context, &recipe, None, sol_input, 1, destination, None).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidSolPaymentFeature);
+
+    // Levels 1 to 10 double the maximum payment from 1 SOL to 512 SOL.
+    for level in 1..=10u8 {
+        let max_lamports = LAMPORTS_PER_SOL << (level - 1);
+        let recipe = utils::create_recipe(&mut context).await;
+        utils::update_recipe(&mut context, &recipe, |recipe| {
+            recipe.feature_levels.sol_payment = level;
+        })
+        .await;
+        let sol_input = IngredientType::TransferSolInput;
+        utils::add_ingredient(
+            &mut context,
+            &recipe,
+            None,
+            sol_input,
+            max_lamports,
+            destination,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let ix = token_recipes::instruction::update_ingredient(
+            &recipe,
+            None,
+            &payer,
+            &payer,
+            IngredientType::TransferSolInput,
+            None,
+            Some(max_lamports + 1),
+            None,
+            None,
+        );
+        let result = utils::process(&mut context, &[ix], &[]).await;
+        utils::assert_program_error(result, TokenRecipesError::InvalidSolPaymentFeature);
+    }
+
+    // Level 11 removes the limit.
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.sol_payment = 11;
+    })
+    .await;
+    let sol_input = IngredientType::TransferSolInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        None,
+        sol_input,
+        u64::MAX,
+        destination,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn max_supply_outputs_require_level_1() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let max_supply_output = IngredientType::MintTokenWithMaxSupplyOutput;
+
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        max_supply_output,
+        1,
+        None,
+        Some(10),
+    )
+    .await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidMaxSupplyFeature);
+
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.max_supply = 1;
+    })
+    .await;
+    let max_supply_output = IngredientType::MintTokenWithMaxSupplyOutput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        max_supply_output,
+        2,
+        None,
+        Some(10),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,278 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{find_associated_token_address, find_fees_feature_pda},
+    state::features::{fees::DEFAULT_BASE_FEES, Feature},
+};
+
+#[tokio::test]
+async fn craft_at_every_fees_level() {
+    let mut context = utils::start().await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    // (level, custom fees, recipe fees, admin fees, shards, experience).
+    let levels = [
+        (0, 0, 0, DEFAULT_BASE_FEES, 0, 100),
+        (1, 0, 2_000_000, 18_000_000, 18_000_000, 100),
+        (2, 0, 4_000_000, 16_000_000, 16_000_000, 100),
+        (3, 0, 6_000_000, 14_000_000, 14_000_000, 100),
+        (4, 0, 8_000_000, 12_000_000, 12_000_000, 100),
+        (5, 0, 10_000_000, 10_000_000, 10_000_000, 100),
+        (6, 0, 12_000_000, 8_000_000, 8_000_000, 100),
+        (7, 0, 14_000_000, 6_000_000, 6_000_000, 100),
+        (8, 0, 16_000_000, 4_000_000, 4_000_000, 100),
+        (9, 0, 18_000_000, 2_000_000, 2_000_000, 100),
+        (10, 30_000_000, 27_000_000, 3_000_000, 3_000_000, 100),
+        (10, 10_000_000, 9_000_000, 1_000_000, 0, 0),
+        (11, 30_000_000, 30_000_000, 0, 0, 0),
+    ];
+    for (level, fees, recipe_fees, admin_fees, shards, experience) in levels {
+        let recipe = utils::create_recipe(&mut context).await;
+        utils::update_recipe(&mut context, &recipe, |recipe| {
+            recipe.feature_levels.fees = level;
+            recipe.fees = fees;
+        })
+        .await;
+        utils::activate_recipe(&mut context, &recipe).await;
+        let recipe_lamports = utils::get_lamports(&mut context, &recipe).await;
+
+        utils::craft(&mut context, &recipe, &owner, 1)
+            .await
+            .unwrap();
+
+        let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+        assert_eq!(
+            utils::get_lamports(&mut context, &recipe).await,
+            recipe_lamports + recipe_fees + admin_fees,
+            "level {level}"
+        );
+        assert_eq!(
+            recipe_account.accumulated_admin_fees, admin_fees,
+            "level {level}"
+        );
+        assert_eq!(recipe_account.accumulated_shards, shards, "level {level}");
+        assert_eq!(
+            recipe_account.accumulated_experience, experience,
+            "level {level}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn craft_with_the_fees_of_the_fees_feature() {
+    // Given a fees feature with custom base fees and fee percentages.
+    let mut context = utils::start().await;
+    let mut fees_feature = utils::fees_feature();
+    fees_feature.base_fees = 1_000_000;
+    fees_feature.fee_percentages[3] = 25;
+    utils::set_feature(&mut context, Feature::Fees(fees_feature)).await;
+
+    // And an active recipe at fees level 3.
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.fees = 3;
+    })
+    .await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let recipe_lamports = utils::get_lamports(&mut context, &recipe).await;
+
+    // When it is crafted.
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    // Then the fees follow the fees feature.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        utils::get_lamports(&mut context, &recipe).await,
+        recipe_lamports + 1_000_000
+    );
+    assert_eq!(recipe_account.accumulated_admin_fees, 750_000);
+    assert_eq!(recipe_account.accumulated_shards, 750_000);
+}
+
+#[tokio::test]
+async fn craft_with_the_default_fees_before_the_fees_feature_exists() {
+    // Given a program whose fees feature was not created yet.
+    let mut context = ProgramTest::new("token_recipes", token_recipes::id(), None)
+        .start_with_context()
+        .await;
+
+    // And an active recipe at fees level 1.
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.fees = 1;
+    })
+    .await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let recipe_lamports = utils::get_lamports(&mut context, &recipe).await;
+
+    // When it is crafted.
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    // Then the default fees are used.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        utils::get_lamports(&mut context, &recipe).await,
+        recipe_lamports + DEFAULT_BASE_FEES
+    );
+    assert_eq!(recipe_account.accumulated_admin_fees, 18_000_000);
+    assert_eq!(recipe_account.accumulated_shards, 18_000_000);
+}
+
+#[tokio::test]
+async fn cannot_craft_with_an_uninitialized_account_as_the_fees_feature() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    let mut ix = token_recipes::instruction::craft(
+        &recipe,
+        &recipe_account,
+        &owner.pubkey(),
+        &owner.pubkey(),
+        1,
+        vec![],
+    );
+    ix.accounts[7].pubkey = Pubkey::new_unique();
+
+    let result = utils::process(&mut context, &[ix], &[&owner]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidPda);
+}
+
+#[tokio::test]
+async fn set_custom_fees() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.fees = 10;
+    })
+    .await;
+
+    let ix = token_recipes::instruction::set_fees(&recipe, &payer, 42);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.fees, 42);
+}
+
+#[tokio::test]
+async fn cannot_set_custom_fees_below_level_10() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.fees = 9;
+    })
+    .await;
+
+    let ix = token_recipes::instruction::set_fees(&recipe, &payer, 42);
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidFeesFeature);
+}
+
+#[tokio::test]
+async fn collect_fees_and_shards() {
+    // Given a fees feature whose shard mint is owned by the fees feature PDA.
+    let mut context = utils::start().await;
+    let (fees_feature_pda, _) = find_fees_feature_pda();
+    let shard_mint = utils::create_mint(&mut context, &fees_feature_pda).await;
+    let mut fees_feature = utils::fees_feature();
+    fees_feature.shard_mint = shard_mint;
+    let admin_destination = fees_feature.admin_destination;
+    utils::set_feature(&mut context, Feature::Fees(fees_feature)).await;
+
+    // And a crafted recipe at fees level 5.
+    let authority = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let recipe = utils::create_recipe_with_authority(&mut context, &authority.pubkey()).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.fees = 5;
+    })
+    .await;
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::activate_recipe(&recipe, &authority.pubkey());
+    utils::process(&mut context, &[ix], &[&authority])
+        .await
+        .unwrap();
+    let recipe_lamports = utils::get_lamports(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    // When the authority collects the fees.
+    let ix = token_recipes::instruction::collect_fees(
+        &recipe,
+        &authority.pubkey(),
+        &payer,
+        &admin_destination,
+        &shard_mint,
+    );
+    utils::process(&mut context, &[ix], &[&authority])
+        .await
+        .unwrap();
+
+    // Then the recipe fees went to the authority and the admin fees to the admin.
+    assert_eq!(
+        utils::get_lamports(&mut context, &recipe).await,
+        recipe_lamports
+    );
+    assert_eq!(
+        utils::get_lamports(&mut context, &authority.pubkey()).await,
+        1_000_000_000 + DEFAULT_BASE_FEES / 2
+    );
+    assert_eq!(
+        utils::get_lamports(&mut context, &admin_destination).await,
+        DEFAULT_BASE_FEES / 2
+    );
+
+    // And the shards were minted to the authority.
+    let shard_token =
+        find_associated_token_address(&authority.pubkey(), &shard_mint, &spl_token::id());
+    assert_eq!(
+        utils::get_token_balance(&mut context, &shard_token).await,
+        DEFAULT_BASE_FEES / 2
+    );
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.accumulated_admin_fees, 0);
+    assert_eq!(recipe_account.accumulated_shards, 0);
+}
+
+#[tokio::test]
+async fn cannot_collect_fees_to_another_admin_destination() {
+    let mut context = utils::start().await;
+    let (fees_feature_pda, _) = find_fees_feature_pda();
+    let shard_mint = utils::create_mint(&mut context, &fees_feature_pda).await;
+    let mut fees_feature = utils::fees_feature();
+    fees_feature.shard_mint = shard_mint;
+    utils::set_feature(&mut context, Feature::Fees(fees_feature)).await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+
+    let ix = token_recipes::instruction::collect_fees(
+        &recipe,
+        &payer,
+        &payer,
+        &Keypair::new().pubkey(),
+        &shard_mint,
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
@@ -0,0 +1,393 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshDeserialize;
+use solana_program::{clock::Clock, slot_hashes::MAX_ENTRIES};
+use solana_program_test::{tokio, BanksClientError, ProgramTestContext};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{find_associated_token_address, find_delegated_ingredient_pda, find_loot_roll_pda},
+    state::{
+        ingredient_output::{IngredientOutput, LootTableEntry},
+        loot_roll::{LootRoll, LootTableRolls},
+        recipe::IngredientType,
+    },
+};
+
+async fn add_loot_table_output(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    rolls: u8,
+    entries: Vec<LootTableEntry>,
+) -> Result<(), BanksClientError> {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::add_loot_table_output(
+        recipe,
+        &payer,
+        &payer,
+        &spl_token::id(),
+        rolls,
+        entries,
+    );
+    utils::process(context, &[ix], &[]).await
+}
+
+async fn get_loot_roll(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+) -> Option<LootRoll> {
+    let (loot_roll, _) = find_loot_roll_pda(recipe, owner);
+    utils::get_account(context, &loot_roll)
+        .await
+        .map(|account| LootRoll::deserialize(&mut account.data.as_ref()).unwrap())
+}
+
+async fn reveal_loot(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+) -> Result<(), BanksClientError> {
+    let (loot_roll, _) = find_loot_roll_pda(recipe, owner);
+    let loot_roll_account = get_loot_roll(context, recipe, owner).await.unwrap();
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::reveal_loot(&loot_roll, &loot_roll_account, &payer);
+    utils::process(context, &[ix], &[]).await
+}
+
+/// Move the bank forward by the given number of slots.
+async fn warp_slots(context: &mut ProgramTestContext, slots: u64) {
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + slots).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+}
+
+async fn get_total_loot(
+    context: &mut ProgramTestContext,
+    owner: &Pubkey,
+    entries: &[LootTableEntry],
+) -> u64 {
+    let mut total = 0;
+    for entry in entries {
+        let token = find_associated_token_address(owner, &entry.mint, &spl_token::id());
+        total += utils::get_token_balance(context, &token).await;
+    }
+    total
+}
+
+async fn create_entries(context: &mut ProgramTestContext, weights: &[u16]) -> Vec<LootTableEntry> {
+    let payer = context.payer.pubkey();
+    let mut entries = vec![];
+    for weight in weights {
+        entries.push(LootTableEntry {
+            mint: utils::create_mint(context, &payer).await,
+            amount: 2,
+            weight: *weight,
+        });
+    }
+    entries
+}
+
+#[tokio::test]
+async fn add_craft_and_remove_a_loot_table_output() {
+    // Given a recipe with a loot table rolled 3 times.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.additional_outputs = 1;
+    })
+    .await;
+    let entries = create_entries(&mut context, &[1, 3]).await;
+    add_loot_table_output(&mut context, &recipe, 3, entries.clone())
+        .await
+        .unwrap();
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        recipe_account.outputs,
+        vec![IngredientOutput::MintTokenFromLootTable {
+            rolls: 3,
+            entries: entries.clone()
+        }]
+    );
+
+    // When an owner crafts it twice.
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 2)
+        .await
+        .unwrap();
+
+    // Then the loot table was committed to be rolled 6 times.
+    let loot_roll = get_loot_roll(&mut context, &recipe, &owner.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(loot_roll.owner, owner.pubkey());
+    assert_eq!(loot_roll.payer, owner.pubkey());
+    assert_eq!(loot_roll.total_crafts, 0);
+    assert_eq!(
+        loot_roll.loot_tables,
+        vec![LootTableRolls {
+            rolls: 6,
+            entries: entries.clone()
+        }]
+    );
+    assert_eq!(
+        get_total_loot(&mut context, &owner.pubkey(), &entries).await,
+        0
+    );
+
+    // And the loot can be revealed in a later slot.
+    warp_slots(&mut context, 1).await;
+    reveal_loot(&mut context, &recipe, &owner.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        get_total_loot(&mut context, &owner.pubkey(), &entries).await,
+        6 * 2
+    );
+    assert!(get_loot_roll(&mut context, &recipe, &owner.pubkey())
+        .await
+        .is_none());
+
+    // And the loot table can be removed.
+    let ix = token_recipes::instruction::remove_loot_table_output(
+        &recipe,
+        &payer,
+        &payer,
+        &spl_token::id(),
+        0,
+        &entries,
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert!(recipe_account.outputs.is_empty());
+    for entry in &entries {
+        let (delegated_ingredient, _) = find_delegated_ingredient_pda(&entry.mint);
+        assert!(utils::get_account(&mut context, &delegated_ingredient)
+            .await
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn cannot_add_an_invalid_loot_table() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let entries = create_entries(&mut context, &[1, 1]).await;
+
+    // No rolls.
+    let result = add_loot_table_output(&mut context, &recipe, 0, entries.clone()).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidLootTable);
+
+    // No entries.
+    let result = add_loot_table_output(&mut context, &recipe, 1, vec![]).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidLootTable);
+
+    // A zero weight.
+    let mut invalid_entries = entries.clone();
+    invalid_entries[1].weight = 0;
+    let result = add_loot_table_output(&mut context, &recipe, 1, invalid_entries).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidLootTable);
+
+    // A duplicated mint.
+    let mut invalid_entries = entries.clone();
+    invalid_entries[1].mint = invalid_entries[0].mint;
+    let result = add_loot_table_output(&mut context, &recipe, 1, invalid_entries).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidLootTable);
+
+    // A zero amount.
+    let mut invalid_entries = entries.clone();
+    invalid_entries[1].amount = 0;
+    let result = add_loot_table_output(&mut context, &recipe, 1, invalid_entries).await;
+    utils::assert_program_error(result, TokenRecipesError::CannotAddIngredientWithZeroAmount);
+}
+
+#[tokio::test]
+async fn loot_table_entries_count_as_additional_outputs() {
+    // Given a recipe without the additional outputs feature.
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let entries = create_entries(&mut context, &[1, 1]).await;
+
+    // Then it cannot reward more than one mint from a loot table.
+    let result = add_loot_table_output(&mut context, &recipe, 1, entries.clone()).await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidAdditionalOutputsFeature);
+
+    // But it can once the feature allows two outputs.
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.additional_outputs = 1;
+    })
+    .await;
+    add_loot_table_output(&mut context, &recipe, 2, entries)
+        .await
+        .unwrap();
+
+    // And a third output exceeds the limit.
+    let payer = context.payer.pubkey();
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let result = utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        IngredientType::MintTokenOutput,
+        1,
+        None,
+        None,
+    )
+    .await;
+    utils::assert_program_error(result, TokenRecipesError::InvalidAdditionalOutputsFeature);
+}
+
+#[tokio::test]
+async fn cannot_roll_a_loot_table_too_many_times() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let entries = create_entries(&mut context, &[1]).await;
+    add_loot_table_output(&mut context, &recipe, 33, entries)
+        .await
+        .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    let result = utils::craft(&mut context, &recipe, &owner, 2).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidLootTable);
+}
+
+#[tokio::test]
+async fn reveal_every_loot_table_of_a_craft() {
+    // Given a recipe with two loot tables.
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.additional_outputs = 1;
+    })
+    .await;
+    let first_entries = create_entries(&mut context, &[1]).await;
+    let second_entries = create_entries(&mut context, &[1]).await;
+    add_loot_table_output(&mut context, &recipe, 1, first_entries.clone())
+        .await
+        .unwrap();
+    add_loot_table_output(&mut context, &recipe, 2, second_entries.clone())
+        .await
+        .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    // When an owner crafts it and reveals the loot.
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+    let loot_roll = get_loot_roll(&mut context, &recipe, &owner.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(loot_roll.loot_tables.len(), 2);
+    warp_slots(&mut context, 1).await;
+    reveal_loot(&mut context, &recipe, &owner.pubkey())
+        .await
+        .unwrap();
+
+    // Then both loot tables were rolled.
+    assert_eq!(
+        get_total_loot(&mut context, &owner.pubkey(), &first_entries).await,
+        2
+    );
+    assert_eq!(
+        get_total_loot(&mut context, &owner.pubkey(), &second_entries).await,
+        2 * 2
+    );
+}
+
+#[tokio::test]
+async fn an_expired_loot_roll_is_forfeited() {
+    // Given a committed loot roll whose slot hash is no longer available.
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let entries = create_entries(&mut context, &[1]).await;
+    add_loot_table_output(&mut context, &recipe, 1, entries.clone())
+        .await
+        .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+    warp_slots(&mut context, MAX_ENTRIES as u64 + 1).await;
+
+    // When the loot is revealed.
+    reveal_loot(&mut context, &recipe, &owner.pubkey())
+        .await
+        .unwrap();
+
+    // Then nothing was minted and the owner can craft again.
+    assert_eq!(
+        get_total_loot(&mut context, &owner.pubkey(), &entries).await,
+        0
+    );
+    assert!(get_loot_roll(&mut context, &recipe, &owner.pubkey())
+        .await
+        .is_none());
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn cannot_reveal_the_loot_in_the_slot_of_the_craft() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let entries = create_entries(&mut context, &[1]).await;
+    add_loot_table_output(&mut context, &recipe, 1, entries)
+        .await
+        .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    let result = reveal_loot(&mut context, &recipe, &owner.pubkey()).await;
+
+    utils::assert_program_error(result, TokenRecipesError::LootRollNotRevealable);
+}
+
+#[tokio::test]
+async fn cannot_craft_again_before_revealing_the_loot() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let entries = create_entries(&mut context, &[1]).await;
+    add_loot_table_output(&mut context, &recipe, 1, entries)
+        .await
+        .unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    let result = utils::craft(&mut context, &recipe, &owner, 2).await;
+
+    utils::assert_program_error(result, TokenRecipesError::LootRollPending);
+}
+
+#[tokio::test]
+async fn cannot_remove_a_missing_loot_table() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+
+    let ix = token_recipes::instruction::remove_loot_table_output(
+        &recipe,
+        &payer,
+        &payer,
+        &spl_token::id(),
+        0,
+        &[],
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::MissingIngredient);
+}
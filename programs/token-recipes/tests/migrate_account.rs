@@ -0,0 +1,309 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program_test::{tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{find_delegated_ingredient_pda, find_fees_feature_pda, find_recipe_pda},
+    state::{
+        delegated_ingredient::DelegatedIngredient,
+        features::{
+            fees::{FeesFeature, DEFAULT_BASE_FEES, DEFAULT_FEE_PERCENTAGES},
+            FeatureLevels,
+        },
+        ingredient_input::IngredientInput,
+        ingredient_output::IngredientOutput,
+        key::Key,
+        recipe::{IngredientType, Recipe, RecipeStatus},
+        recipe_header::RecipeHeader,
+    },
+};
+
+/// Start a program test with the given program account and
+/// migrate it using a payer funded by the context payer.
+async fn migrate(address: Pubkey, data: Vec<u8>) -> Account {
+    let mut program_test = ProgramTest::new("token_recipes", token_recipes::id(), None);
+    program_test.add_account(
+        address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: token_recipes::id(),
+            ..Account::default()
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let payer = Keypair::new();
+    let ixs = [
+        solana_sdk::system_instruction::transfer(
+            &context.payer.pubkey(),
+            &payer.pubkey(),
+            1_000_000_000,
+        ),
+        token_recipes::instruction::migrate_account(&address, &payer.pubkey()),
+    ];
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+/// Serialize a recipe with the layout it had before versions were stored, i.e. without
+/// the fields appended to its header and with zero version and bump bytes.
+fn legacy_recipe_data(recipe: &Recipe) -> Vec<u8> {
+    let mut data = Recipe {
+        version: 0,
+        bump: 0,
+        ..recipe.clone()
+    }
+    .try_to_vec()
+    .unwrap();
+    data.drain(Recipe::LEGACY_HEADER_LEN..RecipeHeader::LEN);
+    data
+}
+
+/// Start a program test with the given recipe stored with its legacy layout.
+async fn start_with_legacy_recipe(recipe: Pubkey, recipe_account: &Recipe) -> ProgramTestContext {
+    let data = legacy_recipe_data(recipe_account);
+    let mut program_test = utils::program_test();
+    program_test.add_account(
+        recipe,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: token_recipes::id(),
+            ..Account::default()
+        },
+    );
+    program_test.start_with_context().await
+}
+
+/// An active recipe with ingredients and the given authority.
+fn recipe_with_ingredients(authority: Pubkey) -> (Pubkey, Recipe) {
+    let base = Pubkey::new_unique();
+    let (recipe, bump) = find_recipe_pda(&base);
+    let inputs = vec![IngredientInput::BurnToken {
+        mint: Pubkey::new_unique(),
+        amount: 2,
+    }];
+    let outputs = vec![IngredientOutput::MintToken {
+        mint: Pubkey::new_unique(),
+        amount: 3,
+    }];
+    let recipe_account = Recipe {
+        key: Key::Recipe,
+        base,
+        authority,
+        status: RecipeStatus::Active,
+        total_crafts: 4,
+        total_crafts_with_quantity: 5,
+        fees: 6,
+        accumulated_admin_fees: 7,
+        accumulated_shards: 8,
+        accumulated_experience: 9,
+        feature_levels: FeatureLevels {
+            fees: 1,
+            ..FeatureLevels::default()
+        },
+        version: Recipe::VERSION,
+        bump,
+        pending_authority: Pubkey::default(),
+        max_crafts_per_wallet: 0,
+        start_time: 0,
+        end_time: 0,
+        allowlist_root: [0; 32],
+        inputs,
+        outputs,
+    };
+    (recipe, recipe_account)
+}
+
+#[tokio::test]
+async fn migrate_legacy_recipe() {
+    // Given a recipe with ingredients created before its header grew.
+    let (recipe, recipe_account) = recipe_with_ingredients(Pubkey::new_unique());
+    let legacy_data = legacy_recipe_data(&recipe_account);
+
+    // When anyone migrates it.
+    let account = migrate(recipe, legacy_data.clone()).await;
+
+    // Then it grows by the appended header fields and its ingredients are shifted.
+    assert_eq!(
+        account.data.len(),
+        legacy_data.len() + RecipeHeader::LEN - Recipe::LEGACY_HEADER_LEN
+    );
+    assert_eq!(
+        account.data[RecipeHeader::LEN..],
+        legacy_data[Recipe::LEGACY_HEADER_LEN..]
+    );
+    let account_data = Recipe::try_from_slice(&account.data).unwrap();
+    assert_eq!(
+        account_data.try_to_vec().unwrap(),
+        recipe_account.try_to_vec().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn cannot_use_a_legacy_recipe_before_migrating_it() {
+    // Given a legacy recipe.
+    let authority = Keypair::new();
+    let (recipe, recipe_account) = recipe_with_ingredients(authority.pubkey());
+    let mut context = start_with_legacy_recipe(recipe, &recipe_account).await;
+
+    // When its authority tries to pause it.
+    let ix = token_recipes::instruction::pause_recipe(&recipe, &authority.pubkey());
+    let result = utils::process(&mut context, &[ix], &[&authority]).await;
+
+    // Then it fails until the recipe is migrated.
+    utils::assert_program_error(result, TokenRecipesError::RecipeMustBeMigrated);
+}
+
+#[tokio::test]
+async fn quote_a_legacy_recipe_without_migrating_it() {
+    // Given a legacy recipe without inputs.
+    let (recipe, recipe_account) = recipe_with_ingredients(Pubkey::new_unique());
+    let recipe_account = Recipe {
+        inputs: vec![],
+        ..recipe_account
+    };
+    let mut context = start_with_legacy_recipe(recipe, &recipe_account).await;
+    let legacy_data = legacy_recipe_data(&recipe_account);
+
+    // When anyone quotes it.
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::quote_craft(&recipe, &recipe_account, &payer, &payer, 1);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Then the recipe is read with its legacy layout and left untouched.
+    let account = utils::get_account(&mut context, &recipe).await.unwrap();
+    assert_eq!(account.data, legacy_data);
+}
+
+#[tokio::test]
+async fn update_a_legacy_recipe_by_migrating_it_in_place() {
+    // Given a legacy recipe.
+    let authority = Keypair::new();
+    let (recipe, recipe_account) = recipe_with_ingredients(authority.pubkey());
+    let mut context = start_with_legacy_recipe(recipe, &recipe_account).await;
+
+    // When its authority updates one of its ingredients.
+    let mint = match recipe_account.inputs[0] {
+        IngredientInput::BurnToken { mint, .. } => mint,
+        _ => unreachable!(),
+    };
+    let ix = token_recipes::instruction::update_ingredient(
+        &recipe,
+        Some(&mint),
+        &authority.pubkey(),
+        &context.payer.pubkey(),
+        IngredientType::BurnTokenInput,
+        None,
+        Some(5),
+        None,
+        None,
+    );
+    utils::process(&mut context, &[ix], &[&authority])
+        .await
+        .unwrap();
+
+    // Then the recipe is migrated using the payer before being updated.
+    let account = utils::get_account(&mut context, &recipe).await.unwrap();
+    assert_eq!(account.data.len(), recipe_account.size());
+    let account_data = Recipe::try_from_slice(&account.data).unwrap();
+    assert_eq!(account_data.version, Recipe::VERSION);
+    assert_eq!(account_data.bump, recipe_account.bump);
+    assert_eq!(
+        account_data.inputs,
+        vec![IngredientInput::BurnToken { mint, amount: 5 }]
+    );
+}
+
+#[tokio::test]
+async fn migrate_legacy_delegated_ingredient() {
+    // Given a delegated ingredient created before its bump and version were stored.
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (delegated_ingredient, bump) = find_delegated_ingredient_pda(&mint);
+    let legacy_data = DelegatedIngredient {
+        key: Key::DelegatedIngredient,
+        mint,
+        authority,
+        counter: 2,
+        bump: 0,
+        version: 0,
+    }
+    .try_to_vec()
+    .unwrap()[..DelegatedIngredient::LEGACY_LEN]
+        .to_vec();
+
+    // When anyone migrates it.
+    let account = migrate(delegated_ingredient, legacy_data).await;
+
+    // Then it is resized and rewritten with the current layout.
+    assert_eq!(account.data.len(), DelegatedIngredient::LEN);
+    let account_data = DelegatedIngredient::deserialize(&mut account.data.as_ref()).unwrap();
+    assert_eq!(account_data.mint, mint);
+    assert_eq!(account_data.authority, authority);
+    assert_eq!(account_data.counter, 2);
+    assert_eq!(account_data.bump, bump);
+    assert_eq!(account_data.version, DelegatedIngredient::VERSION);
+}
+
+#[tokio::test]
+async fn migrate_fees_feature_without_fees() {
+    // Given a fees feature created before its base fees and fee percentages were stored.
+    let (fees_feature_pda, bump) = find_fees_feature_pda();
+    let admin_destination = Pubkey::new_unique();
+    let version_1_data = FeesFeature {
+        key: Key::FeesFeature,
+        admin_destination,
+        shard_mint: Pubkey::new_unique(),
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        mint_burn_3: Pubkey::new_unique(),
+        mint_burn_4: Pubkey::new_unique(),
+        mint_burn_5: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        mint_skill_2: Pubkey::new_unique(),
+        mint_skill_3: Pubkey::new_unique(),
+        bump,
+        version: 1,
+        base_fees: 0,
+        fee_percentages: [0; 12],
+    }
+    .try_to_vec()
+    .unwrap()[..FeesFeature::LEGACY_LEN + 2]
+        .to_vec();
+
+    // When anyone migrates it.
+    let account = migrate(fees_feature_pda, version_1_data).await;
+
+    // Then it is resized and stores the default fees.
+    assert_eq!(account.data.len(), FeesFeature::LEN);
+    let account_data = FeesFeature::deserialize(&mut account.data.as_ref()).unwrap();
+    assert_eq!(account_data.admin_destination, admin_destination);
+    assert_eq!(account_data.bump, bump);
+    assert_eq!(account_data.version, FeesFeature::VERSION);
+    assert_eq!(account_data.base_fees, DEFAULT_BASE_FEES);
+    assert_eq!(account_data.fee_percentages, DEFAULT_FEE_PERCENTAGES);
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshDeserialize;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::find_program_config_pda,
+    state::{key::Key, program_config::ProgramConfig},
+};
+
+async fn get_program_config(context: &mut ProgramTestContext) -> ProgramConfig {
+    let (address, _) = find_program_config_pda();
+    let account = utils::get_account(context, &address).await.unwrap();
+    assert_eq!(account.owner, token_recipes::id());
+    ProgramConfig::deserialize(&mut account.data.as_ref()).unwrap()
+}
+
+#[tokio::test]
+async fn initialize_the_program_config() {
+    // Given the upgrade authority of the program.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let upgrade_authority = Keypair::new();
+    utils::set_upgrade_authority(&mut context, &upgrade_authority.pubkey());
+
+    // When it initializes the program config.
+    let admin = Pubkey::new_unique();
+    let ix = token_recipes::instruction::initialize_program_config(
+        &admin,
+        &upgrade_authority.pubkey(),
+        &payer,
+    );
+    utils::process(&mut context, &[ix], &[&upgrade_authority])
+        .await
+        .unwrap();
+
+    // Then the program config stores the admin.
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.key, Key::ProgramConfig);
+    assert_eq!(program_config.admin, admin);
+    assert_eq!(program_config.pending_admin, Pubkey::default());
+    assert_eq!(program_config.bump, find_program_config_pda().1);
+    assert_eq!(program_config.version, ProgramConfig::VERSION);
+}
+
+#[tokio::test]
+async fn cannot_initialize_the_program_config_twice() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    utils::initialize_program_config(&mut context).await;
+    let upgrade_authority = Keypair::new();
+    utils::set_upgrade_authority(&mut context, &upgrade_authority.pubkey());
+
+    let ix = token_recipes::instruction::initialize_program_config(
+        &upgrade_authority.pubkey(),
+        &upgrade_authority.pubkey(),
+        &payer,
+    );
+    let result = utils::process(&mut context, &[ix], &[&upgrade_authority]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::ExpectedEmptyAccount);
+}
+
+#[tokio::test]
+async fn cannot_initialize_the_program_config_without_the_upgrade_authority() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    utils::set_upgrade_authority(&mut context, &Pubkey::new_unique());
+    let impostor = Keypair::new();
+
+    let ix = token_recipes::instruction::initialize_program_config(
+        &impostor.pubkey(),
+        &impostor.pubkey(),
+        &payer,
+    );
+    let result = utils::process(&mut context, &[ix], &[&impostor]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidUpgradeAuthority);
+}
+
+#[tokio::test]
+async fn cannot_initialize_the_program_config_with_another_program_data_account() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let upgrade_authority = Keypair::new();
+    utils::set_upgrade_authority(&mut context, &upgrade_authority.pubkey());
+
+    let mut ix = token_recipes::instruction::initialize_program_config(
+        &upgrade_authority.pubkey(),
+        &upgrade_authority.pubkey(),
+        &payer,
+    );
+    ix.accounts[3].pubkey = Pubkey::new_unique();
+    let result = utils::process(&mut context, &[ix], &[&upgrade_authority]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidProgramData);
+}
+
+#[tokio::test]
+async fn transfer_and_accept_the_admin_authority() {
+    // Given an initialized program config.
+    let mut context = utils::start().await;
+    let admin = utils::initialize_program_config(&mut context).await;
+
+    // When the admin authority is transferred and accepted.
+    let new_admin = Keypair::new();
+    let ix =
+        token_recipes::instruction::transfer_admin_authority(&admin.pubkey(), &new_admin.pubkey());
+    utils::process(&mut context, &[ix], &[&admin])
+        .await
+        .unwrap();
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.pending_admin, new_admin.pubkey());
+    let ix = token_recipes::instruction::accept_admin_authority(&new_admin.pubkey());
+    utils::process(&mut context, &[ix], &[&new_admin])
+        .await
+        .unwrap();
+
+    // Then the program config belongs to the new admin.
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.admin, new_admin.pubkey());
+    assert_eq!(program_config.pending_admin, Pubkey::default());
+}
+
+#[tokio::test]
+async fn cancel_a_pending_admin_transfer() {
+    let mut context = utils::start().await;
+    let admin = utils::initialize_program_config(&mut context).await;
+    let new_admin = Keypair::new();
+    let ix =
+        token_recipes::instruction::transfer_admin_authority(&admin.pubkey(), &new_admin.pubkey());
+    utils::process(&mut context, &[ix], &[&admin])
+        .await
+        .unwrap();
+
+    let ix = token_recipes::instruction::transfer_admin_authority(&admin.pubkey(), &admin.pubkey());
+    utils::process(&mut context, &[ix], &[&admin])
+        .await
+        .unwrap();
+
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.admin, admin.pubkey());
+    assert_eq!(program_config.pending_admin, Pubkey::default());
+}
+
+#[tokio::test]
+async fn cannot_transfer_the_admin_authority_as_another_admin() {
+    let mut context = utils::start().await;
+    utils::initialize_program_config(&mut context).await;
+    let impostor = Keypair::new();
+
+    let ix = token_recipes::instruction::transfer_admin_authority(
+        &impostor.pubkey(),
+        &impostor.pubkey(),
+    );
+    let result = utils::process(&mut context, &[ix], &[&impostor]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
+
+#[tokio::test]
+async fn cannot_accept_without_pending_admin() {
+    let mut context = utils::start().await;
+    utils::initialize_program_config(&mut context).await;
+    let new_admin = Keypair::new();
+
+    let ix = token_recipes::instruction::accept_admin_authority(&new_admin.pubkey());
+    let result = utils::process(&mut context, &[ix], &[&new_admin]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::NoPendingAuthority);
+}
+
+#[tokio::test]
+async fn cannot_accept_as_another_pending_admin() {
+    let mut context = utils::start().await;
+    let admin = utils::initialize_program_config(&mut context).await;
+    let ix = token_recipes::instruction::transfer_admin_authority(
+        &admin.pubkey(),
+        &Pubkey::new_unique(),
+    );
+    utils::process(&mut context, &[ix], &[&admin])
+        .await
+        .unwrap();
+    let impostor = Keypair::new();
+
+    let ix = token_recipes::instruction::accept_admin_authority(&impostor.pubkey());
+    let result = utils::process(&mut context, &[ix], &[&impostor]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
@@ -0,0 +1,201 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshSerialize;
+use solana_program::{instruction::InstructionError, program::MAX_RETURN_DATA};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    account::AccountSharedData,
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    state::{
+        craft_quote::{CraftQuote, TokenAmount},
+        features::{fees::DEFAULT_BASE_FEES, Feature},
+        recipe::IngredientType,
+    },
+};
+
+/// Simulate the QuoteCraft instruction and decode its return data.
+async fn quote_craft(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quantity: u64,
+) -> Result<CraftQuote, TransactionError> {
+    let recipe_account = utils::get_recipe(context, recipe).await;
+    let ix =
+        token_recipes::instruction::quote_craft(recipe, &recipe_account, owner, payer, quantity);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    simulation.result.unwrap()?;
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, token_recipes::id());
+    Ok(CraftQuote::from_return_data(&return_data.data).unwrap())
+}
+
+#[tokio::test]
+async fn quote_a_recipe() {
+    // Given a recipe with a burn input, a SOL payment and a mint output.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.sol_payment = 11;
+    })
+    .await;
+    let input_mint = utils::create_mint(&mut context, &payer).await;
+    let output_mint = utils::create_mint(&mut context, &payer).await;
+    let destination = Some(Pubkey::new_unique());
+    let ingredients = [
+        (Some(&input_mint), IngredientType::BurnTokenInput, 2, None),
+        (
+            None,
+            IngredientType::TransferSolInput,
+            1_000_000,
+            destination,
+        ),
+        (Some(&output_mint), IngredientType::MintTokenOutput, 1, None),
+    ];
+    for (mint, ingredient_type, amount, destination) in ingredients {
+        utils::add_ingredient(
+            &mut context,
+            &recipe,
+            mint,
+            ingredient_type,
+            amount,
+            destination,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::mint_to(&mut context, &input_mint, &owner.pubkey(), 6).await;
+
+    // When we quote crafting it 3 times.
+    let owner = owner.pubkey();
+    let quote = quote_craft(&mut context, &recipe, &owner, &owner, 3)
+        .await
+        .unwrap();
+
+    // Then we get the costs and rewards of the craft.
+    assert_eq!(
+        quote,
+        CraftQuote {
+            quantity: 3,
+            token_costs: vec![TokenAmount {
+                mint: input_mint,
+                amount: 6
+            }],
+            lamport_costs: 3_000_000,
+            fees: DEFAULT_BASE_FEES,
+            admin_fees: DEFAULT_BASE_FEES,
+            shards: 0,
+            experience: 100,
+        }
+    );
+}
+
+#[tokio::test]
+async fn cannot_quote_a_recipe_without_enough_lamports() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, DEFAULT_BASE_FEES - 1).await;
+
+    let owner = owner.pubkey();
+    let result = quote_craft(&mut context, &recipe, &owner, &owner, 1).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenRecipesError::NotEnoughLamports as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn cannot_quote_a_recipe_with_a_fees_feature_outside_its_pda() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let fake_fees_feature = Pubkey::new_unique();
+    let (_, account) = utils::feature_account(Feature::Fees(utils::fees_feature()));
+    context.set_account(&fake_fees_feature, &AccountSharedData::from(account));
+
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    let owner = owner.pubkey();
+    let mut ix =
+        token_recipes::instruction::quote_craft(&recipe, &recipe_account, &owner, &owner, 1);
+    ix.accounts[4].pubkey = fake_fees_feature;
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidPda);
+}
+
+#[tokio::test]
+async fn cannot_quote_a_recipe_whose_quote_exceeds_the_return_data() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    for _ in 0..=CraftQuote::MAX_TOKEN_COSTS {
+        let mint = utils::create_mint(&mut context, &payer).await;
+        utils::add_ingredient(
+            &mut context,
+            &recipe,
+            Some(&mint),
+            IngredientType::BurnTokenInput,
+            1,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    let owner = owner.pubkey();
+    let ix = token_recipes::instruction::quote_craft_with_remaining_accounts(
+        &recipe,
+        &owner,
+        &owner,
+        None,
+        1,
+        vec![],
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::CraftQuoteTooLarge);
+
+    let token_amount = TokenAmount {
+        mint: Pubkey::new_unique(),
+        amount: u64::MAX,
+    };
+    let largest_quote = CraftQuote {
+        quantity: u64::MAX,
+        token_costs: vec![token_amount; CraftQuote::MAX_TOKEN_COSTS],
+        lamport_costs: u64::MAX,
+        fees: u64::MAX,
+        admin_fees: u64::MAX,
+        shards: u64::MAX,
+        experience: u64::MAX,
+    };
+    let size = largest_quote.try_to_vec().unwrap().len();
+    assert!(size <= MAX_RETURN_DATA);
+    assert!(size + TokenAmount::LEN > MAX_RETURN_DATA);
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshDeserialize;
+use solana_program::program_option::COption;
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::find_delegated_ingredient_pda,
+    state::{delegated_ingredient::DelegatedIngredient, recipe::IngredientType},
+};
+
+#[tokio::test]
+async fn transfer_and_accept_the_recipe_authority() {
+    // Given a recipe with a mint output.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let mint_output = IngredientType::MintTokenOutput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        mint_output,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // When the authority is transferred and accepted.
+    let new_authority = Keypair::new();
+    let ix = token_recipes::instruction::transfer_recipe_authority(
+        &recipe,
+        &payer,
+        &new_authority.pubkey(),
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.pending_authority, new_authority.pubkey());
+    let ix = token_recipes::instruction::accept_recipe_authority(
+        &recipe,
+        &recipe_account,
+        &new_authority.pubkey(),
+    );
+    utils::process(&mut context, &[ix], &[&new_authority])
+        .await
+        .unwrap();
+
+    // Then the recipe and its delegated ingredient belong to the new authority.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.authority, new_authority.pubkey());
+    assert_eq!(recipe_account.pending_authority, Pubkey::default());
+    let (delegated_ingredient, _) = find_delegated_ingredient_pda(&mint);
+    let account = utils::get_account(&mut context, &delegated_ingredient)
+        .await
+        .unwrap();
+    let delegated = DelegatedIngredient::deserialize(&mut account.data.as_ref()).unwrap();
+    assert_eq!(delegated.authority, new_authority.pubkey());
+}
+
+#[tokio::test]
+async fn cannot_accept_without_pending_authority() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    let new_authority = Keypair::new();
+
+    let ix = token_recipes::instruction::accept_recipe_authority(
+        &recipe,
+        &recipe_account,
+        &new_authority.pubkey(),
+    );
+    let result = utils::process(&mut context, &[ix], &[&new_authority]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::NoPendingAuthority);
+}
+
+#[tokio::test]
+async fn accept_a_recipe_sharing_a_delegated_ingredient() {
+    // Given two recipes minting the same output.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let other_recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    for recipe in [&recipe, &other_recipe] {
+        let mint_output = IngredientType::MintTokenOutput;
+        utils::add_ingredient(
+            &mut context,
+            recipe,
+            Some(&mint),
+            mint_output,
+            1,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    // When the authority of one of them is transferred and accepted.
+    let new_authority = Keypair::new();
+    let ix = token_recipes::instruction::transfer_recipe_authority(
+        &recipe,
+        &payer,
+        &new_authority.pubkey(),
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    let ix = token_recipes::instruction::accept_recipe_authority(
+        &recipe,
+        &recipe_account,
+        &new_authority.pubkey(),
+    );
+    utils::process(&mut context, &[ix], &[&new_authority])
+        .await
+        .unwrap();
+
+    // Then the shared delegated ingredient stays with the previous authority.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.authority, new_authority.pubkey());
+    let (delegated_ingredient, _) = find_delegated_ingredient_pda(&mint);
+    let account = utils::get_account(&mut context, &delegated_ingredient)
+        .await
+        .unwrap();
+    let delegated = DelegatedIngredient::deserialize(&mut account.data.as_ref()).unwrap();
+    assert_eq!(delegated.authority, payer);
+
+    // And the previous authority gets the mint authority back once both recipes removed it.
+    let ixs = [
+        token_recipes::instruction::remove_ingredient(
+            &recipe,
+            Some(&mint),
+            &new_authority.pubkey(),
+            &payer,
+            &spl_token::id(),
+            IngredientType::MintTokenOutput,
+        ),
+        token_recipes::instruction::remove_ingredient(
+            &other_recipe,
+            Some(&mint),
+            &payer,
+            &payer,
+            &spl_token::id(),
+            IngredientType::MintTokenOutput,
+        ),
+    ];
+    utils::process(&mut context, &ixs, &[&new_authority])
+        .await
+        .unwrap();
+    assert!(utils::get_account(&mut context, &delegated_ingredient)
+        .await
+        .is_none());
+    let mint_account = utils::get_mint(&mut context, &mint).await;
+    assert_eq!(mint_account.mint_authority, COption::Some(payer));
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use borsh::BorshDeserialize;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use token_recipes::state::{
+    recipe::{IngredientType, Recipe},
+    recipe_header::RecipeHeader,
+};
+
+/// Create a recipe with the given amount of burn inputs and a single mint output.
+/// The owner holds enough tokens to craft the recipe once.
+async fn create_recipe_with_inputs(
+    context: &mut ProgramTestContext,
+    inputs: usize,
+    owner: &Pubkey,
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(context).await;
+    for _ in 0..inputs {
+        let mint = utils::create_mint(context, &payer).await;
+        utils::mint_to(context, &mint, owner, 1).await;
+        let burn_input = IngredientType::BurnTokenInput;
+        utils::add_ingredient(context, &recipe, Some(&mint), burn_input, 1, None, None)
+            .await
+            .unwrap();
+    }
+    let mint = utils::create_mint(context, &payer).await;
+    let mint_output = IngredientType::MintTokenOutput;
+    utils::add_ingredient(context, &recipe, Some(&mint), mint_output, 1, None, None)
+        .await
+        .unwrap();
+    recipe
+}
+
+/// Simulate the instruction and return the compute units it consumed.
+async fn simulate_units(context: &mut ProgramTestContext, ix: Instruction) -> u64 {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+    simulation.simulation_details.unwrap().units_consumed
+}
+
+async fn activate_recipe_units(context: &mut ProgramTestContext, recipe: &Pubkey) -> u64 {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::activate_recipe(recipe, &payer);
+    simulate_units(context, ix).await
+}
+
+/// The compute units of writing the header of a recipe, using SetMaxCraftsPerWallet,
+/// and of saving the whole recipe with Borsh, using MigrateAccount on an up-to-date
+/// recipe which deserializes the recipe and serializes it back.
+async fn header_and_borsh_save_units(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+) -> (u64, u64) {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::set_max_crafts_per_wallet(recipe, &payer, 1);
+    let header_units = simulate_units(context, ix).await;
+    let ix = token_recipes::instruction::migrate_account(recipe, &payer);
+    let borsh_save_units = simulate_units(context, ix).await;
+    (header_units, borsh_save_units)
+}
+
+#[tokio::test]
+async fn recipe_header_matches_recipe_layout() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = create_recipe_with_inputs(&mut context, 3, &payer).await;
+
+    let account = utils::get_account(&mut context, &recipe).await.unwrap();
+    let recipe_account = Recipe::deserialize(&mut account.data.as_ref()).unwrap();
+    let header: &RecipeHeader = bytemuck::from_bytes(&account.data[..RecipeHeader::LEN]);
+
+    assert_eq!(
+        bytemuck::bytes_of(header),
+        bytemuck::bytes_of(&RecipeHeader::from(&recipe_account))
+    );
+    assert_eq!(header.authority, context.payer.pubkey());
+    assert_eq!(recipe_account.inputs.len(), 3);
+}
+
+#[tokio::test]
+async fn activating_a_recipe_does_not_depend_on_its_ingredients() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let small_recipe = create_recipe_with_inputs(&mut context, 1, &payer).await;
+    let large_recipe = create_recipe_with_inputs(&mut context, 10, &payer).await;
+
+    let small_units = activate_recipe_units(&mut context, &small_recipe).await;
+    let large_units = activate_recipe_units(&mut context, &large_recipe).await;
+
+    // The ingredients are never deserialized.
+    assert_eq!(small_units, large_units);
+}
+
+#[tokio::test]
+async fn writing_the_header_is_cheaper_than_a_borsh_save() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let small_recipe = create_recipe_with_inputs(&mut context, 1, &payer).await;
+    let large_recipe = create_recipe_with_inputs(&mut context, 12, &payer).await;
+
+    let (small_header, small_borsh_save) =
+        header_and_borsh_save_units(&mut context, &small_recipe).await;
+    let (large_header, large_borsh_save) =
+        header_and_borsh_save_units(&mut context, &large_recipe).await;
+
+    // Writing the header does not depend on the ingredients
+    // whereas a Borsh save grows with every ingredient.
+    assert_eq!(small_header, large_header);
+    assert!(small_header < small_borsh_save);
+    assert!(small_borsh_save < large_borsh_save);
+}
+
+#[tokio::test]
+async fn craft_a_recipe_with_many_ingredients() {
+    // Given an active recipe with 10 burn inputs.
+    let mut context = utils::start().await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let recipe = create_recipe_with_inputs(&mut context, 10, &owner.pubkey()).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let ingredients = utils::get_account(&mut context, &recipe)
+        .await
+        .unwrap()
+        .data[RecipeHeader::LEN..]
+        .to_vec();
+
+    // When the owner crafts it.
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+
+    // Then only the header of the recipe was written.
+    let account = utils::get_account(&mut context, &recipe).await.unwrap();
+    assert_eq!(account.data[RecipeHeader::LEN..], ingredients);
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.total_crafts, 1);
+}
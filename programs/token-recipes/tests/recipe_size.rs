@@ -0,0 +1,224 @@
+mod utils;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use proptest::{collection::vec, prelude::*, sample::Index};
+use solana_program::{
+    entrypoint::ProgramResult, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent,
+    system_program,
+};
+use token_recipes::{
+    instruction::create_recipe,
+    pda::find_recipe_pda,
+    state::{
+        ingredient_input::IngredientInput,
+        ingredient_output::{IngredientOutput, LootTableEntry},
+        recipe::Recipe,
+    },
+};
+use utils::runtime::{process_instruction, with_account_infos, TestAccount};
+
+fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+}
+
+fn ingredient_input() -> impl Strategy<Value = IngredientInput> {
+    prop_oneof![
+        (pubkey(), any::<u64>())
+            .prop_map(|(mint, amount)| IngredientInput::BurnToken { mint, amount }),
+        (pubkey(), any::<u64>(), pubkey()).prop_map(|(mint, amount, destination)| {
+            IngredientInput::TransferToken {
+                mint,
+                amount,
+                destination,
+            }
+        }),
+        (any::<u64>(), pubkey()).prop_map(|(lamports, destination)| {
+            IngredientInput::TransferSol {
+                lamports,
+                destination,
+            }
+        }),
+        (pubkey(), any::<u64>())
+            .prop_map(|(mint, amount)| IngredientInput::HoldToken { mint, amount }),
+    ]
+}
+
+fn loot_table_entry() -> impl Strategy<Value = LootTableEntry> {
+    (pubkey(), any::<u64>(), any::<u16>()).prop_map(|(mint, amount, weight)| LootTableEntry {
+        mint,
+        amount,
+        weight,
+    })
+}
+
+fn ingredient_output() -> impl Strategy<Value = IngredientOutput> {
+    prop_oneof![
+        (pubkey(), any::<u64>())
+            .prop_map(|(mint, amount)| IngredientOutput::MintToken { mint, amount }),
+        (pubkey(), any::<u64>(), any::<u64>()).prop_map(|(mint, amount, max_supply)| {
+            IngredientOutput::MintTokenWithMaxSupply {
+                mint,
+                amount,
+                max_supply,
+            }
+        }),
+        (pubkey(), any::<u64>())
+            .prop_map(|(mint, amount)| IngredientOutput::TransferToken { mint, amount }),
+        (any::<u8>(), vec(loot_table_entry(), 0..32)).prop_map(|(rolls, entries)| {
+            IngredientOutput::MintTokenFromLootTable { rolls, entries }
+        }),
+    ]
+}
+
+#[derive(Clone, Debug)]
+enum Operation {
+    AddInput(IngredientInput),
+    AddOutput(IngredientOutput),
+    UpdateInput(Index, IngredientInput),
+    UpdateOutput(Index, IngredientOutput),
+    RemoveInput(Index),
+    RemoveOutput(Index),
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        ingredient_input().prop_map(Operation::AddInput),
+        ingredient_output().prop_map(Operation::AddOutput),
+        (any::<Index>(), ingredient_input())
+            .prop_map(|(index, input)| Operation::UpdateInput(index, input)),
+        (any::<Index>(), ingredient_output())
+            .prop_map(|(index, output)| Operation::UpdateOutput(index, output)),
+        any::<Index>().prop_map(Operation::RemoveInput),
+        any::<Index>().prop_map(Operation::RemoveOutput),
+    ]
+}
+
+/// Create an empty recipe and return the recipe, payer and system program accounts.
+fn create_empty_recipe() -> [TestAccount; 3] {
+    let base = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let mut accounts = [
+        TestAccount {
+            key: base,
+            is_signer: true,
+            ..TestAccount::default()
+        },
+        TestAccount {
+            key: find_recipe_pda(&base).0,
+            is_writable: true,
+            ..TestAccount::default()
+        },
+        TestAccount {
+            key: Pubkey::new_unique(),
+            ..TestAccount::default()
+        },
+        TestAccount {
+            key: payer,
+            lamports: 1_000 * LAMPORTS_PER_SOL,
+            is_signer: true,
+            is_writable: true,
+            ..TestAccount::default()
+        },
+        TestAccount {
+            key: system_program::id(),
+            executable: true,
+            ..TestAccount::default()
+        },
+    ];
+    let data = create_recipe(&base, &accounts[2].key, &payer).data;
+    process_instruction(&mut accounts, &data).unwrap();
+    let [_, recipe, _, payer, system_program] = accounts;
+    [recipe, payer, system_program]
+}
+
+/// Apply an operation to the recipe account using the `Recipe` methods of the program.
+fn apply(accounts: &mut [TestAccount; 3], operation: &Operation) -> ProgramResult {
+    with_account_infos(accounts, &[], |_, accounts, _| {
+        let (recipe, payer, system_program) = (&accounts[0], &accounts[1], &accounts[2]);
+        let mut recipe_account = Recipe::load(recipe)?;
+        let inputs = recipe_account.inputs.len();
+        let outputs = recipe_account.outputs.len();
+        match operation {
+            Operation::AddInput(input) => {
+                recipe_account.add_ingredient_input(input, recipe, payer, system_program)
+            }
+            Operation::AddOutput(output) => {
+                recipe_account.add_ingredient_output(output, recipe, payer, system_program)
+            }
+            Operation::UpdateInput(index, input) if inputs > 0 => recipe_account
+                .update_ingredient_input(index.index(inputs), input, recipe, payer, system_program),
+            Operation::UpdateOutput(index, output) if outputs > 0 => recipe_account
+                .update_ingredient_output(
+                    index.index(outputs),
+                    output,
+                    recipe,
+                    payer,
+                    system_program,
+                ),
+            Operation::RemoveInput(index) if inputs > 0 => recipe_account
+                .remove_ingredient_input(index.index(inputs), recipe, payer, system_program)
+                .map(|_| ()),
+            Operation::RemoveOutput(index) if outputs > 0 => recipe_account
+                .remove_ingredient_output(index.index(outputs), recipe, payer, system_program)
+                .map(|_| ()),
+            _ => Ok(()),
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn ingredient_input_len_is_its_serialized_size(input in ingredient_input()) {
+        prop_assert_eq!(input.len(), input.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn ingredient_output_len_is_its_serialized_size(output in ingredient_output()) {
+        prop_assert_eq!(output.len(), output.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn recipe_stays_round_trippable_when_adding_and_removing_ingredients(
+        operations in vec(operation(), 1..32)
+    ) {
+        let mut accounts = create_empty_recipe();
+        let mut expected_inputs: Vec<IngredientInput> = vec![];
+        let mut expected_outputs: Vec<IngredientOutput> = vec![];
+
+        for operation in &operations {
+            apply(&mut accounts, operation).unwrap();
+            match operation {
+                Operation::AddInput(input) => expected_inputs.push(input.clone()),
+                Operation::AddOutput(output) => expected_outputs.push(output.clone()),
+                Operation::UpdateInput(index, input) if !expected_inputs.is_empty() => {
+                    let index = index.index(expected_inputs.len());
+                    expected_inputs[index] = input.clone();
+                }
+                Operation::UpdateOutput(index, output) if !expected_outputs.is_empty() => {
+                    let index = index.index(expected_outputs.len());
+                    expected_outputs[index] = output.clone();
+                }
+                Operation::RemoveInput(index) if !expected_inputs.is_empty() => {
+                    expected_inputs.remove(index.index(expected_inputs.len()));
+                }
+                Operation::RemoveOutput(index) if !expected_outputs.is_empty() => {
+                    expected_outputs.remove(index.index(expected_outputs.len()));
+                }
+                _ => {}
+            }
+
+            // The account holds exactly the serialized recipe, without any trailing bytes.
+            let recipe_account = &accounts[0];
+            let recipe = Recipe::deserialize(&mut recipe_account.data.as_ref()).unwrap();
+            prop_assert_eq!(&recipe.inputs, &expected_inputs);
+            prop_assert_eq!(&recipe.outputs, &expected_outputs);
+            prop_assert_eq!(&recipe.try_to_vec().unwrap(), &recipe_account.data);
+
+            // And it stays rent exempt without holding more lamports than needed.
+            prop_assert_eq!(
+                recipe_account.lamports,
+                Rent::default().minimum_balance(recipe_account.data.len())
+            );
+        }
+    }
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program::program_option::COption;
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{find_delegated_ingredient_pda, find_ingredient_record_pda},
+    state::recipe::IngredientType,
+};
+
+#[tokio::test]
+async fn remove_an_input_and_an_output() {
+    // Given a recipe with a burn input and a mint output.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let input_mint = utils::create_mint(&mut context, &payer).await;
+    let output_mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    let mint_output = IngredientType::MintTokenOutput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&input_mint),
+        burn_input,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&output_mint),
+        mint_output,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // When we remove both ingredients.
+    let ixs = [
+        token_recipes::instruction::remove_ingredient(
+            &recipe,
+            Some(&input_mint),
+            &payer,
+            &payer,
+            &spl_token::id(),
+            IngredientType::BurnTokenInput,
+        ),
+        token_recipes::instruction::remove_ingredient(
+            &recipe,
+            Some(&output_mint),
+            &payer,
+            &payer,
+            &spl_token::id(),
+            IngredientType::MintTokenOutput,
+        ),
+    ];
+    utils::process(&mut context, &ixs, &[]).await.unwrap();
+
+    // Then the recipe is empty.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert!(recipe_account.inputs.is_empty());
+    assert!(recipe_account.outputs.is_empty());
+
+    // And the ingredient records and the delegated ingredient are closed.
+    for mint in [&input_mint, &output_mint] {
+        let (ingredient_record, _) = find_ingredient_record_pda(mint, &recipe);
+        assert!(utils::get_account(&mut context, &ingredient_record)
+            .await
+            .is_none());
+    }
+    let (delegated_ingredient, _) = find_delegated_ingredient_pda(&output_mint);
+    assert!(utils::get_account(&mut context, &delegated_ingredient)
+        .await
+        .is_none());
+
+    // And the mint authority is handed back to the recipe authority.
+    let mint_account = utils::get_mint(&mut context, &output_mint).await;
+    assert_eq!(mint_account.mint_authority, COption::Some(payer));
+}
+
+#[tokio::test]
+async fn cannot_remove_a_missing_ingredient() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+
+    let ix = token_recipes::instruction::remove_ingredient(
+        &recipe,
+        Some(&mint),
+        &payer,
+        &payer,
+        &spl_token::id(),
+        IngredientType::BurnTokenInput,
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::MissingIngredient);
+}
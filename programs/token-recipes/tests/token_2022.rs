@@ -0,0 +1,363 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey, system_instruction};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::signature::{Keypair, Signer};
+use spl_token_2022::{
+    extension::{
+        memo_transfer::instruction::enable_required_transfer_memos,
+        transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account, Mint},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    instruction::{self, craft_input_accounts, craft_output_accounts},
+    pda::find_associated_token_address,
+    state::recipe::IngredientType,
+};
+
+/// Create a new Token-2022 mint with no decimals whose mint authority is the context payer.
+async fn create_mint(
+    context: &mut ProgramTestContext,
+    transfer_fee_basis_points: Option<u16>,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let extensions = match transfer_fee_basis_points {
+        Some(_) => vec![ExtensionType::TransferFeeConfig],
+        None => vec![],
+    };
+    let space = ExtensionType::get_account_len::<Mint>(&extensions);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mut ixs = vec![system_instruction::create_account(
+        &payer,
+        &mint.pubkey(),
+        rent.minimum_balance(space),
+        space as u64,
+        &spl_token_2022::id(),
+    )];
+    if let Some(basis_points) = transfer_fee_basis_points {
+        ixs.push(
+            initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                None,
+                None,
+                basis_points,
+                u64::MAX,
+            )
+            .unwrap(),
+        );
+    }
+    ixs.push(
+        spl_token_2022::instruction::initialize_mint(
+            &spl_token_2022::id(),
+            &mint.pubkey(),
+            &payer,
+            None,
+            0,
+        )
+        .unwrap(),
+    );
+    utils::process(context, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Create the associated token account of the owner and mint tokens to it.
+async fn mint_to(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let token = find_associated_token_address(owner, mint, &spl_token_2022::id());
+    let mut ixs = vec![
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            owner,
+            mint,
+            &spl_token_2022::id(),
+        ),
+    ];
+    if amount > 0 {
+        ixs.push(
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::id(),
+                mint,
+                &token,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        );
+    }
+    utils::process(context, &ixs, &[]).await.unwrap();
+    token
+}
+
+async fn get_token_balance(context: &mut ProgramTestContext, token: &Pubkey) -> u64 {
+    match utils::get_account(context, token).await {
+        Some(account) => {
+            StateWithExtensions::<Account>::unpack(&account.data)
+                .unwrap()
+                .base
+                .amount
+        }
+        None => 0,
+    }
+}
+
+async fn get_mint_supply(context: &mut ProgramTestContext, mint: &Pubkey) -> u64 {
+    let account = utils::get_account(context, mint).await.unwrap();
+    StateWithExtensions::<Mint>::unpack(&account.data)
+        .unwrap()
+        .base
+        .supply
+}
+
+async fn add_ingredient(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    mint: &Pubkey,
+    ingredient_type: IngredientType,
+    amount: u64,
+    destination: Option<Pubkey>,
+) {
+    let payer = context.payer.pubkey();
+    let ix = instruction::add_ingredient(
+        recipe,
+        Some(mint),
+        &payer,
+        &payer,
+        &spl_token_2022::id(),
+        ingredient_type,
+        amount,
+        destination,
+        None,
+    );
+    utils::process(context, &[ix], &[]).await.unwrap();
+}
+
+/// Craft a recipe whose mints are all owned by the Token-2022 program.
+async fn craft(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    owner: &Keypair,
+    additional_accounts: Vec<AccountMeta>,
+) -> Result<(), solana_program_test::BanksClientError> {
+    let recipe_account = utils::get_recipe(context, recipe).await;
+    let mut remaining_accounts: Vec<AccountMeta> = recipe_account
+        .inputs
+        .iter()
+        .flat_map(|input| craft_input_accounts(input, &owner.pubkey(), &spl_token_2022::id()))
+        .collect();
+    remaining_accounts.extend(additional_accounts);
+    remaining_accounts.extend(recipe_account.outputs.iter().flat_map(|output| {
+        craft_output_accounts(output, recipe, &owner.pubkey(), &spl_token_2022::id())
+    }));
+    let mut ix = instruction::craft_with_remaining_accounts(
+        recipe,
+        &owner.pubkey(),
+        &owner.pubkey(),
+        None,
+        1,
+        vec![],
+        remaining_accounts,
+    );
+    // The top-level token program may be either token program.
+    ix.accounts[4].pubkey = spl_token_2022::id();
+    utils::process(context, &[ix], &[owner]).await
+}
+
+#[tokio::test]
+async fn craft_with_token_2022_mints() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let input_mint = create_mint(&mut context, None).await;
+    let output_mint = create_mint(&mut context, None).await;
+    add_ingredient(
+        &mut context,
+        &recipe,
+        &input_mint,
+        IngredientType::BurnTokenInput,
+        2,
+        None,
+    )
+    .await;
+    add_ingredient(
+        &mut context,
+        &recipe,
+        &output_mint,
+        IngredientType::MintTokenOutput,
+        3,
+        None,
+    )
+    .await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let input_token = mint_to(&mut context, &input_mint, &owner.pubkey(), 5).await;
+    craft(&mut context, &recipe, &owner, vec![]).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &input_token).await, 3);
+    assert_eq!(get_mint_supply(&mut context, &input_mint).await, 3);
+    let output_token =
+        find_associated_token_address(&owner.pubkey(), &output_mint, &spl_token_2022::id());
+    assert_eq!(get_token_balance(&mut context, &output_token).await, 3);
+}
+
+#[tokio::test]
+async fn craft_with_a_transfer_fee_mint() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.transfer_inputs = 1;
+    })
+    .await;
+    let destination = Pubkey::new_unique();
+    // A 10% transfer fee.
+    let input_mint = create_mint(&mut context, Some(1_000)).await;
+    add_ingredient(
+        &mut context,
+        &recipe,
+        &input_mint,
+        IngredientType::TransferTokenInput,
+        100,
+        Some(destination),
+    )
+    .await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let input_token = mint_to(&mut context, &input_mint, &owner.pubkey(), 100).await;
+    craft(&mut context, &recipe, &owner, vec![]).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &input_token).await, 0);
+    let destination_token =
+        find_associated_token_address(&destination, &input_mint, &spl_token_2022::id());
+    assert_eq!(
+        get_token_balance(&mut context, &destination_token).await,
+        90
+    );
+}
+
+#[tokio::test]
+async fn cannot_craft_without_the_memo_program_when_the_destination_requires_memos() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.transfer_inputs = 1;
+    })
+    .await;
+    let destination = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let input_mint = create_mint(&mut context, None).await;
+    add_ingredient(
+        &mut context,
+        &recipe,
+        &input_mint,
+        IngredientType::TransferTokenInput,
+        1,
+        Some(destination.pubkey()),
+    )
+    .await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    // Require memos on the destination token account.
+    let destination_token = mint_to(&mut context, &input_mint, &destination.pubkey(), 0).await;
+    let payer = context.payer.pubkey();
+    let ixs = [
+        spl_token_2022::instruction::reallocate(
+            &spl_token_2022::id(),
+            &destination_token,
+            &payer,
+            &destination.pubkey(),
+            &[],
+            &[ExtensionType::MemoTransfer],
+        )
+        .unwrap(),
+        enable_required_transfer_memos(
+            &spl_token_2022::id(),
+            &destination_token,
+            &destination.pubkey(),
+            &[],
+        )
+        .unwrap(),
+    ];
+    utils::process(&mut context, &ixs, &[&destination])
+        .await
+        .unwrap();
+
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    mint_to(&mut context, &input_mint, &owner.pubkey(), 1).await;
+    let not_memo_program = vec![AccountMeta::new_readonly(spl_token_2022::id(), false)];
+    let result = craft(&mut context, &recipe, &owner, not_memo_program).await;
+
+    utils::assert_program_error(result, TokenRecipesError::AccountMismatch);
+}
+
+#[tokio::test]
+async fn cannot_craft_with_the_wrong_token_program() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let input_mint = create_mint(&mut context, None).await;
+    add_ingredient(
+        &mut context,
+        &recipe,
+        &input_mint,
+        IngredientType::BurnTokenInput,
+        1,
+        None,
+    )
+    .await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    mint_to(&mut context, &input_mint, &owner.pubkey(), 1).await;
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    let mut remaining_accounts = craft_input_accounts(
+        &recipe_account.inputs[0],
+        &owner.pubkey(),
+        &spl_token_2022::id(),
+    );
+    remaining_accounts[2] = AccountMeta::new_readonly(spl_memo::id(), false);
+    let ix = instruction::craft_with_remaining_accounts(
+        &recipe,
+        &owner.pubkey(),
+        &owner.pubkey(),
+        None,
+        1,
+        vec![],
+        remaining_accounts,
+    );
+    let result = utils::process(&mut context, &[ix], &[&owner]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidTokenProgram);
+}
+
+#[tokio::test]
+async fn cannot_craft_with_a_top_level_account_that_is_not_a_token_program() {
+    let mut context = utils::start().await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let mut ix = instruction::craft_with_remaining_accounts(
+        &recipe,
+        &owner.pubkey(),
+        &owner.pubkey(),
+        None,
+        1,
+        vec![],
+        vec![],
+    );
+    ix.accounts[4].pubkey = spl_memo::id();
+    let result = utils::process(&mut context, &[ix], &[&owner]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidTokenProgram);
+}
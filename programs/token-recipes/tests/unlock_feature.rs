@@ -0,0 +1,178 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::{tokio, BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::find_feature_pda,
+    state::features::{fees::DEFAULT_BASE_FEES, Feature, FeatureLevels},
+};
+
+/// Unlock a feature of a recipe whose authority is the context payer.
+async fn unlock_feature(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    feature_pda: &Pubkey,
+    owner: &Keypair,
+    mint: &Pubkey,
+) -> Result<(), BanksClientError> {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::unlock_feature(
+        recipe,
+        feature_pda,
+        &payer,
+        &owner.pubkey(),
+        mint,
+        &spl_token::id(),
+    );
+    utils::process(context, &[ix], &[owner]).await
+}
+
+#[tokio::test]
+async fn unlock_every_feature_by_burning_a_token() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let recipe = utils::create_recipe(&mut context).await;
+
+    // Given every feature uses a real mint as its first burn mint.
+    let mut mints = vec![];
+    for _ in 0..6 {
+        mints.push(utils::create_mint(&mut context, &payer).await);
+    }
+    let mut fees = utils::fees_feature();
+    fees.mint_burn_1 = mints[0];
+    let mut additional_outputs = utils::additional_outputs_feature();
+    additional_outputs.mint_burn_1 = mints[1];
+    let mut transfer_inputs = utils::transfer_inputs_feature();
+    transfer_inputs.mint_burn_1 = mints[2];
+    let mut max_supply = utils::max_supply_feature();
+    max_supply.mint_burn_1 = mints[3];
+    let mut sol_payment = utils::sol_payment_feature();
+    sol_payment.mint_burn_1 = mints[4];
+    let mut wisdom = utils::wisdom_feature();
+    wisdom.mint_burn_1 = mints[5];
+    let features = [
+        Feature::Fees(fees),
+        Feature::AdditionalOutputs(additional_outputs),
+        Feature::TransferInputs(transfer_inputs),
+        Feature::MaxSupply(max_supply),
+        Feature::SolPayment(sol_payment),
+        Feature::Wisdom(wisdom),
+    ];
+
+    // When the owner burns one token of each mint.
+    for (feature, mint) in features.into_iter().zip(mints.iter()) {
+        let (feature_pda, _) = find_feature_pda(&feature);
+        utils::set_feature(&mut context, feature).await;
+        let token = utils::mint_to(&mut context, mint, &owner.pubkey(), 1).await;
+        unlock_feature(&mut context, &recipe, &feature_pda, &owner, mint)
+            .await
+            .unwrap();
+        assert_eq!(utils::get_token_balance(&mut context, &token).await, 0);
+    }
+
+    // Then every feature reached level 1.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        recipe_account.feature_levels,
+        FeatureLevels {
+            fees: 1,
+            additional_outputs: 1,
+            transfer_inputs: 1,
+            max_supply: 1,
+            sol_payment: 1,
+            wisdom: 1,
+            ..FeatureLevels::default()
+        }
+    );
+}
+
+#[tokio::test]
+async fn unlock_custom_fees_with_a_skill_token() {
+    // Given a fees feature whose second skill mint is a real mint.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let mut fees = utils::fees_feature();
+    fees.mint_skill_2 = mint;
+    let feature = Feature::Fees(fees);
+    let (feature_pda, _) = find_feature_pda(&feature);
+    utils::set_feature(&mut context, feature).await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    let token = utils::mint_to(&mut context, &mint, &owner.pubkey(), 1).await;
+
+    // When the owner uses its skill token.
+    unlock_feature(&mut context, &recipe, &feature_pda, &owner, &mint)
+        .await
+        .unwrap();
+
+    // Then the recipe reached level 10 with base fees as custom fees.
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.feature_levels.fees, 10);
+    assert_eq!(recipe_account.fees, DEFAULT_BASE_FEES);
+
+    // And the skill token was kept.
+    assert_eq!(utils::get_token_balance(&mut context, &token).await, 1);
+}
+
+#[tokio::test]
+async fn cannot_unlock_a_feature_above_its_maximum_level() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let mut max_supply = utils::max_supply_feature();
+    max_supply.mint_skill_1 = mint;
+    let feature = Feature::MaxSupply(max_supply);
+    let (feature_pda, _) = find_feature_pda(&feature);
+    utils::set_feature(&mut context, feature).await;
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.max_supply = 1;
+    })
+    .await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::mint_to(&mut context, &mint, &owner.pubkey(), 1).await;
+
+    let result = unlock_feature(&mut context, &recipe, &feature_pda, &owner, &mint).await;
+
+    utils::assert_program_error(result, TokenRecipesError::MaxFeatureLevelReached);
+}
+
+#[tokio::test]
+async fn cannot_unlock_a_feature_with_another_mint() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let feature = Feature::Wisdom(utils::wisdom_feature());
+    let (feature_pda, _) = find_feature_pda(&feature);
+    utils::set_feature(&mut context, feature).await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::mint_to(&mut context, &mint, &owner.pubkey(), 1).await;
+
+    let result = unlock_feature(&mut context, &recipe, &feature_pda, &owner, &mint).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidMintToLevelUpFeature);
+}
+
+#[tokio::test]
+async fn cannot_unlock_a_feature_from_an_account_that_is_not_a_feature() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let recipe = utils::create_recipe(&mut context).await;
+    let other_recipe = utils::create_recipe(&mut context).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::mint_to(&mut context, &mint, &owner.pubkey(), 1).await;
+
+    let result = unlock_feature(&mut context, &recipe, &other_recipe, &owner, &mint).await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidAccountKey);
+}
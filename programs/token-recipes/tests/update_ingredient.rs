@@ -0,0 +1,243 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::{tokio, BanksClientError, ProgramTestContext};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use token_recipes::{
+    error::TokenRecipesError,
+    state::{
+        ingredient_input::IngredientInput, ingredient_output::IngredientOutput,
+        recipe::IngredientType,
+    },
+};
+
+#[allow(clippy::too_many_arguments)]
+async fn update_ingredient(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    mint: &Pubkey,
+    ingredient_type: IngredientType,
+    new_ingredient_type: Option<IngredientType>,
+    amount: Option<u64>,
+    destination: Option<Pubkey>,
+    max_supply: Option<u64>,
+) -> Result<(), BanksClientError> {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::update_ingredient(
+        recipe,
+        Some(mint),
+        &payer,
+        &payer,
+        ingredient_type,
+        new_ingredient_type,
+        amount,
+        destination,
+        max_supply,
+    );
+    utils::process(context, &[ix], &[]).await
+}
+
+#[tokio::test]
+async fn update_a_burn_input_to_a_transfer_input() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.transfer_inputs = 1;
+    })
+    .await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let destination = Pubkey::new_unique();
+
+    update_ingredient(
+        &mut context,
+        &recipe,
+        &mint,
+        IngredientType::BurnTokenInput,
+        Some(IngredientType::TransferTokenInput),
+        Some(3),
+        Some(destination),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        recipe_account.inputs,
+        vec![IngredientInput::TransferToken {
+            mint,
+            amount: 3,
+            destination
+        }]
+    );
+}
+
+#[tokio::test]
+async fn update_a_mint_output_to_a_max_supply_output() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::update_recipe(&mut context, &recipe, |recipe| {
+        recipe.feature_levels.max_supply = 1;
+    })
+    .await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let mint_output = IngredientType::MintTokenOutput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        mint_output,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    update_ingredient(
+        &mut context,
+        &recipe,
+        &mint,
+        IngredientType::MintTokenOutput,
+        Some(IngredientType::MintTokenWithMaxSupplyOutput),
+        None,
+        None,
+        Some(10),
+    )
+    .await
+    .unwrap();
+
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(
+        recipe_account.outputs,
+        vec![IngredientOutput::MintTokenWithMaxSupply {
+            mint,
+            amount: 1,
+            max_supply: 10
+        }]
+    );
+}
+
+#[tokio::test]
+async fn cannot_update_an_input_to_an_output() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        burn_input,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let result = update_ingredient(
+        &mut context,
+        &recipe,
+        &mint,
+        IngredientType::BurnTokenInput,
+        Some(IngredientType::MintTokenOutput),
+        None,
+        None,
+        None,
+    )
+    .await;
+
+    utils::assert_program_error(result, TokenRecipesError::InvalidIngredientUpdate);
+}
+
+#[tokio::test]
+async fn cannot_update_an_ingredient_with_invalid_arguments() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let input_mint = utils::create_mint(&mut context, &payer).await;
+    let output_mint = utils::create_mint(&mut context, &payer).await;
+    let burn_input = IngredientType::BurnTokenInput;
+    let mint_output = IngredientType::MintTokenOutput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&input_mint),
+        burn_input,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&output_mint),
+        mint_output,
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // A zero amount.
+    let result = update_ingredient(
+        &mut context,
+        &recipe,
+        &input_mint,
+        IngredientType::BurnTokenInput,
+        None,
+        Some(0),
+        None,
+        None,
+    )
+    .await;
+    utils::assert_program_error(result, TokenRecipesError::CannotAddIngredientWithZeroAmount);
+
+    // A transfer input without destination.
+    let result = update_ingredient(
+        &mut context,
+        &recipe,
+        &input_mint,
+        IngredientType::BurnTokenInput,
+        Some(IngredientType::TransferTokenInput),
+        None,
+        None,
+        None,
+    )
+    .await;
+    utils::assert_program_error(result, TokenRecipesError::MissingDestinationArgument);
+
+    // A max supply output without max supply.
+    let result = update_ingredient(
+        &mut context,
+        &recipe,
+        &output_mint,
+        IngredientType::MintTokenOutput,
+        Some(IngredientType::MintTokenWithMaxSupplyOutput),
+        None,
+        None,
+        None,
+    )
+    .await;
+    utils::assert_program_error(result, TokenRecipesError::MissingMaxSupplyArgument);
+}
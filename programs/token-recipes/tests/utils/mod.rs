@@ -0,0 +1,441 @@
+#![allow(dead_code)]
+
+pub mod runtime;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    bpf_loader_upgradeable, program_pack::Pack, pubkey::Pubkey, rent::Rent, system_instruction,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::{Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use token_recipes::{
+    error::TokenRecipesError,
+    pda::{
+        find_associated_token_address, find_feature_pda, find_program_data_address, find_recipe_pda,
+    },
+    state::{
+        features::{
+            additional_outputs::AdditionalOutputsFeature,
+            fees::{FeesFeature, DEFAULT_BASE_FEES, DEFAULT_FEE_PERCENTAGES},
+            max_supply::MaxSupplyFeature,
+            sol_payment::SolPaymentFeature,
+            transfer_inputs::TransferInputsFeature,
+            wisdom::WisdomFeature,
+            Feature,
+        },
+        key::Key,
+        recipe::{IngredientType, Recipe},
+    },
+};
+
+/// A program test with a default fees feature, which is required to craft recipes.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("token_recipes", token_recipes::id(), None);
+    let (address, account) = feature_account(Feature::Fees(fees_feature()));
+    program_test.add_account(address, account);
+    program_test
+}
+
+pub async fn start() -> ProgramTestContext {
+    program_test().start_with_context().await
+}
+
+/// Assert that the first instruction of a transaction failed with the given program error.
+pub fn assert_program_error(result: Result<(), BanksClientError>, error: TokenRecipesError) {
+    assert_instruction_error(result, InstructionError::Custom(error as u32));
+}
+
+/// Assert that the first instruction of a transaction failed with the given instruction error.
+pub fn assert_instruction_error(result: Result<(), BanksClientError>, error: InstructionError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, error)
+    );
+}
+
+/// Send a transaction paid by the context payer and signed by the given signers.
+pub async fn process(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn get_account(context: &mut ProgramTestContext, address: &Pubkey) -> Option<Account> {
+    context.banks_client.get_account(*address).await.unwrap()
+}
+
+pub async fn get_lamports(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    get_account(context, address)
+        .await
+        .map_or(0, |account| account.lamports)
+}
+
+/// Create a new funded keypair.
+pub async fn create_funded_keypair(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    let ix = system_instruction::transfer(&context.payer.pubkey(), &keypair.pubkey(), lamports);
+    process(context, &[ix], &[]).await.unwrap();
+    keypair
+}
+
+/// Create a new SPL Token mint with no decimals.
+pub async fn create_mint(context: &mut ProgramTestContext, mint_authority: &Pubkey) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            mint_authority,
+            None,
+            0,
+        )
+        .unwrap(),
+    ];
+    process(context, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Create the associated token account of the owner, if needed, and mint
+/// tokens to it. The context payer must be the mint authority of the mint.
+pub async fn mint_to(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let token = find_associated_token_address(owner, mint, &spl_token::id());
+    let mut ixs = vec![];
+    if get_account(context, &token).await.is_none() {
+        ixs.push(
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer,
+                owner,
+                mint,
+                &spl_token::id(),
+            ),
+        );
+    }
+    if amount > 0 {
+        ixs.push(
+            spl_token::instruction::mint_to(&spl_token::id(), mint, &token, &payer, &[], amount)
+                .unwrap(),
+        );
+    }
+    if !ixs.is_empty() {
+        process(context, &ixs, &[]).await.unwrap();
+    }
+    token
+}
+
+/// Returns the token balance of a token account, or 0 if it does not exist.
+pub async fn get_token_balance(context: &mut ProgramTestContext, token: &Pubkey) -> u64 {
+    get_account(context, token).await.map_or(0, |account| {
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    })
+}
+
+pub async fn get_mint(context: &mut ProgramTestContext, mint: &Pubkey) -> spl_token::state::Mint {
+    let account = get_account(context, mint).await.unwrap();
+    spl_token::state::Mint::unpack(&account.data).unwrap()
+}
+
+/// Create a new recipe whose authority is the context payer.
+pub async fn create_recipe(context: &mut ProgramTestContext) -> Pubkey {
+    let payer = context.payer.pubkey();
+    create_recipe_with_authority(context, &payer).await
+}
+
+pub async fn create_recipe_with_authority(
+    context: &mut ProgramTestContext,
+    authority: &Pubkey,
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let base = Keypair::new();
+    let ix = token_recipes::instruction::create_recipe(&base.pubkey(), authority, &payer);
+    process(context, &[ix], &[&base]).await.unwrap();
+    find_recipe_pda(&base.pubkey()).0
+}
+
+pub async fn get_recipe(context: &mut ProgramTestContext, recipe: &Pubkey) -> Recipe {
+    let account = get_account(context, recipe).await.unwrap();
+    Recipe::deserialize(&mut account.data.as_ref()).unwrap()
+}
+
+/// Overwrite the fixed-size fields of a recipe, e.g. to set its feature levels
+/// without unlocking them. The ingredients of the recipe must not be changed.
+pub async fn update_recipe<F: FnOnce(&mut Recipe)>(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    update: F,
+) {
+    let mut account = get_account(context, recipe).await.unwrap();
+    let mut recipe_account = Recipe::deserialize(&mut account.data.as_ref()).unwrap();
+    update(&mut recipe_account);
+    let data = recipe_account.try_to_vec().unwrap();
+    assert_eq!(data.len(), account.data.len());
+    account.data = data;
+    context.set_account(recipe, &AccountSharedData::from(account));
+}
+
+/// Add an ingredient to a recipe whose authority is the context payer.
+pub async fn add_ingredient(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    mint: Option<&Pubkey>,
+    ingredient_type: IngredientType,
+    amount: u64,
+    destination: Option<Pubkey>,
+    max_supply: Option<u64>,
+) -> Result<(), BanksClientError> {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::add_ingredient(
+        recipe,
+        mint,
+        &payer,
+        &payer,
+        &spl_token::id(),
+        ingredient_type,
+        amount,
+        destination,
+        max_supply,
+    );
+    process(context, &[ix], &[]).await
+}
+
+pub async fn activate_recipe(context: &mut ProgramTestContext, recipe: &Pubkey) {
+    let payer = context.payer.pubkey();
+    let ix = token_recipes::instruction::activate_recipe(recipe, &payer);
+    process(context, &[ix], &[]).await.unwrap();
+}
+
+/// Craft a recipe using the associated token accounts of the owner.
+pub async fn craft(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    owner: &Keypair,
+    quantity: u64,
+) -> Result<(), BanksClientError> {
+    craft_with_proof(context, recipe, owner, quantity, vec![]).await
+}
+
+pub async fn craft_with_proof(
+    context: &mut ProgramTestContext,
+    recipe: &Pubkey,
+    owner: &Keypair,
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<(), BanksClientError> {
+    let recipe_account = get_recipe(context, recipe).await;
+    let ix = token_recipes::instruction::craft(
+        recipe,
+        &recipe_account,
+        &owner.pubkey(),
+        &owner.pubkey(),
+        quantity,
+        proof,
+    );
+    process(context, &[ix], &[owner]).await
+}
+
+/// Returns the address and account of a feature with its canonical bump and current version.
+pub fn feature_account(mut feature: Feature) -> (Pubkey, Account) {
+    let (address, bump) = find_feature_pda(&feature);
+    let data = match &mut feature {
+        Feature::Fees(f) => {
+            (f.bump, f.version) = (bump, FeesFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::AdditionalOutputs(f) => {
+            (f.bump, f.version) = (bump, AdditionalOutputsFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::TransferInputs(f) => {
+            (f.bump, f.version) = (bump, TransferInputsFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::MaxSupply(f) => {
+            (f.bump, f.version) = (bump, MaxSupplyFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::SolPayment(f) => {
+            (f.bump, f.version) = (bump, SolPaymentFeature::VERSION);
+            f.try_to_vec()
+        }
+        Feature::Wisdom(f) => {
+            (f.bump, f.version) = (bump, WisdomFeature::VERSION);
+            f.try_to_vec()
+        }
+    }
+    .unwrap();
+    let account = Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: token_recipes::id(),
+        ..Account::default()
+    };
+    (address, account)
+}
+
+/// Store a feature account at its PDA with its canonical bump and current version.
+pub async fn set_feature(context: &mut ProgramTestContext, feature: Feature) {
+    let (address, account) = feature_account(feature);
+    context.set_account(&address, &AccountSharedData::from(account));
+}
+
+/// Store the ProgramData account of the program with the given upgrade authority.
+/// Programs added to a `ProgramTest` are not upgradeable and have no ProgramData account.
+pub fn set_upgrade_authority(context: &mut ProgramTestContext, upgrade_authority: &Pubkey) {
+    // The bincode layout of `UpgradeableLoaderState::ProgramData` at slot 0.
+    let mut data = vec![3, 0, 0, 0];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(upgrade_authority.as_ref());
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: bpf_loader_upgradeable::id(),
+        ..Account::default()
+    };
+    context.set_account(
+        &find_program_data_address(),
+        &AccountSharedData::from(account),
+    );
+}
+
+/// Initialize the program config with a new admin, returned as a keypair.
+pub async fn initialize_program_config(context: &mut ProgramTestContext) -> Keypair {
+    let admin = Keypair::new();
+    let upgrade_authority = Keypair::new();
+    set_upgrade_authority(context, &upgrade_authority.pubkey());
+    let ix = token_recipes::instruction::initialize_program_config(
+        &admin.pubkey(),
+        &upgrade_authority.pubkey(),
+        &context.payer.pubkey(),
+    );
+    process(context, &[ix], &[&upgrade_authority])
+        .await
+        .unwrap();
+    admin
+}
+
+/// A fees feature with the default fees whose mints are all random addresses.
+pub fn fees_feature() -> FeesFeature {
+    FeesFeature {
+        key: Key::FeesFeature,
+        admin_destination: Pubkey::new_unique(),
+        shard_mint: Pubkey::new_unique(),
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        mint_burn_3: Pubkey::new_unique(),
+        mint_burn_4: Pubkey::new_unique(),
+        mint_burn_5: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        mint_skill_2: Pubkey::new_unique(),
+        mint_skill_3: Pubkey::new_unique(),
+        bump: 0,
+        version: 0,
+        base_fees: DEFAULT_BASE_FEES,
+        fee_percentages: DEFAULT_FEE_PERCENTAGES,
+    }
+}
+
+/// An additional outputs feature whose mints are all random addresses.
+pub fn additional_outputs_feature() -> AdditionalOutputsFeature {
+    AdditionalOutputsFeature {
+        key: Key::AdditionalOutputsFeature,
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        mint_burn_3: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        mint_skill_2: Pubkey::new_unique(),
+        bump: 0,
+        version: 0,
+    }
+}
+
+/// A transfer inputs feature whose mints are all random addresses.
+pub fn transfer_inputs_feature() -> TransferInputsFeature {
+    TransferInputsFeature {
+        key: Key::TransferInputsFeature,
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        mint_burn_3: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        mint_skill_2: Pubkey::new_unique(),
+        bump: 0,
+        version: 0,
+    }
+}
+
+/// A max supply feature whose mints are all random addresses.
+pub fn max_supply_feature() -> MaxSupplyFeature {
+    MaxSupplyFeature {
+        key: Key::MaxSupplyFeature,
+        mint_burn_1: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        bump: 0,
+        version: 0,
+    }
+}
+
+/// A SOL payment feature whose mints are all random addresses.
+pub fn sol_payment_feature() -> SolPaymentFeature {
+    SolPaymentFeature {
+        key: Key::SolPaymentFeature,
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        mint_burn_3: Pubkey::new_unique(),
+        mint_burn_4: Pubkey::new_unique(),
+        mint_burn_5: Pubkey::new_unique(),
+        mint_burn_6: Pubkey::new_unique(),
+        mint_burn_7: Pubkey::new_unique(),
+        mint_burn_8: Pubkey::new_unique(),
+        mint_burn_9: Pubkey::new_unique(),
+        mint_skill_1: Pubkey::new_unique(),
+        mint_skill_2: Pubkey::new_unique(),
+        mint_skill_3: Pubkey::new_unique(),
+        mint_skill_4: Pubkey::new_unique(),
+        mint_skill_5: Pubkey::new_unique(),
+        bump: 0,
+        version: 0,
+    }
+}
+
+/// A wisdom feature whose mints are all random addresses.
+pub fn wisdom_feature() -> WisdomFeature {
+    WisdomFeature {
+        key: Key::WisdomFeature,
+        experience_mint: Pubkey::new_unique(),
+        mint_burn_1: Pubkey::new_unique(),
+        mint_burn_2: Pubkey::new_unique(),
+        bump: 0,
+        version: 0,
+    }
+}
@@ -0,0 +1,196 @@
+//! Run the program natively, without a bank, for property-based tests and fuzzing.
+//!
+//! Accounts are serialized into an input buffer exactly like the runtime does before
+//! invoking a program, so the resulting `AccountInfo`s can be reallocated safely.
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{self, ProgramResult, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{self, SyscallStubs},
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use std::sync::Once;
+
+/// An account passed to the program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+}
+
+/// Invoke the processor with the given accounts and write their new state back into them.
+pub fn process_instruction(accounts: &mut [TestAccount], instruction_data: &[u8]) -> ProgramResult {
+    with_account_infos(accounts, instruction_data, |program_id, accounts, data| {
+        token_recipes::processor::Processor::process_instruction(program_id, accounts, data)
+    })
+}
+
+/// Call `f` with the `AccountInfo`s of the given accounts and write their new state back into them.
+///
+/// Accounts sharing the same key are passed as duplicates of the first one.
+/// Each call behaves like a separate instruction: account data may grow by at most
+/// `MAX_PERMITTED_DATA_INCREASE` bytes.
+pub fn with_account_infos<R>(
+    accounts: &mut [TestAccount],
+    instruction_data: &[u8],
+    f: impl for<'a> FnOnce(&Pubkey, &'a [AccountInfo<'a>], &[u8]) -> R,
+) -> R {
+    set_syscall_stubs();
+    let mut input = serialize_input(&token_recipes::id(), accounts, instruction_data);
+    let (program_id, account_infos, data) =
+        unsafe { entrypoint::deserialize(input.as_mut_ptr() as *mut u8) };
+    let result = f(program_id, &account_infos, data);
+
+    for (account, account_info) in accounts.iter_mut().zip(account_infos.iter()) {
+        account.owner = *account_info.owner;
+        account.lamports = account_info.lamports();
+        account.data = account_info.data.borrow().to_vec();
+    }
+    result
+}
+
+/// Serialize the program input in a buffer aligned on 8 bytes, as expected by `entrypoint::deserialize`.
+fn serialize_input(program_id: &Pubkey, accounts: &[TestAccount], data: &[u8]) -> Vec<u64> {
+    let mut bytes: Vec<u8> = vec![];
+    bytes.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for (index, account) in accounts.iter().enumerate() {
+        if let Some(position) = accounts[..index].iter().position(|a| a.key == account.key) {
+            bytes.push(position as u8);
+            bytes.extend_from_slice(&[0; 7]);
+            continue;
+        }
+        bytes.push(NON_DUP_MARKER);
+        bytes.push(account.is_signer as u8);
+        bytes.push(account.is_writable as u8);
+        bytes.push(account.executable as u8);
+        bytes.extend_from_slice(&[0; 4]); // original_data_len
+        bytes.extend_from_slice(account.key.as_ref());
+        bytes.extend_from_slice(account.owner.as_ref());
+        bytes.extend_from_slice(&account.lamports.to_le_bytes());
+        bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&account.data);
+        bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // rent_epoch
+    }
+    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(program_id.as_ref());
+
+    let mut input = vec![0u64; bytes.len().div_ceil(8)];
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), input.as_mut_ptr() as *mut u8, bytes.len());
+    }
+    input
+}
+
+/// Install syscall stubs providing the default rent and clock sysvars and simulating
+/// the system program. Other cross-program invocations succeed without any effect.
+pub fn set_syscall_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(TestSyscallStubs));
+    });
+}
+
+struct TestSyscallStubs;
+
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id != system_program::id() {
+            return Ok(());
+        }
+        let account = |index: usize| {
+            let key = instruction
+                .accounts
+                .get(index)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?
+                .pubkey;
+            account_infos
+                .iter()
+                .find(|account_info| *account_info.key == key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        let transfer = |from: &AccountInfo, to: &AccountInfo, lamports: u64| {
+            let from_lamports = from
+                .lamports()
+                .checked_sub(lamports)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            **from.try_borrow_mut_lamports()? = from_lamports;
+            let to_lamports = to
+                .lamports()
+                .checked_add(lamports)
+                .ok_or(ProgramError::InvalidArgument)?;
+            **to.try_borrow_mut_lamports()? = to_lamports;
+            Ok::<(), ProgramError>(())
+        };
+        let allocate = |account: &AccountInfo, space: u64| {
+            if !account.data_is_empty() || *account.owner != system_program::id() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            account.realloc(space as usize, true)
+        };
+        let assign = |account: &AccountInfo, owner: &Pubkey| {
+            if *account.owner != system_program::id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            account.assign(owner);
+            Ok(())
+        };
+
+        match limited_deserialize(&instruction.data, 1024)
+            .map_err(|_| ProgramError::InvalidInstructionData)?
+        {
+            SystemInstruction::Transfer { lamports } => {
+                transfer(account(0)?, account(1)?, lamports)
+            }
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => {
+                let (from, to) = (account(0)?, account(1)?);
+                if to.lamports() > 0 {
+                    return Err(ProgramError::AccountAlreadyInitialized);
+                }
+                transfer(from, to, lamports)?;
+                allocate(to, space)?;
+                assign(to, &owner)
+            }
+            SystemInstruction::Allocate { space } => allocate(account(0)?, space),
+            SystemInstruction::Assign { owner } => assign(account(0)?, &owner),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+use token_recipes::{
+    error::TokenRecipesError, pda::find_associated_token_address, state::recipe::IngredientType,
+};
+
+#[tokio::test]
+async fn deposit_and_withdraw_vault_tokens() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let token = utils::mint_to(&mut context, &mint, &payer, 10).await;
+    let vault = find_associated_token_address(&recipe, &mint, &spl_token::id());
+
+    // When we deposit 10 tokens and withdraw 3 of them.
+    let ixs = [
+        token_recipes::instruction::deposit_vault_tokens(
+            &recipe,
+            &payer,
+            &payer,
+            &mint,
+            &spl_token::id(),
+            10,
+        ),
+        token_recipes::instruction::withdraw_vault_tokens(
+            &recipe,
+            &payer,
+            &payer,
+            &mint,
+            &spl_token::id(),
+            3,
+        ),
+    ];
+    utils::process(&mut context, &ixs, &[]).await.unwrap();
+
+    // Then the vault of the recipe holds the remaining tokens.
+    assert_eq!(utils::get_token_balance(&mut context, &vault).await, 7);
+    assert_eq!(utils::get_token_balance(&mut context, &token).await, 3);
+}
+
+#[tokio::test]
+async fn cannot_withdraw_more_than_the_vault_holds() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    utils::mint_to(&mut context, &mint, &payer, 10).await;
+    let ix = token_recipes::instruction::deposit_vault_tokens(
+        &recipe,
+        &payer,
+        &payer,
+        &mint,
+        &spl_token::id(),
+        10,
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+
+    let ix = token_recipes::instruction::withdraw_vault_tokens(
+        &recipe,
+        &payer,
+        &payer,
+        &mint,
+        &spl_token::id(),
+        11,
+    );
+    let result = utils::process(&mut context, &[ix], &[]).await;
+
+    utils::assert_program_error(result, TokenRecipesError::NotEnoughTokensInVault);
+}
+
+#[tokio::test]
+async fn cannot_craft_more_than_the_vault_holds() {
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let recipe = utils::create_recipe(&mut context).await;
+    let mint = utils::create_mint(&mut context, &payer).await;
+    let transfer_output = IngredientType::TransferTokenOutput;
+    utils::add_ingredient(
+        &mut context,
+        &recipe,
+        Some(&mint),
+        transfer_output,
+        2,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    utils::mint_to(&mut context, &mint, &payer, 3).await;
+    let ix = token_recipes::instruction::deposit_vault_tokens(
+        &recipe,
+        &payer,
+        &payer,
+        &mint,
+        &spl_token::id(),
+        3,
+    );
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    let result = utils::craft(&mut context, &recipe, &owner, 2).await;
+
+    utils::assert_program_error(result, TokenRecipesError::NotEnoughTokensInVault);
+}
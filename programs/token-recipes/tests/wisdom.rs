@@ -0,0 +1,84 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+use token_recipes::{
+    pda::{find_associated_token_address, find_wisdom_feature_pda},
+    state::features::Feature,
+};
+
+#[tokio::test]
+async fn craft_at_every_wisdom_level() {
+    let mut context = utils::start().await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+
+    // (level, experience per craft).
+    let levels = [
+        (0, 100),
+        (1, 125),
+        (2, 150),
+        (3, 175),
+        (4, 200),
+        (5, 250),
+        (6, 300),
+    ];
+    for (level, experience) in levels {
+        let recipe = utils::create_recipe(&mut context).await;
+        utils::update_recipe(&mut context, &recipe, |recipe| {
+            recipe.feature_levels.wisdom = level;
+        })
+        .await;
+        utils::activate_recipe(&mut context, &recipe).await;
+
+        // Experience is gained once per craft regardless of the quantity.
+        utils::craft(&mut context, &recipe, &owner, 2)
+            .await
+            .unwrap();
+
+        let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+        assert_eq!(
+            recipe_account.accumulated_experience, experience,
+            "level {level}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn collect_experience() {
+    // Given a wisdom feature whose experience mint is owned by the wisdom feature PDA.
+    let mut context = utils::start().await;
+    let payer = context.payer.pubkey();
+    let (wisdom_feature_pda, _) = find_wisdom_feature_pda();
+    let experience_mint = utils::create_mint(&mut context, &wisdom_feature_pda).await;
+    let mut wisdom_feature = utils::wisdom_feature();
+    wisdom_feature.experience_mint = experience_mint;
+    utils::set_feature(&mut context, Feature::Wisdom(wisdom_feature)).await;
+
+    // And a recipe crafted twice.
+    let recipe = utils::create_recipe(&mut context).await;
+    utils::activate_recipe(&mut context, &recipe).await;
+    let owner = utils::create_funded_keypair(&mut context, 1_000_000_000).await;
+    utils::craft(&mut context, &recipe, &owner, 1)
+        .await
+        .unwrap();
+    utils::craft(&mut context, &recipe, &owner, 2)
+        .await
+        .unwrap();
+
+    // When the authority collects the experience.
+    let ix =
+        token_recipes::instruction::collect_experience(&recipe, &payer, &payer, &experience_mint);
+    utils::process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Then the experience was minted to the authority.
+    let experience_token =
+        find_associated_token_address(&payer, &experience_mint, &spl_token::id());
+    assert_eq!(
+        utils::get_token_balance(&mut context, &experience_token).await,
+        200
+    );
+    let recipe_account = utils::get_recipe(&mut context, &recipe).await;
+    assert_eq!(recipe_account.accumulated_experience, 0);
+}